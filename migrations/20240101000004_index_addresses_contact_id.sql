@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS idx_addresses_contact_id ON addresses(contact_id);
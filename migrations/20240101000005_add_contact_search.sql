@@ -0,0 +1,25 @@
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+-- 'simple' keeps names, emails and phone numbers unstemmed.
+ALTER TABLE contacts
+    ADD COLUMN IF NOT EXISTS search_text TEXT GENERATED ALWAYS AS (
+        lower(first_name || ' ' || coalesce(last_name, '') || ' ' || coalesce(email, '') || ' ' || coalesce(phone, ''))
+    ) STORED,
+    ADD COLUMN IF NOT EXISTS search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple'::regconfig, first_name || ' ' || coalesce(last_name, '')), 'A') ||
+        setweight(to_tsvector('simple'::regconfig, coalesce(email, '') || ' ' || coalesce(phone, '')), 'B')
+    ) STORED;
+
+ALTER TABLE addresses
+    ADD COLUMN IF NOT EXISTS search_text TEXT GENERATED ALWAYS AS (
+        lower(coalesce(street, '') || ' ' || coalesce(city, '') || ' ' || coalesce(province, '') || ' ' || country || ' ' || coalesce(postal_code, ''))
+    ) STORED,
+    ADD COLUMN IF NOT EXISTS search_vector TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple'::regconfig,
+            coalesce(street, '') || ' ' || coalesce(city, '') || ' ' || coalesce(province, '') || ' ' || country || ' ' || coalesce(postal_code, '')), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_contacts_search_vector ON contacts USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_contacts_search_text_trgm ON contacts USING GIN (search_text gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_addresses_search_vector ON addresses USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_addresses_search_text_trgm ON addresses USING GIN (search_text gin_trgm_ops);
@@ -0,0 +1,12 @@
+-- One row per change to a contact or its addresses. `snapshot` holds the whole
+-- contact (with addresses) as it was after the change.
+CREATE TABLE IF NOT EXISTS contact_versions (
+    id UUID PRIMARY KEY,
+    contact_id UUID NOT NULL REFERENCES contacts(id) ON DELETE CASCADE,
+    version INTEGER NOT NULL,
+    action VARCHAR(32) NOT NULL,
+    actor_id UUID REFERENCES users(id) ON DELETE SET NULL,
+    snapshot JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    UNIQUE (contact_id, version)
+);
@@ -0,0 +1,2 @@
+-- Bumped on every write; exposed as the contact's ETag for optimistic concurrency.
+ALTER TABLE contacts ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
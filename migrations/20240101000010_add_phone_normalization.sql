@@ -0,0 +1,12 @@
+-- Region (ISO 3166-1 alpha-2) used to read contact phone numbers written
+-- without a country code.
+ALTER TABLE users ADD COLUMN phone_region VARCHAR(2);
+
+-- `phone` now holds the E.164 form; `phone_raw` keeps what the user typed.
+-- Existing numbers are kept as entered until the contact is next edited.
+ALTER TABLE contacts ADD COLUMN phone_raw VARCHAR(64);
+UPDATE contacts SET phone_raw = phone WHERE phone IS NOT NULL;
+
+-- Phone filters match on digits only.
+CREATE INDEX idx_contacts_phone_digits ON contacts
+    USING GIN (regexp_replace(phone, '[^0-9]', '', 'g') gin_trgm_ops);
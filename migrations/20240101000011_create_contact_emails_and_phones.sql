@@ -0,0 +1,44 @@
+-- A contact can have several labelled emails and phone numbers. The
+-- `contacts.email`, `contacts.phone` and `contacts.phone_raw` columns stay
+-- as a copy of the primary entry, so sorting and search keep working on the
+-- contact row.
+CREATE TABLE IF NOT EXISTS contact_emails (
+    id UUID PRIMARY KEY,
+    contact_id UUID NOT NULL REFERENCES contacts(id) ON DELETE CASCADE,
+    email VARCHAR(100) NOT NULL,
+    label VARCHAR(20) NOT NULL DEFAULT 'other',
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS contact_phones (
+    id UUID PRIMARY KEY,
+    contact_id UUID NOT NULL REFERENCES contacts(id) ON DELETE CASCADE,
+    -- E.164, with the number as entered alongside, as on contacts.
+    phone VARCHAR(20) NOT NULL,
+    phone_raw VARCHAR(64),
+    label VARCHAR(20) NOT NULL DEFAULT 'other',
+    is_primary BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_contact_emails_contact_id ON contact_emails (contact_id);
+CREATE INDEX IF NOT EXISTS idx_contact_phones_contact_id ON contact_phones (contact_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_contact_emails_primary ON contact_emails (contact_id) WHERE is_primary;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_contact_phones_primary ON contact_phones (contact_id) WHERE is_primary;
+
+-- Email and phone filters match any entry, not just the primary one.
+CREATE INDEX IF NOT EXISTS idx_contact_emails_email_trgm ON contact_emails USING GIN (email gin_trgm_ops);
+DROP INDEX IF EXISTS idx_contacts_phone_digits;
+CREATE INDEX IF NOT EXISTS idx_contact_phones_phone_digits ON contact_phones
+    USING GIN (regexp_replace(phone, '[^0-9]', '', 'g') gin_trgm_ops);
+
+INSERT INTO contact_emails (id, contact_id, email, label, is_primary, created_at, updated_at)
+SELECT gen_random_uuid(), id, email, 'other', TRUE, created_at, updated_at
+FROM contacts WHERE email IS NOT NULL;
+
+INSERT INTO contact_phones (id, contact_id, phone, phone_raw, label, is_primary, created_at, updated_at)
+SELECT gen_random_uuid(), id, phone, phone_raw, 'other', TRUE, created_at, updated_at
+FROM contacts WHERE phone IS NOT NULL;
@@ -0,0 +1,59 @@
+-- Addresses get a kind and, like emails and phones, exactly one primary
+-- address per contact.
+ALTER TABLE addresses
+    ADD COLUMN IF NOT EXISTS kind VARCHAR(20) NOT NULL DEFAULT 'other',
+    ADD COLUMN IF NOT EXISTS is_primary BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE addresses SET is_primary = TRUE
+WHERE id IN (SELECT DISTINCT ON (contact_id) id FROM addresses ORDER BY contact_id, created_at, id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_addresses_primary ON addresses (contact_id) WHERE is_primary;
+
+-- Countries are stored as ISO 3166-1 alpha-2 codes. Codes that were already
+-- entered are upper-cased and common country names mapped; anything else is
+-- kept as entered and must be corrected on the next edit of that address.
+UPDATE addresses SET country = upper(trim(country)) WHERE trim(country) ~ '^[A-Za-z]{2}$';
+
+UPDATE addresses a SET country = n.code
+FROM (VALUES
+    ('indonesia', 'ID'),
+    ('malaysia', 'MY'),
+    ('singapore', 'SG'),
+    ('thailand', 'TH'),
+    ('philippines', 'PH'),
+    ('vietnam', 'VN'),
+    ('viet nam', 'VN'),
+    ('japan', 'JP'),
+    ('china', 'CN'),
+    ('south korea', 'KR'),
+    ('korea', 'KR'),
+    ('india', 'IN'),
+    ('australia', 'AU'),
+    ('new zealand', 'NZ'),
+    ('united states', 'US'),
+    ('united states of america', 'US'),
+    ('usa', 'US'),
+    ('canada', 'CA'),
+    ('mexico', 'MX'),
+    ('brazil', 'BR'),
+    ('united kingdom', 'GB'),
+    ('uk', 'GB'),
+    ('great britain', 'GB'),
+    ('ireland', 'IE'),
+    ('germany', 'DE'),
+    ('france', 'FR'),
+    ('netherlands', 'NL'),
+    ('belgium', 'BE'),
+    ('spain', 'ES'),
+    ('portugal', 'PT'),
+    ('italy', 'IT'),
+    ('switzerland', 'CH'),
+    ('austria', 'AT'),
+    ('sweden', 'SE'),
+    ('norway', 'NO'),
+    ('denmark', 'DK'),
+    ('poland', 'PL'),
+    ('russia', 'RU'),
+    ('south africa', 'ZA')
+) AS n(name, code)
+WHERE lower(trim(a.country)) = n.name;
@@ -0,0 +1,17 @@
+-- A share grants another user read or edit access to one contact, or to
+-- every contact in one group (including contacts added to it later).
+CREATE TABLE IF NOT EXISTS contact_shares (
+    id UUID PRIMARY KEY,
+    owner_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    grantee_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    contact_id UUID REFERENCES contacts(id) ON DELETE CASCADE,
+    group_id UUID REFERENCES contact_groups(id) ON DELETE CASCADE,
+    permission VARCHAR(10) NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    CHECK ((contact_id IS NULL) <> (group_id IS NULL))
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_contact_shares_contact_grantee ON contact_shares (contact_id, grantee_id) WHERE contact_id IS NOT NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_contact_shares_group_grantee ON contact_shares (group_id, grantee_id) WHERE group_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_contact_shares_grantee_id ON contact_shares (grantee_id);
@@ -0,0 +1,149 @@
+-- Row-level security on contacts, addresses, emails and phones, so the
+-- database itself refuses another user's rows. The policies bind only `contacts_app`: the
+-- contact repository switches to that role for each transaction and sets
+-- `app.current_user_id` to the acting user. Other connections (the table
+-- owner, the trash purge) are not restricted.
+--
+-- Creating the role needs CREATEROLE (or a superuser), which the migrating
+-- user often lacks. Where it does, run this once as an administrator first;
+-- the migration then only checks the role is there and granted:
+--
+--     CREATE ROLE contacts_app NOLOGIN;
+--     GRANT contacts_app TO <migrating user>;
+DO $$
+BEGIN
+    IF NOT EXISTS (SELECT 1 FROM pg_roles WHERE rolname = 'contacts_app') THEN
+        CREATE ROLE contacts_app NOLOGIN;
+    END IF;
+EXCEPTION
+    -- Roles are cluster-wide: another database may be creating it too.
+    WHEN duplicate_object OR unique_violation THEN NULL;
+    WHEN insufficient_privilege THEN
+        RAISE EXCEPTION 'role contacts_app does not exist and % may not create it', current_user
+            USING HINT = 'As an administrator, run: CREATE ROLE contacts_app NOLOGIN; GRANT contacts_app TO '
+                || quote_ident(current_user) || ';';
+END
+$$;
+
+DO $$
+BEGIN
+    IF NOT pg_has_role(current_user, 'contacts_app', 'MEMBER') THEN
+        EXECUTE format('GRANT contacts_app TO %I', current_user);
+    END IF;
+EXCEPTION
+    WHEN insufficient_privilege THEN
+        RAISE EXCEPTION '% is not a member of role contacts_app and may not grant it', current_user
+            USING HINT = 'As an administrator, run: GRANT contacts_app TO ' || quote_ident(current_user) || ';';
+END
+$$;
+
+-- Only what the contact repository does while switched to the role. Users,
+-- tokens and any later table stay out of its reach.
+GRANT USAGE ON SCHEMA public TO contacts_app;
+-- No UPDATE of user_id or organization_id: a policy cannot compare the new
+-- row with the old one, so without this an edit share would let its grantee
+-- move the contact into their own account or organization.
+GRANT SELECT, INSERT, DELETE ON contacts TO contacts_app;
+GRANT UPDATE (first_name, last_name, email, phone, phone_raw, custom_fields, updated_at, version, deleted_at)
+    ON contacts TO contacts_app;
+GRANT SELECT, INSERT, UPDATE, DELETE ON addresses, contact_emails, contact_phones TO contacts_app;
+GRANT INSERT ON contact_versions TO contacts_app;
+-- Merges copy tags and group memberships; filters and the policies below
+-- read the rest.
+GRANT SELECT, INSERT ON contact_tags, contact_group_members TO contacts_app;
+GRANT SELECT ON tags, contact_shares, organization_members TO contacts_app;
+
+-- NULL when unset, which matches no rows.
+CREATE OR REPLACE FUNCTION app_current_user_id() RETURNS UUID
+LANGUAGE sql STABLE AS $$
+    SELECT NULLIF(current_setting('app.current_user_id', true), '')::uuid
+$$;
+
+-- Personal contacts belong to their user, organization contacts to every
+-- member.
+CREATE OR REPLACE FUNCTION app_owns_contact(owner UUID, organization UUID) RETURNS BOOLEAN
+LANGUAGE sql STABLE AS $$
+    SELECT CASE
+        WHEN organization IS NULL THEN owner = app_current_user_id()
+        ELSE EXISTS (
+            SELECT 1 FROM organization_members m
+            WHERE m.organization_id = organization AND m.user_id = app_current_user_id()
+        )
+    END
+$$;
+
+-- Whether the contact is shared with the current user, directly or through
+-- a group, with at least `needed` ('read' or 'edit') permission.
+CREATE OR REPLACE FUNCTION app_shared_contact(contact UUID, needed VARCHAR) RETURNS BOOLEAN
+LANGUAGE sql STABLE AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM contact_shares s
+        WHERE s.grantee_id = app_current_user_id()
+          AND (s.permission = 'edit' OR needed = 'read')
+          AND (s.contact_id = contact OR s.group_id IN
+              (SELECT gm.group_id FROM contact_group_members gm WHERE gm.contact_id = contact))
+    )
+$$;
+
+-- Whether the current user may change the contact and its addresses.
+CREATE OR REPLACE FUNCTION app_can_edit_contact(contact UUID) RETURNS BOOLEAN
+LANGUAGE sql STABLE AS $$
+    SELECT EXISTS (
+        SELECT 1 FROM contacts c
+        WHERE c.id = contact
+          AND (app_owns_contact(c.user_id, c.organization_id) OR app_shared_contact(c.id, 'edit'))
+    )
+$$;
+
+ALTER TABLE contacts ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY contacts_select ON contacts FOR SELECT TO contacts_app
+    USING (app_owns_contact(user_id, organization_id) OR app_shared_contact(id, 'read'));
+CREATE POLICY contacts_insert ON contacts FOR INSERT TO contacts_app
+    WITH CHECK (user_id = app_current_user_id() AND app_owns_contact(user_id, organization_id));
+-- The owner columns cannot change (see the grants above), so a share only
+-- ever passes the check for the row it was granted on.
+CREATE POLICY contacts_update ON contacts FOR UPDATE TO contacts_app
+    USING (app_owns_contact(user_id, organization_id) OR app_shared_contact(id, 'edit'))
+    WITH CHECK (app_owns_contact(user_id, organization_id) OR app_shared_contact(id, 'edit'));
+-- Only owners delete, as with the usecases; shares allow trashing at most.
+CREATE POLICY contacts_delete ON contacts FOR DELETE TO contacts_app
+    USING (app_owns_contact(user_id, organization_id));
+
+ALTER TABLE addresses ENABLE ROW LEVEL SECURITY;
+
+-- The subquery is itself filtered by contacts_select.
+CREATE POLICY addresses_select ON addresses FOR SELECT TO contacts_app
+    USING (EXISTS (SELECT 1 FROM contacts c WHERE c.id = contact_id));
+CREATE POLICY addresses_insert ON addresses FOR INSERT TO contacts_app
+    WITH CHECK (app_can_edit_contact(contact_id));
+CREATE POLICY addresses_update ON addresses FOR UPDATE TO contacts_app
+    USING (app_can_edit_contact(contact_id))
+    WITH CHECK (app_can_edit_contact(contact_id));
+CREATE POLICY addresses_delete ON addresses FOR DELETE TO contacts_app
+    USING (app_can_edit_contact(contact_id));
+
+-- Emails and phones follow their contact like addresses.
+ALTER TABLE contact_emails ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY contact_emails_select ON contact_emails FOR SELECT TO contacts_app
+    USING (EXISTS (SELECT 1 FROM contacts c WHERE c.id = contact_id));
+CREATE POLICY contact_emails_insert ON contact_emails FOR INSERT TO contacts_app
+    WITH CHECK (app_can_edit_contact(contact_id));
+CREATE POLICY contact_emails_update ON contact_emails FOR UPDATE TO contacts_app
+    USING (app_can_edit_contact(contact_id))
+    WITH CHECK (app_can_edit_contact(contact_id));
+CREATE POLICY contact_emails_delete ON contact_emails FOR DELETE TO contacts_app
+    USING (app_can_edit_contact(contact_id));
+
+ALTER TABLE contact_phones ENABLE ROW LEVEL SECURITY;
+
+CREATE POLICY contact_phones_select ON contact_phones FOR SELECT TO contacts_app
+    USING (EXISTS (SELECT 1 FROM contacts c WHERE c.id = contact_id));
+CREATE POLICY contact_phones_insert ON contact_phones FOR INSERT TO contacts_app
+    WITH CHECK (app_can_edit_contact(contact_id));
+CREATE POLICY contact_phones_update ON contact_phones FOR UPDATE TO contacts_app
+    USING (app_can_edit_contact(contact_id))
+    WITH CHECK (app_can_edit_contact(contact_id));
+CREATE POLICY contact_phones_delete ON contact_phones FOR DELETE TO contacts_app
+    USING (app_can_edit_contact(contact_id));
@@ -0,0 +1,7 @@
+-- History entries are now numbered with the contact's own version. Lift any
+-- contact whose history already went further, so its next write cannot reuse
+-- a recorded number.
+UPDATE contacts c
+SET version = v.max_version
+FROM (SELECT contact_id, MAX(version) AS max_version FROM contact_versions GROUP BY contact_id) v
+WHERE v.contact_id = c.id AND c.version < v.max_version;
@@ -1,31 +1,104 @@
 use crate::delivery::http::handler::user_handler::AppState;
 use crate::delivery::http::router::create_router;
 use crate::infrastructure::auth::jwt::JwtService;
+use crate::infrastructure::auth::revocation::spawn_revocation_sync;
 use crate::infrastructure::db::postgres::create_pool;
 use crate::infrastructure::repository::postgres_contact_repository::PostgresContactRepository;
+use crate::infrastructure::repository::postgres_contact_version_repository::PostgresContactVersionRepository;
+use crate::infrastructure::repository::postgres_custom_field_repository::PostgresCustomFieldRepository;
+use crate::infrastructure::repository::postgres_group_repository::PostgresGroupRepository;
+use crate::infrastructure::repository::postgres_organization_repository::PostgresOrganizationRepository;
+use crate::infrastructure::repository::postgres_refresh_token_repository::PostgresRefreshTokenRepository;
+use crate::infrastructure::repository::postgres_share_repository::PostgresShareRepository;
+use crate::infrastructure::repository::postgres_tag_repository::PostgresTagRepository;
+use crate::infrastructure::repository::postgres_token_revocation_repository::PostgresTokenRevocationRepository;
 use crate::infrastructure::repository::postgres_user_repository::PostgresUserRepository;
-use crate::usecase::contact_usecase::ContactUsecase;
+use crate::usecase::contact_usecase::{spawn_trash_purge, ContactUsecase};
+use crate::usecase::custom_field_usecase::CustomFieldUsecase;
+use crate::usecase::group_usecase::GroupUsecase;
+use crate::usecase::organization_usecase::OrganizationUsecase;
+use crate::usecase::share_usecase::ShareUsecase;
+use crate::usecase::tag_usecase::TagUsecase;
 use crate::usecase::user_usecase::UserUsecase;
 use axum::Router;
 use std::net::SocketAddr;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::TcpListener;
 
 use sqlx::{Pool, Postgres};
 
 pub async fn create_app(pool: Pool<Postgres>) -> Router {
     let user_repo = Arc::new(PostgresUserRepository::new(pool.clone()));
+    let refresh_token_repo = Arc::new(PostgresRefreshTokenRepository::new(pool.clone()));
+    let token_revocation_repo = Arc::new(PostgresTokenRevocationRepository::new(pool.clone()));
+    let tag_repo = Arc::new(PostgresTagRepository::new(pool.clone()));
+    let contact_version_repo = Arc::new(PostgresContactVersionRepository::new(pool.clone()));
+    let group_repo = Arc::new(PostgresGroupRepository::new(pool.clone()));
+    let custom_field_repo = Arc::new(PostgresCustomFieldRepository::new(pool.clone()));
+    let share_repo = Arc::new(PostgresShareRepository::new(pool.clone()));
+    let organization_repo = Arc::new(PostgresOrganizationRepository::new(pool.clone()));
     let contact_repo = Arc::new(PostgresContactRepository::new(pool));
     
     let jwt_service = Arc::new(JwtService::new());
+    jwt_service
+        .revocations()
+        .reload(token_revocation_repo.as_ref())
+        .await
+        .expect("Failed to load token revocations");
+    let sync_seconds = std::env::var("TOKEN_REVOCATION_SYNC_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30);
+    spawn_revocation_sync(
+        jwt_service.revocations().clone(),
+        token_revocation_repo.clone(),
+        Duration::from_secs(sync_seconds),
+    );
     
-    let user_usecase = Arc::new(UserUsecase::new(user_repo, jwt_service.clone()));
-    let contact_usecase = Arc::new(ContactUsecase::new(contact_repo));
+    let user_usecase = Arc::new(UserUsecase::new(
+        user_repo.clone(),
+        refresh_token_repo,
+        token_revocation_repo,
+        jwt_service.clone(),
+    ));
+    let contact_usecase = Arc::new(ContactUsecase::new(
+        contact_repo.clone(),
+        tag_repo.clone(),
+        contact_version_repo,
+        user_repo.clone(),
+        custom_field_repo.clone(),
+        share_repo.clone(),
+    ));
+    let retention_days = std::env::var("TRASH_RETENTION_DAYS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(30);
+    let purge_seconds = std::env::var("TRASH_PURGE_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(3600);
+    spawn_trash_purge(
+        contact_usecase.clone(),
+        chrono::Duration::days(retention_days),
+        Duration::from_secs(purge_seconds),
+    );
+    let tag_usecase = Arc::new(TagUsecase::new(tag_repo, contact_repo.clone()));
+    let share_usecase = Arc::new(ShareUsecase::new(share_repo, contact_repo.clone(), group_repo.clone(), user_repo.clone()));
+    let organization_usecase = Arc::new(OrganizationUsecase::new(organization_repo, user_repo));
+    let group_usecase = Arc::new(GroupUsecase::new(group_repo, contact_repo));
+    let custom_field_usecase = Arc::new(CustomFieldUsecase::new(custom_field_repo));
 
     let app_state = Arc::new(AppState { 
         user_usecase,
         contact_usecase,
+        tag_usecase,
+        group_usecase,
+        custom_field_usecase,
+        share_usecase,
+        organization_usecase,
         jwt_service,
+        require_if_match: std::env::var("REQUIRE_IF_MATCH").is_ok_and(|v| v == "true"),
     });
 
     create_router(app_state)
@@ -0,0 +1,118 @@
+use crate::delivery::http::handler::user_handler::AppState;
+use crate::domain::error::DomainError;
+use crate::domain::repository::contact_repository::Tenant;
+use crate::infrastructure::auth::jwt::Claims;
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderName},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+// Selects an organization the caller belongs to as the address book for the
+// request; without it contact routes work on the caller's personal contacts.
+pub static ORGANIZATION_HEADER: HeaderName = HeaderName::from_static("x-organization-id");
+
+// The authenticated principal for a request. Taking `AuthUser` as a handler
+// argument is all a route needs to be protected: the bearer token is
+// verified and the user is confirmed to still exist before the handler runs.
+#[derive(Debug, Clone)]
+pub struct AuthUser {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub claims: Claims,
+    // The organization selected with ORGANIZATION_HEADER, membership checked.
+    pub organization_id: Option<Uuid>,
+}
+
+impl AuthUser {
+    pub fn tenant(&self) -> Tenant {
+        Tenant { user_id: self.id, organization_id: self.organization_id }
+    }
+}
+
+async fn authenticate(parts: &Parts, state: &Arc<AppState>) -> Result<AuthUser, DomainError> {
+    let auth_header = parts
+        .headers
+        .get(header::AUTHORIZATION)
+        .and_then(|h| h.to_str().ok())
+        .ok_or_else(|| DomainError::Unauthorized("Missing Authorization header".to_string()))?;
+
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| DomainError::Unauthorized("Invalid token format".to_string()))?;
+
+    let claims = state
+        .jwt_service
+        .verify_token(token)
+        .map_err(|_| DomainError::Unauthorized("Invalid token".to_string()))?;
+
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| DomainError::Unauthorized("Invalid user ID in token".to_string()))?;
+
+    // A token can outlive its user; treat a deleted account as unauthenticated.
+    let user = match state.user_usecase.get_user(user_id).await {
+        Ok(user) => user,
+        Err(DomainError::NotFound(_)) => {
+            return Err(DomainError::Unauthorized("User no longer exists".to_string()))
+        }
+        Err(e) => return Err(e),
+    };
+
+    let organization_id = match parts.headers.get(&ORGANIZATION_HEADER) {
+        Some(value) => {
+            let organization_id = value
+                .to_str()
+                .ok()
+                .and_then(|v| Uuid::parse_str(v.trim()).ok())
+                .ok_or_else(|| DomainError::validation(format!("{} must be an organization id", ORGANIZATION_HEADER)))?;
+            state.organization_usecase.find_membership(user.id, organization_id).await?;
+            Some(organization_id)
+        }
+        None => None,
+    };
+
+    Ok(AuthUser {
+        id: user.id,
+        username: user.username,
+        email: user.email,
+        claims,
+        organization_id,
+    })
+}
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for AuthUser {
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        // Reuse the principal when `require_auth` already ran for this route.
+        if let Some(user) = parts.extensions.get::<AuthUser>() {
+            return Ok(user.clone());
+        }
+
+        authenticate(parts, state).await
+    }
+}
+
+// Route layer that rejects unauthenticated requests before they reach any
+// handler, for groups of routes that must never be public.
+pub async fn require_auth(
+    State(state): State<Arc<AppState>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (mut parts, body) = request.into_parts();
+
+    match authenticate(&parts, &state).await {
+        Ok(user) => {
+            parts.extensions.insert(user);
+            next.run(Request::from_parts(parts, body)).await
+        }
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,138 @@
+use crate::delivery::http::request_id::current_request_id;
+use crate::domain::error::{DomainError, FieldErrors};
+use axum::{
+    extract::rejection::{JsonDataError, JsonRejection, QueryRejection},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+
+// RFC 7807 problem details. `type` values are stable identifiers clients can
+// switch on; `title` is the human readable summary for that type.
+#[derive(Debug, Serialize)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: &'static str,
+    pub title: &'static str,
+    pub status: u16,
+    pub detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "FieldErrors::is_empty")]
+    pub errors: FieldErrors,
+}
+
+impl DomainError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            DomainError::NotFound(_) => StatusCode::NOT_FOUND,
+            DomainError::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            DomainError::Forbidden(_) => StatusCode::FORBIDDEN,
+            DomainError::Conflict(_) => StatusCode::CONFLICT,
+            DomainError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            DomainError::PreconditionRequired(_) => StatusCode::PRECONDITION_REQUIRED,
+            DomainError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            DomainError::Validation { .. } => StatusCode::BAD_REQUEST,
+            DomainError::Infrastructure(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn problem_type(&self) -> (&'static str, &'static str) {
+        match self {
+            DomainError::NotFound(_) => ("/problems/not-found", "Resource not found"),
+            DomainError::Unauthorized(_) => ("/problems/unauthorized", "Authentication required"),
+            DomainError::Forbidden(_) => ("/problems/forbidden", "Access denied"),
+            DomainError::Conflict(_) => ("/problems/conflict", "Conflict"),
+            DomainError::PreconditionFailed(_) => ("/problems/precondition-failed", "Precondition failed"),
+            DomainError::PreconditionRequired(_) => ("/problems/precondition-required", "Precondition required"),
+            DomainError::UnsupportedMediaType(_) => ("/problems/unsupported-media-type", "Unsupported media type"),
+            DomainError::Validation { .. } => ("/problems/validation-error", "Validation failed"),
+            DomainError::Infrastructure(_) => ("/problems/internal-error", "Internal server error"),
+        }
+    }
+}
+
+impl IntoResponse for DomainError {
+    fn into_response(self) -> Response {
+        let status = self.status_code();
+        let (problem_type, title) = self.problem_type();
+        let request_id = current_request_id();
+
+        // Infrastructure failures carry driver messages that should not leak
+        // to clients; log them and return a generic detail instead.
+        let (detail, errors) = match self {
+            DomainError::Infrastructure(detail) => {
+                tracing::error!(request_id = request_id.as_deref(), "infrastructure error: {}", detail);
+                ("Internal server error".to_string(), FieldErrors::new())
+            }
+            DomainError::Validation { message, fields } => (message, fields),
+            other => (other.to_string(), FieldErrors::new()),
+        };
+
+        let problem = ProblemDetails {
+            problem_type,
+            title,
+            status: status.as_u16(),
+            detail,
+            request_id,
+            errors,
+        };
+
+        (
+            status,
+            [(header::CONTENT_TYPE, "application/problem+json")],
+            Json(problem),
+        )
+            .into_response()
+    }
+}
+
+impl From<QueryRejection> for DomainError {
+    fn from(rejection: QueryRejection) -> Self {
+        DomainError::validation(rejection.body_text())
+    }
+}
+
+impl From<JsonRejection> for DomainError {
+    fn from(rejection: JsonRejection) -> Self {
+        match rejection {
+            JsonRejection::JsonDataError(e) => match body_field_error(&e) {
+                Some((field, message)) => DomainError::invalid_field(field, message),
+                None => DomainError::validation(e.body_text()),
+            },
+            JsonRejection::MissingJsonContentType(e) => DomainError::UnsupportedMediaType(e.body_text()),
+            other => DomainError::validation(other.body_text()),
+        }
+    }
+}
+
+// The field a body that is valid JSON but does not fit the request type got
+// wrong, named like validator's field paths (`addresses[0].city`), with the
+// reason. None when the body as a whole has the wrong shape.
+fn body_field_error(rejection: &JsonDataError) -> Option<(String, String)> {
+    let mut source = std::error::Error::source(rejection);
+    let error = loop {
+        let error = source?;
+        if let Some(found) = error.downcast_ref::<serde_path_to_error::Error<serde_json::Error>>() {
+            break found;
+        }
+        source = error.source();
+    };
+
+    let inner = error.inner();
+    let path = error.path().to_string();
+    let message = inner.to_string();
+    let message = message
+        .strip_suffix(&format!(" at line {} column {}", inner.line(), inner.column()))
+        .unwrap_or(&message);
+
+    if let Some((name, _)) = message.strip_prefix("missing field `").and_then(|m| m.split_once('`')) {
+        let field = if path == "." { name.to_string() } else { format!("{}.{}", path, name) };
+        return Some((field, "Field is required".to_string()));
+    }
+    if path == "." {
+        return None;
+    }
+    Some((path, message.to_string()))
+}
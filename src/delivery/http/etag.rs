@@ -0,0 +1,62 @@
+use crate::delivery::http::handler::user_handler::AppState;
+use crate::domain::error::DomainError;
+use async_trait::async_trait;
+use axum::{
+    extract::FromRequestParts,
+    http::{header, request::Parts, HeaderValue},
+};
+use std::sync::Arc;
+
+// A contact's entity tag is its quoted version number.
+pub fn contact_etag(version: i32) -> HeaderValue {
+    HeaderValue::from_str(&format!("\"{}\"", version)).expect("quoted integer is a valid header value")
+}
+
+// Parsed `If-Match` request header. Holds the versions the client expects
+// the contact to be at, or None when any version will do (no header, or `*`).
+// With REQUIRE_IF_MATCH set, a missing header is rejected with 428.
+#[derive(Debug, Clone, Default)]
+pub struct IfMatch(pub Option<Vec<i32>>);
+
+impl IfMatch {
+    pub fn versions(&self) -> Option<&[i32]> {
+        self.0.as_deref()
+    }
+}
+
+#[async_trait]
+impl FromRequestParts<Arc<AppState>> for IfMatch {
+    type Rejection = DomainError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &Arc<AppState>) -> Result<Self, Self::Rejection> {
+        let tags: Vec<&str> = parts
+            .headers
+            .get_all(header::IF_MATCH)
+            .iter()
+            .filter_map(|v| v.to_str().ok())
+            .flat_map(|v| v.split(','))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        if tags.is_empty() {
+            if state.require_if_match {
+                return Err(DomainError::PreconditionRequired(
+                    "This request must be made conditional with If-Match".to_string(),
+                ));
+            }
+            return Ok(IfMatch(None));
+        }
+        if tags.contains(&"*") {
+            return Ok(IfMatch(None));
+        }
+
+        // If-Match uses strong comparison, so weak (W/) or malformed tags can
+        // never match; they are dropped and lead to 412.
+        let versions = tags
+            .iter()
+            .filter_map(|t| t.strip_prefix('"')?.strip_suffix('"')?.parse().ok())
+            .collect();
+        Ok(IfMatch(Some(versions)))
+    }
+}
@@ -1,137 +1,498 @@
+use crate::delivery::http::auth::AuthUser;
+use crate::delivery::http::etag::{contact_etag, IfMatch};
 use crate::delivery::http::handler::user_handler::AppState;
-use crate::infrastructure::auth::jwt::Claims;
-use crate::usecase::contact_usecase::{CreateAddressRequest, CreateContactRequest, UpdateContactRequest};
+use crate::delivery::http::json_body::JsonBody;
+use crate::domain::error::DomainError;
+use crate::usecase::contact_csv::CsvImportOptions;
+use crate::usecase::contact_usecase::{
+    ContactListQuery, ContactPatch, CreateAddressRequest, CreateContactRequest, CreateEmailRequest,
+    CreatePhoneRequest, MergeContactsRequest, UpdateAddressRequest, UpdateContactRequest, UpdateEmailRequest,
+    UpdatePhoneRequest,
+};
+use crate::usecase::vcard::VCardExportOptions;
 use axum::{
-    extract::{Path, State},
-    http::{HeaderMap, StatusCode},
+    body::{Body, Bytes},
+    extract::{rejection::QueryRejection, Path, Query, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
     response::IntoResponse,
     Json,
 };
 use std::sync::Arc;
 use uuid::Uuid;
 
-// Helper to extract user_id from JWT in headers
-fn extract_user_id(
-    headers: &HeaderMap,
-    app_state: &Arc<AppState>,
-) -> Result<Uuid, (StatusCode, String)> {
-    let auth_header = headers
-        .get("Authorization")
-        .and_then(|h| h.to_str().ok())
-        .ok_or((StatusCode::UNAUTHORIZED, "Missing Authorization header".to_string()))?;
-
-    if !auth_header.starts_with("Bearer ") {
-        return Err((StatusCode::UNAUTHORIZED, "Invalid token format".to_string()));
-    }
-
-    let token = &auth_header[7..];
-    let claims = app_state
-        .jwt_service // We need to expose jwt_service in AppState or similar.
-        // Actually AppState currently only has user_usecase. We need to add jwt_service there or use middleware.
-        // For simplicity, let's assume we can access jwt_service via AppState or similar mechanism.
-        // Wait, AppState definition in user_handler.rs only has user_usecase.
-        // I need to update AppState first to include jwt_service or contact_usecase.
-        .verify_token(token)
-        .map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid token".to_string()))?;
-
-    Uuid::parse_str(&claims.sub).map_err(|_| (StatusCode::UNAUTHORIZED, "Invalid user ID in token".to_string()))
-}
-
 // Handler functions
 
 pub async fn create_contact(
     State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
-    Json(payload): Json<CreateContactRequest>,
+    auth: AuthUser,
+    JsonBody(payload): JsonBody<CreateContactRequest>,
 ) -> impl IntoResponse {
-    let user_id = match extract_user_id(&headers, &state) {
-        Ok(id) => id,
-        Err(err) => return err.into_response(),
-    };
-
-    match state.contact_usecase.create_contact(user_id, payload).await {
+    match state.contact_usecase.create_contact(auth.tenant(), payload).await {
         Ok(contact) => (StatusCode::CREATED, Json(contact)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 pub async fn update_contact(
     State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+    if_match: IfMatch,
+    JsonBody(payload): JsonBody<UpdateContactRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.update_contact(auth.tenant(), contact_id, payload, if_match.versions()).await {
+        Ok(contact) => (StatusCode::OK, [(header::ETAG, contact_etag(contact.version))], Json(contact)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+fn parse_contact_patch(headers: &HeaderMap, body: &[u8]) -> Result<ContactPatch, DomainError> {
+    let media_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.split(';').next())
+        .map(|v| v.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let parsed = match media_type.as_str() {
+        "application/merge-patch+json" => serde_json::from_slice(body).map(ContactPatch::Merge),
+        "application/json-patch+json" => serde_json::from_slice(body).map(ContactPatch::Json),
+        _ => {
+            return Err(DomainError::UnsupportedMediaType(
+                "PATCH requires application/merge-patch+json or application/json-patch+json".to_string(),
+            ))
+        }
+    };
+    parsed.map_err(|e| DomainError::validation(format!("Malformed patch document: {}", e)))
+}
+
+pub async fn patch_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
     Path(contact_id): Path<Uuid>,
-    Json(payload): Json<UpdateContactRequest>,
+    if_match: IfMatch,
+    headers: HeaderMap,
+    body: Bytes,
 ) -> impl IntoResponse {
-    let user_id = match extract_user_id(&headers, &state) {
-        Ok(id) => id,
-        Err(err) => return err.into_response(),
+    let patch = match parse_contact_patch(&headers, &body) {
+        Ok(patch) => patch,
+        Err(e @ DomainError::UnsupportedMediaType(_)) => {
+            let mut response = e.into_response();
+            response.headers_mut().insert(
+                "accept-patch",
+                HeaderValue::from_static("application/merge-patch+json, application/json-patch+json"),
+            );
+            return response;
+        }
+        Err(e) => return e.into_response(),
     };
 
-    match state.contact_usecase.update_contact(user_id, contact_id, payload).await {
-        Ok(contact) => (StatusCode::OK, Json(contact)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    match state.contact_usecase.patch_contact(auth.tenant(), contact_id, patch, if_match.versions()).await {
+        Ok(contact) => (StatusCode::OK, [(header::ETAG, contact_etag(contact.version))], Json(contact)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 pub async fn search_contacts(
     State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
+    auth: AuthUser,
+    query: Result<Query<ContactListQuery>, QueryRejection>,
 ) -> impl IntoResponse {
-    let user_id = match extract_user_id(&headers, &state) {
-        Ok(id) => id,
-        Err(err) => return err.into_response(),
+    let Query(query) = match query {
+        Ok(query) => query,
+        Err(rejection) => return DomainError::from(rejection).into_response(),
     };
 
-    match state.contact_usecase.search_contacts(user_id).await {
+    match state.contact_usecase.search_contacts(auth.tenant(), query).await {
         Ok(contacts) => (StatusCode::OK, Json(contacts)).into_response(),
-        Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
+fn vcard_response(filename: &str, body: String) -> axum::response::Response {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/vcard; charset=utf-8".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", filename)),
+        ],
+        body,
+    )
+        .into_response()
+}
+
+// Also serves `/contacts/:id.vcf`, which shares this route segment.
 pub async fn get_contact(
     State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
-    Path(contact_id): Path<Uuid>,
+    auth: AuthUser,
+    Path(contact_id): Path<String>,
+    vcard_options: Result<Query<VCardExportOptions>, QueryRejection>,
 ) -> impl IntoResponse {
-     let user_id = match extract_user_id(&headers, &state) {
-        Ok(id) => id,
-        Err(err) => return err.into_response(),
+    let (raw_id, as_vcard) = match contact_id.strip_suffix(".vcf") {
+        Some(id) => (id, true),
+        None => (contact_id.as_str(), false),
+    };
+    let Ok(contact_id) = Uuid::parse_str(raw_id) else {
+        return DomainError::invalid_field("contact_id", "Invalid contact id").into_response();
     };
 
-    match state.contact_usecase.get_contact(user_id, contact_id).await {
-        Ok(contact) => (StatusCode::OK, Json(contact)).into_response(),
-        Err(e) => (StatusCode::NOT_FOUND, e).into_response(),
+    if as_vcard {
+        let Query(options) = match vcard_options {
+            Ok(options) => options,
+            Err(rejection) => return DomainError::from(rejection).into_response(),
+        };
+        return match state.contact_usecase.export_vcard(auth.tenant(), contact_id, options.version).await {
+            Ok(card) => vcard_response(&format!("{}.vcf", contact_id), card),
+            Err(e) => e.into_response(),
+        };
+    }
+
+    match state.contact_usecase.get_contact(auth.tenant(), contact_id).await {
+        Ok(contact) => (StatusCode::OK, [(header::ETAG, contact_etag(contact.version))], Json(contact)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn export_vcards(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    options: Result<Query<VCardExportOptions>, QueryRejection>,
+) -> impl IntoResponse {
+    let Query(options) = match options {
+        Ok(options) => options,
+        Err(rejection) => return DomainError::from(rejection).into_response(),
+    };
+
+    match state.contact_usecase.export_vcards(auth.tenant(), options.version).await {
+        Ok(cards) => vcard_response("contacts.vcf", cards),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn export_csv(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8"),
+            (header::CONTENT_DISPOSITION, "attachment; filename=\"contacts.csv\""),
+        ],
+        Body::from_stream(state.contact_usecase.export_csv(auth.tenant())),
+    )
+}
+
+pub async fn import_csv(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    options: Result<Query<CsvImportOptions>, QueryRejection>,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Query(options) = match options {
+        Ok(options) => options,
+        Err(rejection) => return DomainError::from(rejection).into_response(),
+    };
+
+    match state.contact_usecase.import_csv(auth.tenant(), &body, &options).await {
+        Ok(report) => (StatusCode::OK, Json(report)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn import_vcards(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    body: Bytes,
+) -> impl IntoResponse {
+    let Ok(input) = std::str::from_utf8(&body) else {
+        return DomainError::validation("vCard file must be UTF-8 encoded").into_response();
+    };
+
+    match state.contact_usecase.import_vcards(auth.tenant(), input).await {
+        Ok(report) => (StatusCode::OK, Json(report)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 pub async fn delete_contact(
     State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
+    auth: AuthUser,
     Path(contact_id): Path<Uuid>,
+    if_match: IfMatch,
 ) -> impl IntoResponse {
-     let user_id = match extract_user_id(&headers, &state) {
-        Ok(id) => id,
-        Err(err) => return err.into_response(),
-    };
+    match state.contact_usecase.delete_contact(auth.tenant(), contact_id, if_match.versions()).await {
+        Ok(_) => (StatusCode::OK, "Contact moved to trash").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
 
-    match state.contact_usecase.delete_contact(user_id, contact_id).await {
-        Ok(_) => (StatusCode::OK, "Contact deleted").into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+pub async fn find_duplicates(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.contact_usecase.find_duplicates(auth.tenant()).await {
+        Ok(groups) => (StatusCode::OK, Json(groups)).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
-pub async fn create_address(
+// If-Match applies to the target contact.
+pub async fn merge_contacts(
     State(state): State<Arc<AppState>>,
-    headers: HeaderMap,
+    auth: AuthUser,
+    if_match: IfMatch,
+    JsonBody(payload): JsonBody<MergeContactsRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.merge_contacts(auth.tenant(), payload, if_match.versions()).await {
+        Ok(contact) => (StatusCode::OK, [(header::ETAG, contact_etag(contact.version))], Json(contact)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_trash(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.contact_usecase.list_trash(auth.tenant()).await {
+        Ok(contacts) => (StatusCode::OK, Json(contacts)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_shared_contacts(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.contact_usecase.list_shared_contacts(auth.id).await {
+        Ok(contacts) => (StatusCode::OK, Json(contacts)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn restore_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
     Path(contact_id): Path<Uuid>,
-    Json(payload): Json<CreateAddressRequest>,
 ) -> impl IntoResponse {
-     let user_id = match extract_user_id(&headers, &state) {
-        Ok(id) => id,
-        Err(err) => return err.into_response(),
-    };
+    match state.contact_usecase.restore_contact(auth.tenant(), contact_id).await {
+        Ok(contact) => (StatusCode::OK, Json(contact)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn purge_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.contact_usecase.purge_contact(auth.tenant(), contact_id).await {
+        Ok(_) => (StatusCode::OK, "Contact permanently deleted").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn contact_history(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.contact_usecase.contact_history(auth.tenant(), contact_id).await {
+        Ok(history) => (StatusCode::OK, Json(history)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn revert_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, version)): Path<(Uuid, i32)>,
+) -> impl IntoResponse {
+    match state.contact_usecase.revert_contact(auth.tenant(), contact_id, version).await {
+        Ok(contact) => (StatusCode::OK, Json(contact)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
 
-    match state.contact_usecase.create_address(user_id, contact_id, payload).await {
+pub async fn create_address(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<CreateAddressRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.create_address(auth.tenant(), contact_id, payload).await {
         Ok(address) => (StatusCode::CREATED, Json(address)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_addresses(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.contact_usecase.list_addresses(auth.tenant(), contact_id).await {
+        Ok(addresses) => (StatusCode::OK, Json(addresses)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_address(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, address_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.contact_usecase.get_address(auth.tenant(), contact_id, address_id).await {
+        Ok(address) => (StatusCode::OK, Json(address)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn replace_address(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, address_id)): Path<(Uuid, Uuid)>,
+    JsonBody(payload): JsonBody<CreateAddressRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.replace_address(auth.tenant(), contact_id, address_id, payload).await {
+        Ok(address) => (StatusCode::OK, Json(address)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_address(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, address_id)): Path<(Uuid, Uuid)>,
+    JsonBody(payload): JsonBody<UpdateAddressRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.update_address(auth.tenant(), contact_id, address_id, payload).await {
+        Ok(address) => (StatusCode::OK, Json(address)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_address(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, address_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.contact_usecase.delete_address(auth.tenant(), contact_id, address_id).await {
+        Ok(_) => (StatusCode::OK, "Address deleted").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn create_email(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<CreateEmailRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.create_email(auth.tenant(), contact_id, payload).await {
+        Ok(email) => (StatusCode::CREATED, Json(email)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_emails(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.contact_usecase.list_emails(auth.tenant(), contact_id).await {
+        Ok(emails) => (StatusCode::OK, Json(emails)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_email(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, email_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.contact_usecase.get_email(auth.tenant(), contact_id, email_id).await {
+        Ok(email) => (StatusCode::OK, Json(email)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_email(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, email_id)): Path<(Uuid, Uuid)>,
+    JsonBody(payload): JsonBody<UpdateEmailRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.update_email(auth.tenant(), contact_id, email_id, payload).await {
+        Ok(email) => (StatusCode::OK, Json(email)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_email(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, email_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.contact_usecase.delete_email(auth.tenant(), contact_id, email_id).await {
+        Ok(_) => (StatusCode::OK, "Email deleted").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn create_phone(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<CreatePhoneRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.create_phone(auth.tenant(), contact_id, payload).await {
+        Ok(phone) => (StatusCode::CREATED, Json(phone)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_phones(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.contact_usecase.list_phones(auth.tenant(), contact_id).await {
+        Ok(phones) => (StatusCode::OK, Json(phones)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_phone(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, phone_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.contact_usecase.get_phone(auth.tenant(), contact_id, phone_id).await {
+        Ok(phone) => (StatusCode::OK, Json(phone)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_phone(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, phone_id)): Path<(Uuid, Uuid)>,
+    JsonBody(payload): JsonBody<UpdatePhoneRequest>,
+) -> impl IntoResponse {
+    match state.contact_usecase.update_phone(auth.tenant(), contact_id, phone_id, payload).await {
+        Ok(phone) => (StatusCode::OK, Json(phone)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_phone(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, phone_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.contact_usecase.delete_phone(auth.tenant(), contact_id, phone_id).await {
+        Ok(_) => (StatusCode::OK, "Phone deleted").into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -0,0 +1,67 @@
+use crate::delivery::http::auth::AuthUser;
+use crate::delivery::http::handler::user_handler::AppState;
+use crate::delivery::http::json_body::JsonBody;
+use crate::usecase::custom_field_usecase::{CreateCustomFieldRequest, UpdateCustomFieldRequest};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub async fn create_custom_field(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    JsonBody(payload): JsonBody<CreateCustomFieldRequest>,
+) -> impl IntoResponse {
+    match state.custom_field_usecase.create_field(auth.id, payload).await {
+        Ok(field) => (StatusCode::CREATED, Json(field)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_custom_fields(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.custom_field_usecase.list_fields(auth.id).await {
+        Ok(fields) => (StatusCode::OK, Json(fields)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_custom_field(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(field_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.custom_field_usecase.get_field(auth.id, field_id).await {
+        Ok(field) => (StatusCode::OK, Json(field)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_custom_field(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(field_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<UpdateCustomFieldRequest>,
+) -> impl IntoResponse {
+    match state.custom_field_usecase.update_field(auth.id, field_id, payload).await {
+        Ok(field) => (StatusCode::OK, Json(field)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_custom_field(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(field_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.custom_field_usecase.delete_field(auth.id, field_id).await {
+        Ok(_) => (StatusCode::OK, "Custom field deleted").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -0,0 +1,89 @@
+use crate::delivery::http::auth::AuthUser;
+use crate::delivery::http::handler::user_handler::AppState;
+use crate::delivery::http::json_body::JsonBody;
+use crate::usecase::group_usecase::{CreateGroupRequest, UpdateGroupRequest};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub async fn create_group(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    JsonBody(payload): JsonBody<CreateGroupRequest>,
+) -> impl IntoResponse {
+    match state.group_usecase.create_group(auth.id, payload).await {
+        Ok(group) => (StatusCode::CREATED, Json(group)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_groups(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.group_usecase.list_groups(auth.id).await {
+        Ok(groups) => (StatusCode::OK, Json(groups)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_group(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(group_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.group_usecase.get_group(auth.id, group_id).await {
+        Ok(group) => (StatusCode::OK, Json(group)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_group(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(group_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<UpdateGroupRequest>,
+) -> impl IntoResponse {
+    match state.group_usecase.update_group(auth.id, group_id, payload).await {
+        Ok(group) => (StatusCode::OK, Json(group)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_group(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(group_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.group_usecase.delete_group(auth.id, group_id).await {
+        Ok(_) => (StatusCode::OK, "Group deleted").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn add_group_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((group_id, contact_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.group_usecase.add_contact(auth.id, group_id, contact_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn remove_group_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((group_id, contact_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.group_usecase.remove_contact(auth.id, group_id, contact_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
@@ -1,2 +1,8 @@
 pub mod contact_handler;
+pub mod custom_field_handler;
+pub mod group_handler;
+pub mod organization_handler;
+pub mod share_handler;
+pub mod tag_handler;
 pub mod user_handler;
+pub mod well_known_handler;
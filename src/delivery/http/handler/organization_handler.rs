@@ -0,0 +1,115 @@
+use crate::delivery::http::auth::AuthUser;
+use crate::delivery::http::handler::user_handler::AppState;
+use crate::delivery::http::json_body::JsonBody;
+use crate::usecase::organization_usecase::{
+    AddMemberRequest, CreateOrganizationRequest, UpdateMemberRequest, UpdateOrganizationRequest,
+};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub async fn create_organization(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    JsonBody(payload): JsonBody<CreateOrganizationRequest>,
+) -> impl IntoResponse {
+    match state.organization_usecase.create_organization(auth.id, payload).await {
+        Ok(organization) => (StatusCode::CREATED, Json(organization)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_organizations(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.organization_usecase.list_organizations(auth.id).await {
+        Ok(organizations) => (StatusCode::OK, Json(organizations)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_organization(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(organization_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.organization_usecase.get_organization(auth.id, organization_id).await {
+        Ok(organization) => (StatusCode::OK, Json(organization)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_organization(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(organization_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<UpdateOrganizationRequest>,
+) -> impl IntoResponse {
+    match state.organization_usecase.update_organization(auth.id, organization_id, payload).await {
+        Ok(organization) => (StatusCode::OK, Json(organization)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_organization(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(organization_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.organization_usecase.delete_organization(auth.id, organization_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_members(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(organization_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.organization_usecase.list_members(auth.id, organization_id).await {
+        Ok(members) => (StatusCode::OK, Json(members)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn add_member(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(organization_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<AddMemberRequest>,
+) -> impl IntoResponse {
+    match state.organization_usecase.add_member(auth.id, organization_id, payload).await {
+        Ok(member) => (StatusCode::CREATED, Json(member)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_member(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((organization_id, user_id)): Path<(Uuid, Uuid)>,
+    JsonBody(payload): JsonBody<UpdateMemberRequest>,
+) -> impl IntoResponse {
+    match state.organization_usecase.update_member(auth.id, organization_id, user_id, payload).await {
+        Ok(member) => (StatusCode::OK, Json(member)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn remove_member(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((organization_id, user_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.organization_usecase.remove_member(auth.id, organization_id, user_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
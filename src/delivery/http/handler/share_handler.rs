@@ -0,0 +1,81 @@
+use crate::delivery::http::auth::AuthUser;
+use crate::delivery::http::handler::user_handler::AppState;
+use crate::delivery::http::json_body::JsonBody;
+use crate::usecase::share_usecase::{CreateShareRequest, UpdateShareRequest};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub async fn share_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<CreateShareRequest>,
+) -> impl IntoResponse {
+    match state.share_usecase.share_contact(auth.id, contact_id, payload).await {
+        Ok(share) => (StatusCode::CREATED, Json(share)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_contact_shares(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(contact_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.share_usecase.list_contact_shares(auth.id, contact_id).await {
+        Ok(shares) => (StatusCode::OK, Json(shares)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn share_group(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(group_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<CreateShareRequest>,
+) -> impl IntoResponse {
+    match state.share_usecase.share_group(auth.id, group_id, payload).await {
+        Ok(share) => (StatusCode::CREATED, Json(share)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_group_shares(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(group_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.share_usecase.list_group_shares(auth.id, group_id).await {
+        Ok(shares) => (StatusCode::OK, Json(shares)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_share(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(share_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<UpdateShareRequest>,
+) -> impl IntoResponse {
+    match state.share_usecase.update_share(auth.id, share_id, payload).await {
+        Ok(share) => (StatusCode::OK, Json(share)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_share(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(share_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.share_usecase.delete_share(auth.id, share_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
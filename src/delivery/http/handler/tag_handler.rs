@@ -0,0 +1,89 @@
+use crate::delivery::http::auth::AuthUser;
+use crate::delivery::http::handler::user_handler::AppState;
+use crate::delivery::http::json_body::JsonBody;
+use crate::usecase::tag_usecase::CreateTagRequest;
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub async fn create_tag(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    JsonBody(payload): JsonBody<CreateTagRequest>,
+) -> impl IntoResponse {
+    match state.tag_usecase.create_tag(auth.id, payload).await {
+        Ok(tag) => (StatusCode::CREATED, Json(tag)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn list_tags(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.tag_usecase.list_tags(auth.id).await {
+        Ok(tags) => (StatusCode::OK, Json(tags)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_tag(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(tag_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.tag_usecase.get_tag(auth.id, tag_id).await {
+        Ok(tag) => (StatusCode::OK, Json(tag)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn rename_tag(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(tag_id): Path<Uuid>,
+    JsonBody(payload): JsonBody<CreateTagRequest>,
+) -> impl IntoResponse {
+    match state.tag_usecase.rename_tag(auth.id, tag_id, payload).await {
+        Ok(tag) => (StatusCode::OK, Json(tag)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn delete_tag(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path(tag_id): Path<Uuid>,
+) -> impl IntoResponse {
+    match state.tag_usecase.delete_tag(auth.id, tag_id).await {
+        Ok(_) => (StatusCode::OK, "Tag deleted").into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn tag_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, tag_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.tag_usecase.tag_contact(auth.id, contact_id, tag_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn untag_contact(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    Path((contact_id, tag_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    match state.tag_usecase.untag_contact(auth.id, contact_id, tag_id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
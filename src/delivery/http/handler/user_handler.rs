@@ -1,6 +1,16 @@
+use crate::delivery::http::auth::AuthUser;
+use crate::delivery::http::json_body::JsonBody;
+use crate::domain::error::DomainError;
 use crate::infrastructure::auth::jwt::JwtService;
 use crate::usecase::contact_usecase::ContactUsecase;
-use crate::usecase::user_usecase::{LoginRequest, RegisterRequest, UserUsecase};
+use crate::usecase::custom_field_usecase::CustomFieldUsecase;
+use crate::usecase::group_usecase::GroupUsecase;
+use crate::usecase::organization_usecase::OrganizationUsecase;
+use crate::usecase::share_usecase::ShareUsecase;
+use crate::usecase::tag_usecase::TagUsecase;
+use crate::usecase::user_usecase::{
+    LoginRequest, LogoutRequest, RefreshRequest, RegisterRequest, UpdateUserRequest, UserUsecase,
+};
 use axum::{
     extract::{State, Json},
     http::StatusCode,
@@ -11,25 +21,91 @@ use std::sync::Arc;
 pub struct AppState {
     pub user_usecase: Arc<UserUsecase>,
     pub contact_usecase: Arc<ContactUsecase>,
+    pub tag_usecase: Arc<TagUsecase>,
+    pub group_usecase: Arc<GroupUsecase>,
+    pub custom_field_usecase: Arc<CustomFieldUsecase>,
+    pub share_usecase: Arc<ShareUsecase>,
+    pub organization_usecase: Arc<OrganizationUsecase>,
     pub jwt_service: Arc<JwtService>,
+    // Reject contact writes without If-Match (see `IfMatch`).
+    pub require_if_match: bool,
 }
 
 pub async fn register(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<RegisterRequest>,
+    JsonBody(payload): JsonBody<RegisterRequest>,
 ) -> impl IntoResponse {
     match state.user_usecase.register(payload).await {
         Ok(user) => (StatusCode::CREATED, Json(user)).into_response(),
-        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        Err(e) => e.into_response(),
     }
 }
 
 pub async fn login(
     State(state): State<Arc<AppState>>,
-    Json(payload): Json<LoginRequest>,
+    JsonBody(payload): JsonBody<LoginRequest>,
 ) -> impl IntoResponse {
     match state.user_usecase.login(payload).await {
         Ok(res) => (StatusCode::OK, Json(res)).into_response(),
-        Err(e) => (StatusCode::UNAUTHORIZED, e).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn refresh(
+    State(state): State<Arc<AppState>>,
+    JsonBody(payload): JsonBody<RefreshRequest>,
+) -> impl IntoResponse {
+    match state.user_usecase.refresh(payload).await {
+        Ok(res) => (StatusCode::OK, Json(res)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn get_me(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.user_usecase.get_user(auth.id).await {
+        Ok(user) => (StatusCode::OK, Json(user)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn update_me(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    JsonBody(payload): JsonBody<UpdateUserRequest>,
+) -> impl IntoResponse {
+    match state.user_usecase.update_user(auth.id, payload).await {
+        Ok(user) => (StatusCode::OK, Json(user)).into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn logout(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+    payload: Result<JsonBody<LogoutRequest>, DomainError>,
+) -> impl IntoResponse {
+    // The body is optional, but one that is sent must be valid.
+    let payload = match payload {
+        Ok(JsonBody(payload)) => payload,
+        Err(DomainError::UnsupportedMediaType(_)) => LogoutRequest::default(),
+        Err(e) => return e.into_response(),
+    };
+
+    match state.user_usecase.logout(&auth.claims, payload).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
+    }
+}
+
+pub async fn logout_all(
+    State(state): State<Arc<AppState>>,
+    auth: AuthUser,
+) -> impl IntoResponse {
+    match state.user_usecase.logout_all(auth.id).await {
+        Ok(_) => StatusCode::NO_CONTENT.into_response(),
+        Err(e) => e.into_response(),
     }
 }
@@ -0,0 +1,26 @@
+use crate::domain::error::DomainError;
+use async_trait::async_trait;
+use axum::{
+    extract::{FromRequest, Request},
+    Json,
+};
+use serde::de::DeserializeOwned;
+
+// `axum::Json` for request bodies, rejecting with a problem+json response
+// (see `From<JsonRejection> for DomainError`) instead of axum's plain text.
+#[derive(Debug, Clone, Default)]
+pub struct JsonBody<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for JsonBody<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = DomainError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+        Ok(JsonBody(value))
+    }
+}
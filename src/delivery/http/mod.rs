@@ -1,2 +1,7 @@
+pub mod auth;
+pub mod error;
+pub mod etag;
 pub mod handler;
+pub mod json_body;
+pub mod request_id;
 pub mod router;
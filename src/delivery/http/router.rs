@@ -1,22 +1,143 @@
+use crate::delivery::http::auth::require_auth;
 use crate::delivery::http::handler::contact_handler::{
-    create_address, create_contact, delete_contact, get_contact, search_contacts, update_contact,
+    contact_history, create_address, create_contact, create_email, create_phone, delete_address,
+    delete_contact, delete_email, delete_phone, export_csv, export_vcards, find_duplicates,
+    get_address, get_contact, get_email, get_phone, import_csv, import_vcards, list_addresses,
+    list_emails, list_phones, list_shared_contacts, list_trash, merge_contacts, patch_contact, purge_contact,
+    replace_address, restore_contact, revert_contact, search_contacts, update_address,
+    update_contact, update_email, update_phone,
 };
-use crate::delivery::http::handler::user_handler::{login, register, AppState};
+use crate::delivery::http::handler::custom_field_handler::{
+    create_custom_field, delete_custom_field, get_custom_field, list_custom_fields,
+    update_custom_field,
+};
+use crate::delivery::http::handler::group_handler::{
+    add_group_contact, create_group, delete_group, get_group, list_groups, remove_group_contact,
+    update_group,
+};
+use crate::delivery::http::handler::organization_handler::{
+    add_member, create_organization, delete_organization, get_organization, list_members,
+    list_organizations, remove_member, update_member, update_organization,
+};
+use crate::delivery::http::handler::share_handler::{
+    delete_share, list_contact_shares, list_group_shares, share_contact, share_group, update_share,
+};
+use crate::delivery::http::handler::tag_handler::{
+    create_tag, delete_tag, get_tag, list_tags, rename_tag, tag_contact, untag_contact,
+};
+use crate::delivery::http::handler::user_handler::{
+    get_me, login, logout, logout_all, refresh, register, update_me, AppState,
+};
+use crate::delivery::http::handler::well_known_handler::jwks;
+use crate::delivery::http::request_id::request_id;
 use axum::{
-    routing::{delete, get, post, put},
+    middleware,
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use std::sync::Arc;
 
 pub fn create_router(app_state: Arc<AppState>) -> Router {
-    Router::new()
-        .route("/users/register", post(register))
-        .route("/users/login", post(login))
+    // Everything in here requires a valid bearer token, even if a handler
+    // forgets to take `AuthUser`.
+    let protected = Router::new()
+        .route("/users/me", get(get_me).patch(update_me))
+        .route("/users/logout", post(logout))
+        .route("/users/logout-all", post(logout_all))
         .route("/contacts", post(create_contact).get(search_contacts))
         .route(
             "/contacts/:contact_id",
-            get(get_contact).put(update_contact).delete(delete_contact),
+            get(get_contact)
+                .put(update_contact)
+                .patch(patch_contact)
+                .delete(delete_contact),
+        )
+        .route("/contacts/export.vcf", get(export_vcards))
+        .route("/contacts/export.csv", get(export_csv))
+        .route("/contacts/import", post(import_vcards))
+        .route("/contacts/import/csv", post(import_csv))
+        .route("/contacts/duplicates", get(find_duplicates))
+        .route("/contacts/merge", post(merge_contacts))
+        .route("/contacts/trash", get(list_trash))
+        .route("/contacts/shared", get(list_shared_contacts))
+        .route("/contacts/trash/:contact_id", delete(purge_contact))
+        .route("/contacts/:contact_id/restore", post(restore_contact))
+        .route("/contacts/:contact_id/history", get(contact_history))
+        .route("/contacts/:contact_id/revert/:version", post(revert_contact))
+        .route(
+            "/contacts/:contact_id/shares",
+            post(share_contact).get(list_contact_shares),
+        )
+        .route(
+            "/contacts/:contact_id/addresses",
+            post(create_address).get(list_addresses),
+        )
+        .route(
+            "/contacts/:contact_id/addresses/:address_id",
+            get(get_address)
+                .put(replace_address)
+                .patch(update_address)
+                .delete(delete_address),
+        )
+        .route(
+            "/contacts/:contact_id/emails",
+            post(create_email).get(list_emails),
+        )
+        .route(
+            "/contacts/:contact_id/emails/:email_id",
+            get(get_email).patch(update_email).delete(delete_email),
+        )
+        .route(
+            "/contacts/:contact_id/phones",
+            post(create_phone).get(list_phones),
         )
-        .route("/contacts/:contact_id/addresses", post(create_address))
+        .route(
+            "/contacts/:contact_id/phones/:phone_id",
+            get(get_phone).patch(update_phone).delete(delete_phone),
+        )
+        .route(
+            "/contacts/:contact_id/tags/:tag_id",
+            put(tag_contact).delete(untag_contact),
+        )
+        .route("/tags", post(create_tag).get(list_tags))
+        .route("/tags/:tag_id", get(get_tag).put(rename_tag).delete(delete_tag))
+        .route("/groups", post(create_group).get(list_groups))
+        .route(
+            "/groups/:group_id",
+            get(get_group).patch(update_group).delete(delete_group),
+        )
+        .route(
+            "/groups/:group_id/contacts/:contact_id",
+            put(add_group_contact).delete(remove_group_contact),
+        )
+        .route("/groups/:group_id/shares", post(share_group).get(list_group_shares))
+        .route("/shares/:share_id", patch(update_share).delete(delete_share))
+        .route("/organizations", post(create_organization).get(list_organizations))
+        .route(
+            "/organizations/:organization_id",
+            get(get_organization).patch(update_organization).delete(delete_organization),
+        )
+        .route(
+            "/organizations/:organization_id/members",
+            post(add_member).get(list_members),
+        )
+        .route(
+            "/organizations/:organization_id/members/:user_id",
+            patch(update_member).delete(remove_member),
+        )
+        .route("/custom-fields", post(create_custom_field).get(list_custom_fields))
+        .route(
+            "/custom-fields/:field_id",
+            get(get_custom_field).patch(update_custom_field).delete(delete_custom_field),
+        )
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), require_auth));
+
+    Router::new()
+        .route("/users/register", post(register))
+        .route("/users/login", post(login))
+        .route("/users/refresh", post(refresh))
+        .route("/.well-known/jwks.json", get(jwks))
+        .merge(protected)
+        .layer(middleware::from_fn(request_id))
         .with_state(app_state)
 }
@@ -2,6 +2,17 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum AddressKind {
+    Home,
+    Work,
+    #[default]
+    Other,
+}
+
+// A contact with addresses has exactly one primary address.
 #[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
 pub struct Address {
     pub id: Uuid,
@@ -9,8 +20,11 @@ pub struct Address {
     pub street: Option<String>,
     pub city: Option<String>,
     pub province: Option<String>,
+    // ISO 3166-1 alpha-2, e.g. "ID".
     pub country: String,
     pub postal_code: Option<String>,
+    pub kind: AddressKind,
+    pub is_primary: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -1,15 +1,30 @@
+use crate::domain::entity::custom_field_entity::CustomFields;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
 pub struct Contact {
     pub id: Uuid,
+    // The creator; the owner too unless the contact belongs to an
+    // organization.
     pub user_id: Uuid,
+    pub organization_id: Option<Uuid>,
     pub first_name: String,
     pub last_name: Option<String>,
+    // Copies of the primary ContactEmail and ContactPhone, kept in sync by
+    // the repository. The phone is E.164, e.g. "+6281234567890"; `phone_raw`
+    // is the number as the user entered it.
     pub email: Option<String>,
     pub phone: Option<String>,
+    pub phone_raw: Option<String>,
+    // Values of the owner's CustomFieldDefinitions, keyed by field name.
+    pub custom_fields: Json<CustomFields>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    // Incremented by every write; updates only apply to the version they read.
+    pub version: i32,
+    // Set while the contact is in the trash.
+    pub deleted_at: Option<DateTime<Utc>>,
 }
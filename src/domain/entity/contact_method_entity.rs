@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ContactLabel {
+    Home,
+    Work,
+    Mobile,
+    #[default]
+    Other,
+}
+
+// A contact has at most one primary email; the repository keeps
+// `Contact::email` equal to it.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ContactEmail {
+    pub id: Uuid,
+    pub contact_id: Uuid,
+    pub email: String,
+    pub label: ContactLabel,
+    pub is_primary: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// Same rules as ContactEmail, mirrored into `Contact::phone` and
+// `Contact::phone_raw`.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ContactPhone {
+    pub id: Uuid,
+    pub contact_id: Uuid,
+    // E.164, e.g. "+6281234567890".
+    pub phone: String,
+    // The number as the user entered it, for display.
+    pub phone_raw: Option<String>,
+    pub label: ContactLabel,
+    pub is_primary: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,145 @@
+use super::address_entity::{Address, AddressKind};
+use super::contact_entity::Contact;
+use super::contact_method_entity::{ContactEmail, ContactLabel, ContactPhone};
+use super::custom_field_entity::CustomFields;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::types::Json;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ContactChange {
+    Created,
+    Updated,
+    Deleted,
+    Restored,
+    Reverted,
+    Merged,
+    // Recorded on each source of a merge as it moves to the trash.
+    MergedInto,
+    AddressCreated,
+    AddressUpdated,
+    AddressDeleted,
+    EmailCreated,
+    EmailUpdated,
+    EmailDeleted,
+    PhoneCreated,
+    PhoneUpdated,
+    PhoneDeleted,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AddressSnapshot {
+    pub id: Uuid,
+    pub street: Option<String>,
+    pub city: Option<String>,
+    pub province: Option<String>,
+    pub country: String,
+    pub postal_code: Option<String>,
+    // Absent from snapshots taken before addresses had a kind and a primary.
+    #[serde(default)]
+    pub kind: AddressKind,
+    #[serde(default)]
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EmailSnapshot {
+    pub id: Uuid,
+    pub email: String,
+    pub label: ContactLabel,
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PhoneSnapshot {
+    pub id: Uuid,
+    pub phone: String,
+    pub phone_raw: Option<String>,
+    pub label: ContactLabel,
+    pub is_primary: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContactSnapshot {
+    pub first_name: String,
+    pub last_name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    // Absent from snapshots taken before phone normalization.
+    #[serde(default)]
+    pub phone_raw: Option<String>,
+    pub addresses: Vec<AddressSnapshot>,
+    // None in snapshots taken before contacts had several emails and phones;
+    // only `email` and `phone` are known for those.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub emails: Option<Vec<EmailSnapshot>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub phones: Option<Vec<PhoneSnapshot>>,
+    #[serde(default)]
+    pub custom_fields: CustomFields,
+}
+
+impl ContactSnapshot {
+    pub fn new(contact: &Contact, addresses: &[Address], emails: &[ContactEmail], phones: &[ContactPhone]) -> Self {
+        Self {
+            first_name: contact.first_name.clone(),
+            last_name: contact.last_name.clone(),
+            email: contact.email.clone(),
+            phone: contact.phone.clone(),
+            phone_raw: contact.phone_raw.clone(),
+            addresses: addresses
+                .iter()
+                .map(|a| AddressSnapshot {
+                    id: a.id,
+                    street: a.street.clone(),
+                    city: a.city.clone(),
+                    province: a.province.clone(),
+                    country: a.country.clone(),
+                    postal_code: a.postal_code.clone(),
+                    kind: a.kind,
+                    is_primary: a.is_primary,
+                })
+                .collect(),
+            emails: Some(
+                emails
+                    .iter()
+                    .map(|e| EmailSnapshot {
+                        id: e.id,
+                        email: e.email.clone(),
+                        label: e.label,
+                        is_primary: e.is_primary,
+                    })
+                    .collect(),
+            ),
+            phones: Some(
+                phones
+                    .iter()
+                    .map(|p| PhoneSnapshot {
+                        id: p.id,
+                        phone: p.phone.clone(),
+                        phone_raw: p.phone_raw.clone(),
+                        label: p.label,
+                        is_primary: p.is_primary,
+                    })
+                    .collect(),
+            ),
+            custom_fields: contact.custom_fields.0.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct ContactVersion {
+    pub id: Uuid,
+    pub contact_id: Uuid,
+    // The contact's version (its ETag) right after the change. Contacts
+    // written before history was kept skip the numbers from before.
+    pub version: i32,
+    pub action: ContactChange,
+    pub actor_id: Option<Uuid>,
+    pub snapshot: Json<ContactSnapshot>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,32 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use uuid::Uuid;
+
+// Custom field values of one contact, keyed by definition name.
+pub type CustomFields = Map<String, Value>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum CustomFieldType {
+    Text,
+    Number,
+    Boolean,
+    // Stored as "YYYY-MM-DD".
+    Date,
+    // One of `enum_values`.
+    Enum,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct CustomFieldDefinition {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub field_type: CustomFieldType,
+    pub required: bool,
+    pub enum_values: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -1,3 +1,12 @@
 pub mod address_entity;
 pub mod contact_entity;
+pub mod contact_method_entity;
+pub mod contact_version_entity;
+pub mod custom_field_entity;
+pub mod group_entity;
+pub mod organization_entity;
+pub mod refresh_token_entity;
+pub mod share_entity;
+pub mod tag_entity;
+pub mod token_revocation_entity;
 pub mod user_entity;
@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+// Ordered by strength: edit includes read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "varchar", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum SharePermission {
+    Read,
+    Edit,
+}
+
+// Access granted by `owner_id` to `grantee_id`, on either one contact or
+// every contact in a group.
+#[derive(Debug, Serialize, Deserialize, Clone, sqlx::FromRow)]
+pub struct ContactShare {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub grantee_id: Uuid,
+    pub contact_id: Option<Uuid>,
+    pub group_id: Option<Uuid>,
+    pub permission: SharePermission,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
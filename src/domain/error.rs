@@ -0,0 +1,90 @@
+use std::collections::BTreeMap;
+use thiserror::Error;
+use validator::{ValidationErrors, ValidationErrorsKind};
+
+// Field path (e.g. `email`, `addresses[0].country`) to the messages for it.
+pub type FieldErrors = BTreeMap<String, Vec<String>>;
+
+// Error type shared by repositories and usecases. Each variant maps to one
+// HTTP status in the delivery layer, so handlers never have to guess.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum DomainError {
+    #[error("{0}")]
+    NotFound(String),
+    #[error("{0}")]
+    Unauthorized(String),
+    #[error("{0}")]
+    Forbidden(String),
+    #[error("{0}")]
+    Conflict(String),
+    // The resource changed since the client read it (stale If-Match).
+    #[error("{0}")]
+    PreconditionFailed(String),
+    #[error("{0}")]
+    PreconditionRequired(String),
+    #[error("{0}")]
+    UnsupportedMediaType(String),
+    #[error("{message}")]
+    Validation { message: String, fields: FieldErrors },
+    #[error("{0}")]
+    Infrastructure(String),
+}
+
+impl DomainError {
+    pub fn validation(message: impl Into<String>) -> Self {
+        DomainError::Validation {
+            message: message.into(),
+            fields: FieldErrors::new(),
+        }
+    }
+
+    pub fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> Self {
+        DomainError::Validation {
+            message: "Request validation failed".to_string(),
+            fields: FieldErrors::from([(field.into(), vec![message.into()])]),
+        }
+    }
+}
+
+impl From<ValidationErrors> for DomainError {
+    fn from(errors: ValidationErrors) -> Self {
+        let mut fields = FieldErrors::new();
+        collect_field_errors(&errors, "", &mut fields);
+
+        DomainError::Validation {
+            message: "Request validation failed".to_string(),
+            fields,
+        }
+    }
+}
+
+fn collect_field_errors(errors: &ValidationErrors, prefix: &str, out: &mut FieldErrors) {
+    for (field, kind) in errors.errors() {
+        let path = if prefix.is_empty() {
+            field.to_string()
+        } else {
+            format!("{}.{}", prefix, field)
+        };
+
+        match kind {
+            ValidationErrorsKind::Field(list) => {
+                let messages = out.entry(path).or_default();
+                for error in list {
+                    messages.push(
+                        error
+                            .message
+                            .as_ref()
+                            .map(|m| m.to_string())
+                            .unwrap_or_else(|| error.code.to_string()),
+                    );
+                }
+            }
+            ValidationErrorsKind::Struct(nested) => collect_field_errors(nested, &path, out),
+            ValidationErrorsKind::List(items) => {
+                for (index, nested) in items {
+                    collect_field_errors(nested, &format!("{}[{}]", path, index), out);
+                }
+            }
+        }
+    }
+}
@@ -1,25 +1,217 @@
 use super::super::entity::address_entity::Address;
 use super::super::entity::contact_entity::Contact;
+use super::super::entity::contact_method_entity::{ContactEmail, ContactPhone};
+use super::super::entity::contact_version_entity::ContactChange;
+use super::super::entity::custom_field_entity::CustomFields;
+use super::super::error::DomainError;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+// The address book a request works in: the user's personal contacts, or
+// those of the organization they selected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tenant {
+    pub user_id: Uuid,
+    pub organization_id: Option<Uuid>,
+}
+
+impl Tenant {
+    pub fn personal(user_id: Uuid) -> Self {
+        Self { user_id, organization_id: None }
+    }
+
+    // Whether `contact` is in this address book, rather than shared into it.
+    pub fn owns(&self, contact: &Contact) -> bool {
+        match self.organization_id {
+            Some(organization_id) => contact.organization_id == Some(organization_id),
+            None => contact.organization_id.is_none() && contact.user_id == self.user_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContactSortField {
+    FirstName,
+    LastName,
+    Email,
+    #[default]
+    CreatedAt,
+    UpdatedAt,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SortDirection {
+    #[default]
+    Asc,
+    Desc,
+}
+
+// Position after the last row of a page for keyset pagination: the sort key
+// of that row (as text) plus its id as a tie-breaker.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContactCursor {
+    pub sort: ContactSortField,
+    pub value: String,
+    pub id: Uuid,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ContactQuery {
+    // Case-insensitive substring filters; `name` matches first or last name.
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<PhoneFilter>,
+    // Tag name (case-insensitive) and group id the contact must belong to.
+    pub tag: Option<String>,
+    pub group: Option<Uuid>,
+    // Custom field values the contact must have, compared exactly.
+    pub custom_fields: Option<CustomFields>,
+    pub sort: ContactSortField,
+    pub direction: SortDirection,
+    pub limit: i64,
+    // Offset and cursor are mutually exclusive; the usecase enforces that.
+    pub offset: i64,
+    pub cursor: Option<ContactCursor>,
+}
+
+// Digits a stored phone number must contain, formatting ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PhoneFilter {
+    // For numbers stored in E.164.
+    pub digits: String,
+    // For numbers stored before normalization, kept as typed (without a
+    // leading `+`).
+    pub legacy_digits: String,
+}
+
+// Addresses, emails and phones to save or delete along with their contact.
+// A saved entry is inserted, or updates the contact's entry with the same id.
+#[derive(Debug, Clone, Default)]
+pub struct ContactChanges {
+    pub addresses: Vec<Address>,
+    pub deleted_addresses: Vec<Uuid>,
+    pub emails: Vec<ContactEmail>,
+    pub deleted_emails: Vec<Uuid>,
+    pub phones: Vec<ContactPhone>,
+    pub deleted_phones: Vec<Uuid>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContactPage {
+    pub contacts: Vec<Contact>,
+    pub total: i64,
+    pub next_cursor: Option<ContactCursor>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContactSearchHit {
+    pub contact: Contact,
+    pub rank: f32,
+    // Matched text with full-text hits wrapped in <mark></mark>.
+    pub snippet: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct ContactSearchPage {
+    pub hits: Vec<ContactSearchHit>,
+    pub total: i64,
+}
+
+// Every query that finds contacts is restricted to a Tenant. The methods
+// taking a contact or contact id work on one found that way.
+//
+// Methods reading or writing contacts, addresses, emails or phones also run
+// as the tenant's user, which the database checks on its own (row-level security): rows the
+// user may not see are missing, and writes to them fail.
+//
+// Every write except a permanent delete adds an entry to the contact's
+// history in the same transaction: the contact as it is after the write,
+// numbered with its new version and attributed to the tenant's user.
 #[cfg_attr(test, mockall::automock)]
 #[async_trait]
 pub trait ContactRepository: Send + Sync {
-    async fn create_contact(&self, contact: &Contact) -> Result<Contact, String>;
-    async fn update_contact(&self, contact: &Contact) -> Result<Contact, String>;
-    async fn delete_contact(&self, id: &Uuid) -> Result<(), String>;
-    async fn find_contact_by_id(&self, id: &Uuid) -> Result<Option<Contact>, String>;
-    async fn find_contacts_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Contact>, String>;
-
-    // Address operations (nested in ContactRepository for simplicity as requested)
-    // Or we can assume addresses are loaded with contacts if needed, or separate methods.
-    // For this requirement "in every contact saved address", let's include address ops or relation.
-    // We will separate creating address to keep it flexible.
-    
-    async fn create_address(&self, address: &Address) -> Result<Address, String>;
-    async fn update_address(&self, address: &Address) -> Result<Address, String>;
-    async fn delete_address(&self, id: &Uuid) -> Result<(), String>;
-    async fn find_address_by_id(&self, id: &Uuid) -> Result<Option<Address>, String>;
-    async fn find_addresses_by_contact_id(&self, contact_id: &Uuid) -> Result<Vec<Address>, String>;
+    // Writes the contact together with `changes`, in one transaction, and
+    // records it as created.
+    //
+    // Saving an entry marked primary demotes the contact's other entries of
+    // that kind. A contact with entries always has a primary one: the oldest
+    // entry is promoted when needed. The contact's `email`, `phone` and
+    // `phone_raw` are copies of its primary email and phone, so the ones
+    // passed in are ignored.
+    async fn create_contact(
+        &self,
+        tenant: &Tenant,
+        contact: &Contact,
+        changes: &ContactChanges,
+    ) -> Result<Contact, DomainError>;
+    // Like create_contact, but bumps the version and records `action`.
+    // Applies only if the stored version still equals `contact.version`,
+    // otherwise PreconditionFailed. The owner (`user_id`, `organization_id`)
+    // never changes; a contact claiming another owner fails the same way.
+    async fn update_contact(
+        &self,
+        tenant: &Tenant,
+        contact: &Contact,
+        changes: &ContactChanges,
+        action: ContactChange,
+    ) -> Result<Contact, DomainError>;
+    // Saves `target` like update_contact, then moves the addresses, emails,
+    // phones, tags and group memberships of `sources` onto it and moves the
+    // sources to the trash, all in one transaction. Moved addresses, emails
+    // and phones are never primary, and emails and phones the target already
+    // has stay with the source. Every source must still be at its given
+    // version.
+    async fn merge_contacts(
+        &self,
+        tenant: &Tenant,
+        target: &Contact,
+        sources: &[Contact],
+    ) -> Result<Contact, DomainError>;
+    // Permanently removes the contact; its addresses go with it.
+    async fn delete_contact(&self, tenant: &Tenant, id: &Uuid) -> Result<(), DomainError>;
+    // Conditional on `version`, like update_contact.
+    async fn trash_contact(
+        &self,
+        tenant: &Tenant,
+        id: &Uuid,
+        version: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), DomainError>;
+    async fn restore_contact(&self, tenant: &Tenant, id: &Uuid) -> Result<Contact, DomainError>;
+    // A contact of the tenant or one shared with its user; None for anything
+    // else. Returns trashed contacts too; callers check `deleted_at`.
+    async fn find_contact_by_id(&self, tenant: &Tenant, id: &Uuid) -> Result<Option<Contact>, DomainError>;
+    // Listing and search methods skip trashed contacts.
+    async fn find_contacts(&self, tenant: &Tenant) -> Result<Vec<Contact>, DomainError>;
+    async fn find_trashed_contacts(&self, tenant: &Tenant) -> Result<Vec<Contact>, DomainError>;
+    // Permanently removes every contact trashed before `deleted_before`, in
+    // every tenant. The one method not restricted by row-level security.
+    async fn purge_trashed_contacts(&self, deleted_before: DateTime<Utc>) -> Result<u64, DomainError>;
+    async fn search_contacts(&self, tenant: &Tenant, query: &ContactQuery) -> Result<ContactPage, DomainError>;
+    // Relevance-ranked, typo-tolerant search over contact and address fields.
+    // `query` filters and offset/limit apply; sort and cursor are ignored.
+    async fn search_contacts_by_text(
+        &self,
+        tenant: &Tenant,
+        text: &str,
+        query: &ContactQuery,
+    ) -> Result<ContactSearchPage, DomainError>;
+
+    // Addresses, emails and phones are written through create_contact and
+    // update_contact.
+    async fn find_address_by_id(&self, tenant: &Tenant, id: &Uuid) -> Result<Option<Address>, DomainError>;
+    // Primary first, then oldest first.
+    async fn find_addresses_by_contact_id(&self, tenant: &Tenant, contact_id: &Uuid) -> Result<Vec<Address>, DomainError>;
+    async fn find_addresses_by_contact_ids(&self, tenant: &Tenant, contact_ids: &[Uuid]) -> Result<Vec<Address>, DomainError>;
+    async fn find_email_by_id(&self, tenant: &Tenant, id: &Uuid) -> Result<Option<ContactEmail>, DomainError>;
+    // Primary first, then oldest first.
+    async fn find_emails_by_contact_id(&self, tenant: &Tenant, contact_id: &Uuid) -> Result<Vec<ContactEmail>, DomainError>;
+    async fn find_emails_by_contact_ids(&self, tenant: &Tenant, contact_ids: &[Uuid]) -> Result<Vec<ContactEmail>, DomainError>;
+    async fn find_phone_by_id(&self, tenant: &Tenant, id: &Uuid) -> Result<Option<ContactPhone>, DomainError>;
+    async fn find_phones_by_contact_id(&self, tenant: &Tenant, contact_id: &Uuid) -> Result<Vec<ContactPhone>, DomainError>;
+    async fn find_phones_by_contact_ids(&self, tenant: &Tenant, contact_ids: &[Uuid]) -> Result<Vec<ContactPhone>, DomainError>;
 }
@@ -0,0 +1,12 @@
+use super::super::entity::contact_version_entity::ContactVersion;
+use super::super::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+// Entries are added by the contact repository, with the write they record.
+pub trait ContactVersionRepository: Send + Sync {
+    async fn find_versions_by_contact_id(&self, contact_id: &Uuid) -> Result<Vec<ContactVersion>, DomainError>;
+    async fn find_version(&self, contact_id: &Uuid, version: i32) -> Result<Option<ContactVersion>, DomainError>;
+}
@@ -0,0 +1,16 @@
+use super::super::entity::custom_field_entity::CustomFieldDefinition;
+use super::super::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait CustomFieldRepository: Send + Sync {
+    async fn create_field(&self, field: &CustomFieldDefinition) -> Result<CustomFieldDefinition, DomainError>;
+    // A rename also renames the key on every contact of the owner.
+    async fn update_field(&self, field: &CustomFieldDefinition) -> Result<CustomFieldDefinition, DomainError>;
+    // Also removes the field's values from every contact of the owner.
+    async fn delete_field(&self, id: &Uuid) -> Result<(), DomainError>;
+    async fn find_field_by_id(&self, id: &Uuid) -> Result<Option<CustomFieldDefinition>, DomainError>;
+    async fn find_fields_by_user_id(&self, user_id: &Uuid) -> Result<Vec<CustomFieldDefinition>, DomainError>;
+}
@@ -0,0 +1,17 @@
+use super::super::entity::group_entity::Group;
+use super::super::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait GroupRepository: Send + Sync {
+    async fn create_group(&self, group: &Group) -> Result<Group, DomainError>;
+    async fn update_group(&self, group: &Group) -> Result<Group, DomainError>;
+    async fn delete_group(&self, id: &Uuid) -> Result<(), DomainError>;
+    async fn find_group_by_id(&self, id: &Uuid) -> Result<Option<Group>, DomainError>;
+    async fn find_groups_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Group>, DomainError>;
+    // Both are idempotent, like the tag assignment methods.
+    async fn add_contact_to_group(&self, group_id: &Uuid, contact_id: &Uuid) -> Result<(), DomainError>;
+    async fn remove_contact_from_group(&self, group_id: &Uuid, contact_id: &Uuid) -> Result<(), DomainError>;
+}
@@ -1,2 +1,10 @@
 pub mod contact_repository;
+pub mod contact_version_repository;
+pub mod custom_field_repository;
+pub mod group_repository;
+pub mod organization_repository;
+pub mod refresh_token_repository;
+pub mod share_repository;
+pub mod tag_repository;
+pub mod token_revocation_repository;
 pub mod user_repository;
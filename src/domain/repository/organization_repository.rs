@@ -0,0 +1,37 @@
+use super::super::entity::organization_entity::{Organization, OrganizationMember, OrganizationRole};
+use super::super::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct MemberOrganization {
+    pub organization: Organization,
+    // The user's role in it.
+    pub role: OrganizationRole,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait OrganizationRepository: Send + Sync {
+    // Creates the organization with `owner` as its first member, in one
+    // transaction.
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+        owner: &OrganizationMember,
+    ) -> Result<Organization, DomainError>;
+    async fn update_organization(&self, organization: &Organization) -> Result<Organization, DomainError>;
+    // Its contacts and memberships go with it.
+    async fn delete_organization(&self, id: &Uuid) -> Result<(), DomainError>;
+    async fn find_organization_by_id(&self, id: &Uuid) -> Result<Option<Organization>, DomainError>;
+    // By name.
+    async fn find_organizations_by_user_id(&self, user_id: &Uuid) -> Result<Vec<MemberOrganization>, DomainError>;
+
+    // Conflict if the user is already a member.
+    async fn add_member(&self, member: &OrganizationMember) -> Result<OrganizationMember, DomainError>;
+    async fn update_member(&self, member: &OrganizationMember) -> Result<OrganizationMember, DomainError>;
+    async fn remove_member(&self, organization_id: &Uuid, user_id: &Uuid) -> Result<(), DomainError>;
+    async fn find_member(&self, organization_id: &Uuid, user_id: &Uuid) -> Result<Option<OrganizationMember>, DomainError>;
+    // Oldest first.
+    async fn find_members(&self, organization_id: &Uuid) -> Result<Vec<OrganizationMember>, DomainError>;
+}
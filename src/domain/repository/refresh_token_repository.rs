@@ -0,0 +1,17 @@
+use super::super::entity::refresh_token_entity::RefreshToken;
+use super::super::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait RefreshTokenRepository: Send + Sync {
+    async fn create_refresh_token(&self, token: &RefreshToken) -> Result<RefreshToken, DomainError>;
+    async fn find_refresh_token_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, DomainError>;
+
+    // Returns false when the token was already revoked, which lets the caller
+    // detect two concurrent refreshes racing on the same token.
+    async fn revoke_refresh_token(&self, id: &Uuid, replaced_by: Option<Uuid>) -> Result<bool, DomainError>;
+    async fn revoke_token_family(&self, family_id: &Uuid) -> Result<(), DomainError>;
+    async fn revoke_refresh_tokens_by_user_id(&self, user_id: &Uuid) -> Result<(), DomainError>;
+}
@@ -0,0 +1,29 @@
+use super::super::entity::contact_entity::Contact;
+use super::super::entity::share_entity::{ContactShare, SharePermission};
+use super::super::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct SharedContact {
+    pub contact: Contact,
+    // The strongest permission over all shares that reach the contact.
+    pub permission: SharePermission,
+}
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait ShareRepository: Send + Sync {
+    // Conflict if the grantee already has a share on the same contact or group.
+    async fn create_share(&self, share: &ContactShare) -> Result<ContactShare, DomainError>;
+    async fn update_share(&self, share: &ContactShare) -> Result<ContactShare, DomainError>;
+    async fn delete_share(&self, id: &Uuid) -> Result<(), DomainError>;
+    async fn find_share_by_id(&self, id: &Uuid) -> Result<Option<ContactShare>, DomainError>;
+    async fn find_shares_by_contact_id(&self, contact_id: &Uuid) -> Result<Vec<ContactShare>, DomainError>;
+    async fn find_shares_by_group_id(&self, group_id: &Uuid) -> Result<Vec<ContactShare>, DomainError>;
+    // What `grantee_id` may do with the contact through direct and group
+    // shares; None without any.
+    async fn find_permission(&self, contact_id: &Uuid, grantee_id: &Uuid) -> Result<Option<SharePermission>, DomainError>;
+    // Contacts outside the trash shared with `grantee_id`, by first name.
+    async fn find_shared_contacts(&self, grantee_id: &Uuid) -> Result<Vec<SharedContact>, DomainError>;
+}
@@ -0,0 +1,18 @@
+use super::super::entity::tag_entity::{ContactTag, Tag};
+use super::super::error::DomainError;
+use async_trait::async_trait;
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TagRepository: Send + Sync {
+    async fn create_tag(&self, tag: &Tag) -> Result<Tag, DomainError>;
+    async fn update_tag(&self, tag: &Tag) -> Result<Tag, DomainError>;
+    async fn delete_tag(&self, id: &Uuid) -> Result<(), DomainError>;
+    async fn find_tag_by_id(&self, id: &Uuid) -> Result<Option<Tag>, DomainError>;
+    async fn find_tags_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Tag>, DomainError>;
+    // Both are idempotent: tagging twice or removing a missing tag is not an error.
+    async fn add_tag_to_contact(&self, contact_id: &Uuid, tag_id: &Uuid) -> Result<(), DomainError>;
+    async fn remove_tag_from_contact(&self, contact_id: &Uuid, tag_id: &Uuid) -> Result<(), DomainError>;
+    async fn find_tags_by_contact_ids(&self, contact_ids: &[Uuid]) -> Result<Vec<ContactTag>, DomainError>;
+}
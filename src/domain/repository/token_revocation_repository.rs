@@ -0,0 +1,17 @@
+use super::super::entity::token_revocation_entity::{RevokedToken, UserTokenRevocation};
+use super::super::error::DomainError;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+#[cfg_attr(test, mockall::automock)]
+#[async_trait]
+pub trait TokenRevocationRepository: Send + Sync {
+    async fn revoke_token(&self, token: &RevokedToken) -> Result<(), DomainError>;
+    async fn revoke_user_tokens(&self, user_id: &Uuid, revoked_before: DateTime<Utc>) -> Result<(), DomainError>;
+
+    // Used to (re)build the in-process cache; expired entries are skipped.
+    async fn find_active_revoked_tokens(&self) -> Result<Vec<RevokedToken>, DomainError>;
+    async fn find_user_token_revocations(&self) -> Result<Vec<UserTokenRevocation>, DomainError>;
+    async fn delete_expired_revoked_tokens(&self) -> Result<u64, DomainError>;
+}
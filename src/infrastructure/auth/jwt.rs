@@ -1,53 +1,189 @@
-use chrono::{Duration, Utc};
-use jsonwebtoken::{encode, decode, Header, Algorithm, Validation, EncodingKey, DecodingKey};
+use super::keys::KeyRing;
+use super::revocation::RevocationCache;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, encode, Header, Validation};
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::env;
+use std::path::Path;
+use std::sync::Arc;
 use uuid::Uuid;
 
-#[derive(Debug, Serialize, Deserialize)]
+const DEFAULT_ACCESS_TOKEN_TTL_MINUTES: i64 = 15;
+const DEFAULT_REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Claims {
     pub sub: String, // user_id
     pub exp: usize,
     pub iat: usize,
+    // `iat` in milliseconds, so logout-all can tell tokens issued just before
+    // it from a login right after it. Missing from older tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iat_ms: Option<i64>,
+    pub jti: String,
 }
 
 pub struct JwtService {
-    secret: String,
+    keys: KeyRing,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
+    revocations: Arc<RevocationCache>,
+}
+
+impl Default for JwtService {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl JwtService {
+    // Uses the PEM keys in JWT_KEYS_DIR when set (see `KeyRing::from_dir`),
+    // falling back to HS256 with JWT_SECRET.
     pub fn new() -> Self {
-        let secret = env::var("JWT_SECRET").expect("JWT_SECRET must be set");
-        Self { secret }
+        let keys = match env::var("JWT_KEYS_DIR") {
+            Ok(dir) => {
+                let active_kid = env::var("JWT_ACTIVE_KID").expect("JWT_ACTIVE_KID must be set when JWT_KEYS_DIR is used");
+                KeyRing::from_dir(Path::new(&dir), &active_kid).expect("Failed to load JWT signing keys")
+            }
+            Err(_) => KeyRing::from_secret(&env::var("JWT_SECRET").expect("JWT_SECRET must be set")),
+        };
+
+        Self::with_keys(keys)
+    }
+
+    pub fn with_keys(keys: KeyRing) -> Self {
+        let access_minutes = env::var("JWT_ACCESS_TOKEN_TTL_MINUTES")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_ACCESS_TOKEN_TTL_MINUTES);
+        let refresh_days = env::var("JWT_REFRESH_TOKEN_TTL_DAYS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_REFRESH_TOKEN_TTL_DAYS);
+
+        Self {
+            keys,
+            access_token_ttl: Duration::minutes(access_minutes),
+            refresh_token_ttl: Duration::days(refresh_days),
+            revocations: Arc::new(RevocationCache::new()),
+        }
+    }
+
+    pub fn refresh_token_ttl(&self) -> Duration {
+        self.refresh_token_ttl
+    }
+
+    pub fn revocations(&self) -> &Arc<RevocationCache> {
+        &self.revocations
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        self.keys.jwks()
     }
 
     pub fn generate_token(&self, user_id: Uuid) -> Result<String, String> {
-        let expiration = Utc::now()
-            .checked_add_signed(Duration::hours(24))
+        let now = Utc::now();
+        let expiration = now
+            .checked_add_signed(self.access_token_ttl)
             .expect("valid timestamp")
             .timestamp();
 
         let claims = Claims {
             sub: user_id.to_string(),
             exp: expiration as usize,
-            iat: Utc::now().timestamp() as usize,
+            iat: now.timestamp() as usize,
+            iat_ms: Some(now.timestamp_millis()),
+            jti: Uuid::new_v4().to_string(),
         };
 
-        encode(
-            &Header::default(),
-            &claims,
-            &EncodingKey::from_secret(self.secret.as_bytes()),
-        )
-        .map_err(|e| e.to_string())
+        let key = self.keys.active();
+        let mut header = Header::new(key.algorithm);
+        if !key.kid.is_empty() {
+            header.kid = Some(key.kid.clone());
+        }
+
+        encode(&header, &claims, key.encoding_key()).map_err(|e| e.to_string())
     }
 
     pub fn verify_token(&self, token: &str) -> Result<Claims, String> {
-        decode::<Claims>(
-            token,
-            &DecodingKey::from_secret(self.secret.as_bytes()),
-            &Validation::new(Algorithm::HS256),
-        )
-        .map(|data| data.claims)
-        .map_err(|e| e.to_string())
+        let header = decode_header(token).map_err(|e| e.to_string())?;
+        let key = self
+            .keys
+            .find(header.kid.as_deref())
+            .ok_or_else(|| "Unknown signing key".to_string())?;
+
+        // The algorithm is pinned by the key, never taken from the token header.
+        let claims = decode::<Claims>(token, key.decoding_key(), &Validation::new(key.algorithm))
+            .map(|data| data.claims)
+            .map_err(|e| e.to_string())?;
+
+        let jti = Uuid::parse_str(&claims.jti).map_err(|_| "Invalid token id".to_string())?;
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| "Invalid user ID in token".to_string())?;
+
+        // Older tokens only have whole seconds; taking the start of the second
+        // errs on the side of revoking them.
+        let issued_at = claims
+            .iat_ms
+            .and_then(DateTime::from_timestamp_millis)
+            .or_else(|| DateTime::from_timestamp(claims.iat as i64, 0))
+            .ok_or_else(|| "Invalid issue time".to_string())?;
+
+        if self.revocations.is_revoked(&jti, &user_id, issued_at) {
+            return Err("Token has been revoked".to_string());
+        }
+
+        Ok(claims)
+    }
+
+    // Refresh tokens are opaque random strings; only their hash is persisted,
+    // so a database leak does not hand out usable tokens.
+    pub fn generate_refresh_token(&self) -> String {
+        let mut bytes = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        URL_SAFE_NO_PAD.encode(bytes)
+    }
+
+    pub fn hash_refresh_token(&self, token: &str) -> String {
+        hex::encode(Sha256::digest(token.as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::auth::keys::{KeyStatus, SigningKey};
+
+    const RSA_PEM: &[u8] = include_bytes!("../../../tests/fixtures/rs256_test_key.pem");
+
+    #[test]
+    fn test_token_signed_before_rotation_still_verifies() {
+        let ed_pem = {
+            use ring::signature::Ed25519KeyPair;
+            let pkcs8 = Ed25519KeyPair::generate_pkcs8(&ring::rand::SystemRandom::new()).unwrap();
+            pem::encode(&pem::Pem::new("PRIVATE KEY", pkcs8.as_ref().to_vec())).into_bytes()
+        };
+
+        let before = JwtService::with_keys(
+            KeyRing::new(vec![SigningKey::from_pem("old", RSA_PEM, KeyStatus::Active).unwrap()]).unwrap(),
+        );
+        let old_token = before.generate_token(Uuid::new_v4()).unwrap();
+        assert_eq!(decode_header(&old_token).unwrap().kid.as_deref(), Some("old"));
+
+        let after = JwtService::with_keys(
+            KeyRing::new(vec![
+                SigningKey::from_pem("old", RSA_PEM, KeyStatus::Retired).unwrap(),
+                SigningKey::from_pem("new", &ed_pem, KeyStatus::Active).unwrap(),
+            ])
+            .unwrap(),
+        );
+        let new_token = after.generate_token(Uuid::new_v4()).unwrap();
+
+        assert!(after.verify_token(&old_token).is_ok());
+        assert!(after.verify_token(&new_token).is_ok());
+        assert_eq!(before.verify_token(&new_token).err().unwrap(), "Unknown signing key");
     }
 }
@@ -0,0 +1,242 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::jwk::{
+    AlgorithmParameters, CommonParameters, EllipticCurve, Jwk, JwkSet, KeyAlgorithm,
+    OctetKeyPairParameters, OctetKeyPairType, PublicKeyUse, RSAKeyParameters, RSAKeyType,
+};
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey};
+use ring::signature::{Ed25519KeyPair, KeyPair, RsaKeyPair, RsaPublicKeyComponents};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyStatus {
+    // Signs new tokens and verifies existing ones.
+    Active,
+    // Only verifies tokens issued before a rotation. Keep a retired key in
+    // place until the longest-lived token it signed has expired.
+    Retired,
+}
+
+pub struct SigningKey {
+    pub kid: String,
+    pub algorithm: Algorithm,
+    pub status: KeyStatus,
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    // Public half published through JWKS; None for shared secrets.
+    jwk: Option<Jwk>,
+}
+
+impl SigningKey {
+    pub fn from_secret(kid: &str, secret: &[u8]) -> Self {
+        Self {
+            kid: kid.to_string(),
+            algorithm: Algorithm::HS256,
+            status: KeyStatus::Active,
+            encoding_key: EncodingKey::from_secret(secret),
+            decoding_key: DecodingKey::from_secret(secret),
+            jwk: None,
+        }
+    }
+
+    // Accepts a PEM-encoded RSA (PKCS#1 or PKCS#8) or Ed25519 (PKCS#8) private
+    // key; the algorithm is derived from the key type.
+    pub fn from_pem(kid: &str, pem_bytes: &[u8], status: KeyStatus) -> Result<Self, String> {
+        let parsed = pem::parse(pem_bytes).map_err(|e| format!("{}: {}", kid, e))?;
+        let der = parsed.contents();
+
+        let rsa = match parsed.tag() {
+            "RSA PRIVATE KEY" => RsaKeyPair::from_der(der).ok(),
+            "PRIVATE KEY" => RsaKeyPair::from_pkcs8(der).ok(),
+            tag => return Err(format!("{}: unsupported PEM block {}", kid, tag)),
+        };
+
+        if let Some(key_pair) = rsa {
+            let public: RsaPublicKeyComponents<Vec<u8>> = key_pair.public().into();
+            let n = URL_SAFE_NO_PAD.encode(&public.n);
+            let e = URL_SAFE_NO_PAD.encode(&public.e);
+
+            return Ok(Self {
+                kid: kid.to_string(),
+                algorithm: Algorithm::RS256,
+                status,
+                encoding_key: EncodingKey::from_rsa_pem(pem_bytes).map_err(|e| format!("{}: {}", kid, e))?,
+                decoding_key: DecodingKey::from_rsa_components(&n, &e).map_err(|e| format!("{}: {}", kid, e))?,
+                jwk: Some(Jwk {
+                    common: public_common_parameters(kid, KeyAlgorithm::RS256),
+                    algorithm: AlgorithmParameters::RSA(RSAKeyParameters {
+                        key_type: RSAKeyType::RSA,
+                        n,
+                        e,
+                    }),
+                }),
+            });
+        }
+
+        let key_pair = Ed25519KeyPair::from_pkcs8_maybe_unchecked(der)
+            .map_err(|_| format!("{}: key is neither RSA nor Ed25519", kid))?;
+        let x = URL_SAFE_NO_PAD.encode(key_pair.public_key().as_ref());
+
+        Ok(Self {
+            kid: kid.to_string(),
+            algorithm: Algorithm::EdDSA,
+            status,
+            encoding_key: EncodingKey::from_ed_der(der),
+            decoding_key: DecodingKey::from_ed_components(&x).map_err(|e| format!("{}: {}", kid, e))?,
+            jwk: Some(Jwk {
+                common: public_common_parameters(kid, KeyAlgorithm::EdDSA),
+                algorithm: AlgorithmParameters::OctetKeyPair(OctetKeyPairParameters {
+                    key_type: OctetKeyPairType::OctetKeyPair,
+                    curve: EllipticCurve::Ed25519,
+                    x,
+                }),
+            }),
+        })
+    }
+
+    pub fn encoding_key(&self) -> &EncodingKey {
+        &self.encoding_key
+    }
+
+    pub fn decoding_key(&self) -> &DecodingKey {
+        &self.decoding_key
+    }
+}
+
+fn public_common_parameters(kid: &str, algorithm: KeyAlgorithm) -> CommonParameters {
+    CommonParameters {
+        public_key_use: Some(PublicKeyUse::Signature),
+        key_algorithm: Some(algorithm),
+        key_id: Some(kid.to_string()),
+        ..Default::default()
+    }
+}
+
+pub struct KeyRing {
+    keys: HashMap<String, SigningKey>,
+    active_kid: String,
+}
+
+impl KeyRing {
+    pub fn new(keys: Vec<SigningKey>) -> Result<Self, String> {
+        let mut active = keys.iter().filter(|k| k.status == KeyStatus::Active);
+        let active_kid = match (active.next(), active.next()) {
+            (Some(key), None) => key.kid.clone(),
+            (None, _) => return Err("No active signing key".to_string()),
+            (Some(_), Some(_)) => return Err("More than one active signing key".to_string()),
+        };
+
+        Ok(Self {
+            keys: keys.into_iter().map(|k| (k.kid.clone(), k)).collect(),
+            active_kid,
+        })
+    }
+
+    // Legacy single-secret HS256 setup. Tokens carry no `kid`, matching what
+    // was issued before key rotation existed.
+    pub fn from_secret(secret: &str) -> Self {
+        let key = SigningKey::from_secret("", secret.as_bytes());
+        Self {
+            active_kid: key.kid.clone(),
+            keys: HashMap::from([(key.kid.clone(), key)]),
+        }
+    }
+
+    // Loads every `<kid>.pem` in `dir`. The key named by `active_kid` signs new
+    // tokens; all others are retired and only used for verification.
+    pub fn from_dir(dir: &Path, active_kid: &str) -> Result<Self, String> {
+        let entries = fs::read_dir(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+
+        let mut keys = Vec::new();
+        for entry in entries {
+            let path = entry.map_err(|e| e.to_string())?.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("pem") {
+                continue;
+            }
+            let kid = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .ok_or_else(|| format!("{}: invalid key file name", path.display()))?;
+            let pem_bytes = fs::read(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+            let status = if kid == active_kid { KeyStatus::Active } else { KeyStatus::Retired };
+            keys.push(SigningKey::from_pem(kid, &pem_bytes, status)?);
+        }
+
+        Self::new(keys)
+    }
+
+    pub fn active(&self) -> &SigningKey {
+        &self.keys[&self.active_kid]
+    }
+
+    // Tokens without a `kid` predate rotation and are checked against the
+    // active key.
+    pub fn find(&self, kid: Option<&str>) -> Option<&SigningKey> {
+        match kid {
+            Some(kid) => self.keys.get(kid),
+            None => self.keys.get(&self.active_kid),
+        }
+    }
+
+    pub fn jwks(&self) -> JwkSet {
+        let mut keys: Vec<&SigningKey> = self.keys.values().collect();
+        keys.sort_by(|a, b| a.kid.cmp(&b.kid));
+
+        JwkSet {
+            keys: keys.into_iter().filter_map(|k| k.jwk.clone()).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::rand::SystemRandom;
+
+    const RSA_PEM: &[u8] = include_bytes!("../../../tests/fixtures/rs256_test_key.pem");
+
+    fn ed25519_pem() -> Vec<u8> {
+        let pkcs8 = Ed25519KeyPair::generate_pkcs8(&SystemRandom::new()).unwrap();
+        pem::encode(&pem::Pem::new("PRIVATE KEY", pkcs8.as_ref().to_vec())).into_bytes()
+    }
+
+    #[test]
+    fn test_key_algorithm_detected_from_pem() {
+        let rsa = SigningKey::from_pem("rsa", RSA_PEM, KeyStatus::Active).unwrap();
+        let ed = SigningKey::from_pem("ed", &ed25519_pem(), KeyStatus::Retired).unwrap();
+
+        assert_eq!(rsa.algorithm, Algorithm::RS256);
+        assert_eq!(ed.algorithm, Algorithm::EdDSA);
+    }
+
+    #[test]
+    fn test_jwks_publishes_public_keys_only() {
+        let ring = KeyRing::new(vec![
+            SigningKey::from_pem("2024-01", RSA_PEM, KeyStatus::Retired).unwrap(),
+            SigningKey::from_pem("2024-02", &ed25519_pem(), KeyStatus::Active).unwrap(),
+        ])
+        .unwrap();
+
+        let jwks = ring.jwks();
+        assert_eq!(jwks.keys.len(), 2);
+        assert!(jwks.find("2024-01").is_some());
+        assert_eq!(ring.active().kid, "2024-02");
+
+        let json = serde_json::to_value(&jwks).unwrap();
+        assert!(json["keys"][0].get("d").is_none());
+        assert!(KeyRing::from_secret("secret").jwks().keys.is_empty());
+    }
+
+    #[test]
+    fn test_key_ring_requires_single_active_key() {
+        let none = KeyRing::new(vec![SigningKey::from_pem("a", RSA_PEM, KeyStatus::Retired).unwrap()]);
+        let two = KeyRing::new(vec![
+            SigningKey::from_pem("a", RSA_PEM, KeyStatus::Active).unwrap(),
+            SigningKey::from_pem("b", &ed25519_pem(), KeyStatus::Active).unwrap(),
+        ]);
+
+        assert!(none.is_err());
+        assert!(two.is_err());
+    }
+}
@@ -1,2 +1,4 @@
 pub mod jwt;
+pub mod keys;
 pub mod password;
+pub mod revocation;
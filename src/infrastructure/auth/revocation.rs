@@ -0,0 +1,102 @@
+use crate::domain::error::DomainError;
+use crate::domain::repository::token_revocation_repository::TokenRevocationRepository;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use uuid::Uuid;
+
+// In-process mirror of the revocation tables so `verify_token` never has to
+// hit the database. Postgres stays the source of truth; the cache is written
+// through on logout and periodically reloaded to pick up revocations made by
+// other instances.
+#[derive(Default)]
+pub struct RevocationCache {
+    tokens: RwLock<HashMap<Uuid, DateTime<Utc>>>, // jti -> token expiry
+    users: RwLock<HashMap<Uuid, DateTime<Utc>>>,  // user_id -> revoked_before
+}
+
+impl RevocationCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn revoke_token(&self, jti: Uuid, expires_at: DateTime<Utc>) {
+        self.tokens.write().unwrap().insert(jti, expires_at);
+    }
+
+    pub fn revoke_user_tokens(&self, user_id: Uuid, revoked_before: DateTime<Utc>) {
+        let mut users = self.users.write().unwrap();
+        let entry = users.entry(user_id).or_insert(revoked_before);
+        if *entry < revoked_before {
+            *entry = revoked_before;
+        }
+    }
+
+    pub fn is_revoked(&self, jti: &Uuid, user_id: &Uuid, issued_at: DateTime<Utc>) -> bool {
+        if self.tokens.read().unwrap().contains_key(jti) {
+            return true;
+        }
+
+        match self.users.read().unwrap().get(user_id) {
+            Some(revoked_before) => issued_at <= *revoked_before,
+            None => false,
+        }
+    }
+
+    // Merges rather than replaces, so a logout written to the cache while the
+    // reload query was in flight is not lost; expired entries are dropped.
+    pub async fn reload(&self, repo: &dyn TokenRevocationRepository) -> Result<(), DomainError> {
+        let tokens = repo.find_active_revoked_tokens().await?;
+        let users = repo.find_user_token_revocations().await?;
+
+        {
+            let now = Utc::now();
+            let mut cached = self.tokens.write().unwrap();
+            cached.retain(|_, expires_at| *expires_at > now);
+            cached.extend(tokens.into_iter().map(|t| (t.jti, t.expires_at)));
+        }
+        for u in users {
+            self.revoke_user_tokens(u.user_id, u.revoked_before);
+        }
+        Ok(())
+    }
+}
+
+pub fn spawn_revocation_sync(
+    cache: Arc<RevocationCache>,
+    repo: Arc<dyn TokenRevocationRepository>,
+    interval: Duration,
+) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            if let Err(e) = repo.delete_expired_revoked_tokens().await {
+                tracing::warn!("failed to purge expired revoked tokens: {}", e);
+            }
+            if let Err(e) = cache.reload(repo.as_ref()).await {
+                tracing::warn!("failed to reload token revocations: {}", e);
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_revocation_compares_sub_second_issue_times() {
+        let cache = RevocationCache::new();
+        let user_id = Uuid::new_v4();
+        let at = |millis| DateTime::from_timestamp_millis(millis).unwrap();
+        cache.revoke_user_tokens(user_id, at(1_700_000_000_500));
+
+        // Same second as the cutoff, on either side of it
+        assert!(cache.is_revoked(&Uuid::new_v4(), &user_id, at(1_700_000_000_400)));
+        assert!(!cache.is_revoked(&Uuid::new_v4(), &user_id, at(1_700_000_000_600)));
+        assert!(!cache.is_revoked(&Uuid::new_v4(), &Uuid::new_v4(), at(1_700_000_000_400)));
+    }
+}
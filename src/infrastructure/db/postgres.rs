@@ -1,3 +1,4 @@
+use crate::domain::error::DomainError;
 use sqlx::postgres::PgPoolOptions;
 use sqlx::{Pool, Postgres};
 use std::env;
@@ -10,3 +11,23 @@ pub async fn create_pool() -> Pool<Postgres> {
         .await
         .expect("Failed to create pool")
 }
+
+impl From<sqlx::Error> for DomainError {
+    fn from(e: sqlx::Error) -> Self {
+        match &e {
+            sqlx::Error::RowNotFound => DomainError::NotFound("Record not found".to_string()),
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                DomainError::Conflict("Record already exists".to_string())
+            }
+            sqlx::Error::Database(db) if db.is_foreign_key_violation() => {
+                DomainError::validation("Referenced record does not exist")
+            }
+            // insufficient_privilege, raised when a write breaks a row-level
+            // security policy.
+            sqlx::Error::Database(db) if db.code().as_deref() == Some("42501") => {
+                DomainError::Forbidden("You do not have access to this record".to_string())
+            }
+            _ => DomainError::Infrastructure(e.to_string()),
+        }
+    }
+}
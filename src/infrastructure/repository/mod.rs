@@ -1,2 +1,10 @@
 pub mod postgres_contact_repository;
+pub mod postgres_contact_version_repository;
+pub mod postgres_custom_field_repository;
+pub mod postgres_group_repository;
+pub mod postgres_organization_repository;
+pub mod postgres_refresh_token_repository;
+pub mod postgres_share_repository;
+pub mod postgres_tag_repository;
+pub mod postgres_token_revocation_repository;
 pub mod postgres_user_repository;
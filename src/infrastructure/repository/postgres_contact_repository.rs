@@ -1,184 +1,898 @@
 use crate::domain::{
-    entity::{address_entity::Address, contact_entity::Contact},
-    repository::contact_repository::ContactRepository,
+    entity::{
+        address_entity::Address,
+        contact_entity::Contact,
+        contact_method_entity::{ContactEmail, ContactPhone},
+        contact_version_entity::{ContactChange, ContactSnapshot},
+    },
+    error::DomainError,
+    repository::contact_repository::{
+        ContactChanges, ContactCursor, ContactPage, ContactQuery, ContactRepository, ContactSearchHit, ContactSearchPage,
+        ContactSortField, SortDirection, Tenant,
+    },
 };
 use async_trait::async_trait;
-use sqlx::{Pool, Postgres};
+use chrono::{DateTime, SecondsFormat, Utc};
+use sqlx::{types::Json, PgConnection, Pool, Postgres, QueryBuilder, Transaction};
 use uuid::Uuid;
 
 pub struct PostgresContactRepository {
     pool: Pool<Postgres>,
 }
 
+// The role the row-level security policies on contacts and addresses apply
+// to (see the migration).
+const RESTRICTED_ROLE: &str = "contacts_app";
+
 impl PostgresContactRepository {
     pub fn new(pool: Pool<Postgres>) -> Self {
         Self { pool }
     }
+
+    // A transaction acting as the tenant's user. Both settings are local to
+    // it, so pooled connections go back unrestricted.
+    async fn begin(&self, tenant: &Tenant) -> Result<Transaction<'static, Postgres>, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        sqlx::query("SELECT set_config('role', $1, true), set_config('app.current_user_id', $2, true)")
+            .bind(RESTRICTED_ROLE)
+            .bind(tenant.user_id.to_string())
+            .execute(&mut *tx)
+            .await?;
+        Ok(tx)
+    }
+}
+
+// Nullable text columns are sorted as '' so keyset comparisons stay total.
+fn sort_expression(sort: ContactSortField) -> &'static str {
+    match sort {
+        ContactSortField::FirstName => "first_name",
+        ContactSortField::LastName => "COALESCE(last_name, '')",
+        ContactSortField::Email => "COALESCE(email, '')",
+        ContactSortField::CreatedAt => "created_at",
+        ContactSortField::UpdatedAt => "updated_at",
+    }
+}
+
+fn sort_value(contact: &Contact, sort: ContactSortField) -> String {
+    match sort {
+        ContactSortField::FirstName => contact.first_name.clone(),
+        ContactSortField::LastName => contact.last_name.clone().unwrap_or_default(),
+        ContactSortField::Email => contact.email.clone().unwrap_or_default(),
+        ContactSortField::CreatedAt => contact.created_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+        ContactSortField::UpdatedAt => contact.updated_at.to_rfc3339_opts(SecondsFormat::Micros, true),
+    }
+}
+
+fn modified_concurrently() -> DomainError {
+    DomainError::PreconditionFailed("Contact was modified by another request".to_string())
+}
+
+fn like_pattern(term: &str) -> String {
+    let escaped = term.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+// Restricts `contacts` to the tenant's address book (see Tenant::owns).
+fn push_tenant(builder: &mut QueryBuilder<'_, Postgres>, tenant: &Tenant) {
+    match tenant.organization_id {
+        Some(organization_id) => {
+            builder.push("contacts.organization_id = ").push_bind(organization_id);
+        }
+        None => {
+            builder
+                .push("contacts.organization_id IS NULL AND contacts.user_id = ")
+                .push_bind(tenant.user_id);
+        }
+    }
+}
+
+fn push_contact_filters(builder: &mut QueryBuilder<'_, Postgres>, tenant: &Tenant, query: &ContactQuery) {
+    builder.push(" WHERE ");
+    push_tenant(builder, tenant);
+    builder.push(" AND deleted_at IS NULL");
+
+    if let Some(name) = &query.name {
+        let pattern = like_pattern(name);
+        builder
+            .push(" AND (first_name ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR last_name ILIKE ")
+            .push_bind(pattern)
+            .push(")");
+    }
+    if let Some(email) = &query.email {
+        builder
+            .push(" AND EXISTS (SELECT 1 FROM contact_emails ce")
+            .push(" WHERE ce.contact_id = contacts.id AND ce.email ILIKE ")
+            .push_bind(like_pattern(email))
+            .push(")");
+    }
+    if let Some(phone) = &query.phone {
+        builder
+            .push(" AND EXISTS (SELECT 1 FROM contact_phones cp")
+            .push(" WHERE cp.contact_id = contacts.id AND (regexp_replace(cp.phone, '[^0-9]', '', 'g') LIKE ")
+            .push_bind(like_pattern(&phone.digits))
+            .push(" OR (cp.phone NOT LIKE '+%' AND regexp_replace(cp.phone, '[^0-9]', '', 'g') LIKE ")
+            .push_bind(like_pattern(&phone.legacy_digits))
+            .push(")))");
+    }
+    if let Some(tag) = &query.tag {
+        builder
+            .push(" AND EXISTS (SELECT 1 FROM contact_tags ct JOIN tags t ON t.id = ct.tag_id")
+            .push(" WHERE ct.contact_id = contacts.id AND lower(t.name) = lower(")
+            .push_bind(tag.clone())
+            .push("))");
+    }
+    if let Some(group) = &query.group {
+        builder
+            .push(" AND EXISTS (SELECT 1 FROM contact_group_members gm")
+            .push(" WHERE gm.contact_id = contacts.id AND gm.group_id = ")
+            .push_bind(*group)
+            .push(")");
+    }
+    if let Some(custom_fields) = &query.custom_fields {
+        builder
+            .push(" AND contacts.custom_fields @> ")
+            .push_bind(Json(custom_fields.clone()));
+    }
+}
+
+// pg_trgm's default of 0.6 misses single-letter typos in short words
+// ("jakrta" vs "jakarta" scores 0.5).
+const WORD_SIMILARITY_THRESHOLD: &str = "0.4";
+
+// Matches on the full-text vectors or, for typos, on trigram word similarity
+// (`<%`, see WORD_SIMILARITY_THRESHOLD) of the contact or any address.
+fn push_text_match(builder: &mut QueryBuilder<'_, Postgres>, text: &str) {
+    let term = text.to_lowercase();
+    builder
+        .push(" AND (contacts.search_vector @@ websearch_to_tsquery('simple', ")
+        .push_bind(text.to_string())
+        .push(") OR ")
+        .push_bind(term.clone())
+        .push(" <% contacts.search_text")
+        .push(" OR EXISTS (SELECT 1 FROM addresses a WHERE a.contact_id = contacts.id")
+        .push(" AND (a.search_vector @@ websearch_to_tsquery('simple', ")
+        .push_bind(text.to_string())
+        .push(") OR ")
+        .push_bind(term)
+        .push(" <% a.search_text)))");
+}
+
+// Clears the primary flag of every other entry of the contact, before
+// `except` is saved as primary.
+async fn demote_others(conn: &mut PgConnection, table: &str, contact_id: &Uuid, except: &Uuid) -> Result<(), DomainError> {
+    sqlx::query(&format!(
+        "UPDATE {} SET is_primary = FALSE WHERE contact_id = $1 AND id <> $2 AND is_primary",
+        table
+    ))
+    .bind(contact_id)
+    .bind(except)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+// Promotes the oldest entry of the contact if none is primary.
+async fn promote_oldest(conn: &mut PgConnection, table: &str, contact_id: &Uuid) -> Result<(), DomainError> {
+    sqlx::query(&format!(
+        "UPDATE {table} SET is_primary = TRUE 
+         WHERE id = (SELECT id FROM {table} WHERE contact_id = $1 ORDER BY created_at, id LIMIT 1) 
+           AND NOT EXISTS (SELECT 1 FROM {table} WHERE contact_id = $1 AND is_primary)"
+    ))
+    .bind(contact_id)
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+// Promotes the oldest entry if none is primary, then copies the primary
+// entry's `columns` into the contact row (NULL when there are no entries).
+async fn sync_primary(conn: &mut PgConnection, table: &str, columns: &str, contact_id: &Uuid) -> Result<(), DomainError> {
+    promote_oldest(conn, table, contact_id).await?;
+    sqlx::query(&format!(
+        "UPDATE contacts SET ({columns}) = (SELECT {columns} FROM {table} WHERE contact_id = $1 AND is_primary) 
+         WHERE id = $1"
+    ))
+    .bind(contact_id)
+    .execute(&mut *conn)
+    .await?;
+    Ok(())
+}
+
+// Deletes the entries of the contact with the given ids from `table`.
+async fn delete_entries(conn: &mut PgConnection, table: &str, contact_id: &Uuid, ids: &[Uuid]) -> Result<(), DomainError> {
+    if ids.is_empty() {
+        return Ok(());
+    }
+    sqlx::query(&format!("DELETE FROM {} WHERE contact_id = $1 AND id = ANY($2)", table))
+        .bind(contact_id)
+        .bind(ids)
+        .execute(conn)
+        .await?;
+    Ok(())
+}
+
+// Applies `changes` to the contact's entries: deletes first, then saves. An
+// id belonging to another contact is left alone.
+async fn save_changes(conn: &mut PgConnection, contact_id: &Uuid, changes: &ContactChanges) -> Result<(), DomainError> {
+    delete_entries(conn, "addresses", contact_id, &changes.deleted_addresses).await?;
+    for address in &changes.addresses {
+        if address.is_primary {
+            demote_others(conn, "addresses", contact_id, &address.id).await?;
+        }
+        sqlx::query(
+            "INSERT INTO addresses (id, contact_id, street, city, province, country, postal_code, kind, is_primary, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11) 
+             ON CONFLICT (id) DO UPDATE 
+             SET street = EXCLUDED.street, city = EXCLUDED.city, province = EXCLUDED.province, country = EXCLUDED.country, 
+                 postal_code = EXCLUDED.postal_code, kind = EXCLUDED.kind, is_primary = EXCLUDED.is_primary, updated_at = EXCLUDED.updated_at 
+             WHERE addresses.contact_id = EXCLUDED.contact_id"
+        )
+        .bind(address.id)
+        .bind(contact_id)
+        .bind(&address.street)
+        .bind(&address.city)
+        .bind(&address.province)
+        .bind(&address.country)
+        .bind(&address.postal_code)
+        .bind(address.kind)
+        .bind(address.is_primary)
+        .bind(address.created_at)
+        .bind(address.updated_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+    if !changes.addresses.is_empty() || !changes.deleted_addresses.is_empty() {
+        promote_oldest(conn, "addresses", contact_id).await?;
+    }
+
+    delete_entries(conn, "contact_emails", contact_id, &changes.deleted_emails).await?;
+    for email in &changes.emails {
+        if email.is_primary {
+            demote_others(conn, "contact_emails", contact_id, &email.id).await?;
+        }
+        sqlx::query(
+            "INSERT INTO contact_emails (id, contact_id, email, label, is_primary, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7) 
+             ON CONFLICT (id) DO UPDATE 
+             SET email = EXCLUDED.email, label = EXCLUDED.label, is_primary = EXCLUDED.is_primary, updated_at = EXCLUDED.updated_at 
+             WHERE contact_emails.contact_id = EXCLUDED.contact_id"
+        )
+        .bind(email.id)
+        .bind(contact_id)
+        .bind(&email.email)
+        .bind(email.label)
+        .bind(email.is_primary)
+        .bind(email.created_at)
+        .bind(email.updated_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+    if !changes.emails.is_empty() || !changes.deleted_emails.is_empty() {
+        sync_primary(conn, "contact_emails", "email", contact_id).await?;
+    }
+
+    delete_entries(conn, "contact_phones", contact_id, &changes.deleted_phones).await?;
+    for phone in &changes.phones {
+        if phone.is_primary {
+            demote_others(conn, "contact_phones", contact_id, &phone.id).await?;
+        }
+        sqlx::query(
+            "INSERT INTO contact_phones (id, contact_id, phone, phone_raw, label, is_primary, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) 
+             ON CONFLICT (id) DO UPDATE 
+             SET phone = EXCLUDED.phone, phone_raw = EXCLUDED.phone_raw, label = EXCLUDED.label, 
+                 is_primary = EXCLUDED.is_primary, updated_at = EXCLUDED.updated_at 
+             WHERE contact_phones.contact_id = EXCLUDED.contact_id"
+        )
+        .bind(phone.id)
+        .bind(contact_id)
+        .bind(&phone.phone)
+        .bind(&phone.phone_raw)
+        .bind(phone.label)
+        .bind(phone.is_primary)
+        .bind(phone.created_at)
+        .bind(phone.updated_at)
+        .execute(&mut *conn)
+        .await?;
+    }
+    if !changes.phones.is_empty() || !changes.deleted_phones.is_empty() {
+        sync_primary(conn, "contact_phones", "phone, phone_raw", contact_id).await?;
+    }
+    Ok(())
+}
+
+// Adds the contact, as it now is, to its history under its current version.
+// Runs in the transaction of the write being recorded, so history and
+// contact cannot disagree.
+async fn record_version(
+    conn: &mut PgConnection,
+    tenant: &Tenant,
+    contact_id: &Uuid,
+    action: ContactChange,
+) -> Result<Contact, DomainError> {
+    let contact = sqlx::query_as::<_, Contact>("SELECT * FROM contacts WHERE id = $1")
+        .bind(contact_id)
+        .fetch_one(&mut *conn)
+        .await?;
+    let addresses = sqlx::query_as::<_, Address>(
+        "SELECT * FROM addresses WHERE contact_id = $1 ORDER BY is_primary DESC, created_at, id"
+    )
+    .bind(contact_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let emails = sqlx::query_as::<_, ContactEmail>(
+        "SELECT * FROM contact_emails WHERE contact_id = $1 ORDER BY is_primary DESC, created_at, id"
+    )
+    .bind(contact_id)
+    .fetch_all(&mut *conn)
+    .await?;
+    let phones = sqlx::query_as::<_, ContactPhone>(
+        "SELECT * FROM contact_phones WHERE contact_id = $1 ORDER BY is_primary DESC, created_at, id"
+    )
+    .bind(contact_id)
+    .fetch_all(&mut *conn)
+    .await?;
+
+    sqlx::query(
+        "INSERT INTO contact_versions (id, contact_id, version, action, actor_id, snapshot, created_at) 
+         VALUES ($1, $2, $3, $4, $5, $6, $7)"
+    )
+    .bind(Uuid::new_v4())
+    .bind(contact.id)
+    .bind(contact.version)
+    .bind(action)
+    .bind(tenant.user_id)
+    .bind(Json(ContactSnapshot::new(&contact, &addresses, &emails, &phones)))
+    .bind(Utc::now())
+    .execute(&mut *conn)
+    .await?;
+    Ok(contact)
+}
+
+#[derive(sqlx::FromRow)]
+struct ContactSearchRow {
+    #[sqlx(flatten)]
+    contact: Contact,
+    rank: f32,
+    snippet: String,
 }
 
 #[async_trait]
 impl ContactRepository for PostgresContactRepository {
-    async fn create_contact(&self, contact: &Contact) -> Result<Contact, String> {
-        let result = sqlx::query_as::<_, Contact>(
-            "INSERT INTO contacts (id, user_id, first_name, last_name, email, phone, created_at, updated_at) 
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) 
-             RETURNING *"
+    async fn create_contact(
+        &self,
+        tenant: &Tenant,
+        contact: &Contact,
+        changes: &ContactChanges,
+    ) -> Result<Contact, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query(
+            "INSERT INTO contacts (id, user_id, organization_id, first_name, last_name, custom_fields, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)"
         )
         .bind(contact.id)
         .bind(contact.user_id)
+        .bind(contact.organization_id)
         .bind(&contact.first_name)
         .bind(&contact.last_name)
-        .bind(&contact.email)
-        .bind(&contact.phone)
+        .bind(&contact.custom_fields)
         .bind(contact.created_at)
         .bind(contact.updated_at)
-        .fetch_one(&self.pool)
+        .execute(&mut *tx)
         .await;
-
-        match result {
-            Ok(c) => Ok(c),
-            Err(e) => Err(e.to_string()),
+        if let Err(e) = result {
+            return Err(e.into());
         }
+
+        save_changes(&mut tx, &contact.id, changes).await?;
+        // Without emails or phones the copies are still NULL from the insert.
+        let created = record_version(&mut tx, tenant, &contact.id, ContactChange::Created).await?;
+        tx.commit().await?;
+        Ok(created)
     }
 
-    async fn update_contact(&self, contact: &Contact) -> Result<Contact, String> {
-        let result = sqlx::query_as::<_, Contact>(
+    async fn update_contact(
+        &self,
+        tenant: &Tenant,
+        contact: &Contact,
+        changes: &ContactChanges,
+        action: ContactChange,
+    ) -> Result<Contact, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query(
             "UPDATE contacts 
-             SET first_name = $1, last_name = $2, email = $3, phone = $4, updated_at = $5 
-             WHERE id = $6 
-             RETURNING *"
+             SET first_name = $1, last_name = $2, custom_fields = $3, updated_at = $4, version = version + 1 
+             WHERE id = $5 AND version = $6 AND user_id = $7 AND organization_id IS NOT DISTINCT FROM $8"
         )
         .bind(&contact.first_name)
         .bind(&contact.last_name)
-        .bind(&contact.email)
-        .bind(&contact.phone)
+        .bind(&contact.custom_fields)
         .bind(contact.updated_at)
         .bind(contact.id)
-        .fetch_one(&self.pool)
+        .bind(contact.version)
+        .bind(contact.user_id)
+        .bind(contact.organization_id)
+        .execute(&mut *tx)
         .await;
 
+        // The caller has just read the row, so a miss means a concurrent write
+        // (or a forged owner). The row stays locked until commit, so `changes`
+        // apply to the version that was checked.
         match result {
-            Ok(c) => Ok(c),
-            Err(e) => Err(e.to_string()),
+            Ok(done) if done.rows_affected() == 0 => return Err(modified_concurrently()),
+            Ok(_) => {}
+            Err(e) => return Err(e.into()),
         }
+
+        save_changes(&mut tx, &contact.id, changes).await?;
+        let updated = record_version(&mut tx, tenant, &contact.id, action).await?;
+        tx.commit().await?;
+        Ok(updated)
     }
 
-    async fn delete_contact(&self, id: &Uuid) -> Result<(), String> {
+    async fn merge_contacts(
+        &self,
+        tenant: &Tenant,
+        target: &Contact,
+        sources: &[Contact],
+    ) -> Result<Contact, DomainError> {
+        let source_ids: Vec<Uuid> = sources.iter().map(|c| c.id).collect();
+        let source_versions: Vec<i32> = sources.iter().map(|c| c.version).collect();
+
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query_as::<_, Contact>(
+            "UPDATE contacts 
+             SET first_name = $1, last_name = $2, custom_fields = $3, updated_at = $4, version = version + 1 
+             WHERE id = $5 AND version = $6 AND user_id = $7 AND organization_id IS NOT DISTINCT FROM $8 
+             RETURNING *"
+        )
+        .bind(&target.first_name)
+        .bind(&target.last_name)
+        .bind(&target.custom_fields)
+        .bind(target.updated_at)
+        .bind(target.id)
+        .bind(target.version)
+        .bind(target.user_id)
+        .bind(target.organization_id)
+        .fetch_one(&mut *tx)
+        .await;
+        match result {
+            Ok(_) => {}
+            Err(sqlx::Error::RowNotFound) => return Err(modified_concurrently()),
+            Err(e) => return Err(e.into()),
+        }
+
+        sqlx::query("UPDATE addresses SET contact_id = $1, is_primary = FALSE WHERE contact_id = ANY($2)")
+            .bind(target.id)
+            .bind(&source_ids)
+            .execute(&mut *tx)
+            .await?;
+        promote_oldest(&mut tx, "addresses", &target.id).await?;
+        sqlx::query(
+            "INSERT INTO contact_tags (contact_id, tag_id) 
+             SELECT $1, tag_id FROM contact_tags WHERE contact_id = ANY($2) 
+             ON CONFLICT DO NOTHING"
+        )
+        .bind(target.id)
+        .bind(&source_ids)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "INSERT INTO contact_group_members (group_id, contact_id) 
+             SELECT group_id, $1 FROM contact_group_members WHERE contact_id = ANY($2) 
+             ON CONFLICT DO NOTHING"
+        )
+        .bind(target.id)
+        .bind(&source_ids)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE contact_emails e SET contact_id = $1, is_primary = FALSE 
+             WHERE e.contact_id = ANY($2) AND NOT EXISTS 
+                 (SELECT 1 FROM contact_emails t WHERE t.contact_id = $1 AND lower(t.email) = lower(e.email))"
+        )
+        .bind(target.id)
+        .bind(&source_ids)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            "UPDATE contact_phones p SET contact_id = $1, is_primary = FALSE 
+             WHERE p.contact_id = ANY($2) AND NOT EXISTS 
+                 (SELECT 1 FROM contact_phones t WHERE t.contact_id = $1 AND t.phone = p.phone)"
+        )
+        .bind(target.id)
+        .bind(&source_ids)
+        .execute(&mut *tx)
+        .await?;
+        sync_primary(&mut tx, "contact_emails", "email", &target.id).await?;
+        sync_primary(&mut tx, "contact_phones", "phone, phone_raw", &target.id).await?;
+
+        // Trashed rather than deleted, so their history stays (a delete would
+        // cascade to it) and a wrong merge can be looked into.
+        let trashed = sqlx::query(
+            "UPDATE contacts SET deleted_at = $1, version = version + 1 
+             WHERE (id, version) IN (SELECT * FROM UNNEST($2::uuid[], $3::int[])) AND deleted_at IS NULL"
+        )
+        .bind(target.updated_at)
+        .bind(&source_ids)
+        .bind(&source_versions)
+        .execute(&mut *tx)
+        .await?;
+        // Dropping `tx` without commit rolls everything back.
+        if trashed.rows_affected() != sources.len() as u64 {
+            return Err(modified_concurrently());
+        }
+        for source_id in &source_ids {
+            record_version(&mut tx, tenant, source_id, ContactChange::MergedInto).await?;
+        }
+
+        let merged = record_version(&mut tx, tenant, &target.id, ContactChange::Merged).await?;
+        tx.commit().await?;
+        Ok(merged)
+    }
+
+    async fn delete_contact(&self, tenant: &Tenant, id: &Uuid) -> Result<(), DomainError> {
+        let mut tx = self.begin(tenant).await?;
         let result = sqlx::query("DELETE FROM contacts WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .execute(&mut *tx)
             .await;
 
         match result {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e.to_string()),
+            Ok(_) => Ok(tx.commit().await?),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn trash_contact(
+        &self,
+        tenant: &Tenant,
+        id: &Uuid,
+        version: i32,
+        deleted_at: DateTime<Utc>,
+    ) -> Result<(), DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query(
+            "UPDATE contacts SET deleted_at = $1, version = version + 1 WHERE id = $2 AND version = $3"
+        )
+        .bind(deleted_at)
+        .bind(id)
+        .bind(version)
+        .execute(&mut *tx)
+        .await;
+
+        match result {
+            Ok(done) if done.rows_affected() == 0 => return Err(modified_concurrently()),
+            Ok(_) => {}
+            Err(e) => return Err(e.into()),
         }
+        record_version(&mut tx, tenant, id, ContactChange::Deleted).await?;
+        Ok(tx.commit().await?)
     }
 
-    async fn find_contact_by_id(&self, id: &Uuid) -> Result<Option<Contact>, String> {
-        let result = sqlx::query_as::<_, Contact>("SELECT * FROM contacts WHERE id = $1")
+    async fn restore_contact(&self, tenant: &Tenant, id: &Uuid) -> Result<Contact, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query("UPDATE contacts SET deleted_at = NULL, version = version + 1 WHERE id = $1")
             .bind(id)
-            .fetch_optional(&self.pool)
+            .execute(&mut *tx)
             .await;
 
+        match result {
+            Ok(done) if done.rows_affected() == 0 => return Err(DomainError::NotFound("Record not found".to_string())),
+            Ok(_) => {}
+            Err(e) => return Err(e.into()),
+        }
+        let restored = record_version(&mut tx, tenant, id, ContactChange::Restored).await?;
+        tx.commit().await?;
+        Ok(restored)
+    }
+
+    async fn find_contact_by_id(&self, tenant: &Tenant, id: &Uuid) -> Result<Option<Contact>, DomainError> {
+        let mut select = QueryBuilder::new("SELECT * FROM contacts WHERE id = ");
+        select.push_bind(*id).push(" AND ((");
+        push_tenant(&mut select, tenant);
+        select
+            .push(") OR EXISTS (SELECT 1 FROM contact_shares s WHERE s.grantee_id = ")
+            .push_bind(tenant.user_id)
+            .push(" AND (s.contact_id = contacts.id OR s.group_id IN")
+            .push(" (SELECT gm.group_id FROM contact_group_members gm WHERE gm.contact_id = contacts.id))))");
+        let mut tx = self.begin(tenant).await?;
+        let result = select.build_query_as::<Contact>().fetch_optional(&mut *tx).await;
+        tx.commit().await?;
+
         match result {
             Ok(c) => Ok(c),
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn find_contacts_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Contact>, String> {
-        let result = sqlx::query_as::<_, Contact>("SELECT * FROM contacts WHERE user_id = $1")
-            .bind(user_id)
-            .fetch_all(&self.pool)
-            .await;
+    async fn find_contacts(&self, tenant: &Tenant) -> Result<Vec<Contact>, DomainError> {
+        let mut select = QueryBuilder::new("SELECT * FROM contacts WHERE ");
+        push_tenant(&mut select, tenant);
+        select.push(" AND deleted_at IS NULL ORDER BY created_at, id");
+        let mut tx = self.begin(tenant).await?;
+        let result = select.build_query_as::<Contact>().fetch_all(&mut *tx).await;
+        tx.commit().await?;
 
         match result {
             Ok(contacts) => Ok(contacts),
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn create_address(&self, address: &Address) -> Result<Address, String> {
-        let result = sqlx::query_as::<_, Address>(
-            "INSERT INTO addresses (id, contact_id, street, city, province, country, postal_code, created_at, updated_at) 
-             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) 
-             RETURNING *"
-        )
-        .bind(address.id)
-        .bind(address.contact_id)
-        .bind(&address.street)
-        .bind(&address.city)
-        .bind(&address.province)
-        .bind(&address.country)
-        .bind(&address.postal_code)
-        .bind(address.created_at)
-        .bind(address.updated_at)
-        .fetch_one(&self.pool)
-        .await;
+    async fn find_trashed_contacts(&self, tenant: &Tenant) -> Result<Vec<Contact>, DomainError> {
+        let mut select = QueryBuilder::new("SELECT * FROM contacts WHERE ");
+        push_tenant(&mut select, tenant);
+        select.push(" AND deleted_at IS NOT NULL ORDER BY deleted_at DESC, id");
+        let mut tx = self.begin(tenant).await?;
+        let result = select.build_query_as::<Contact>().fetch_all(&mut *tx).await;
+        tx.commit().await?;
+
+        match result {
+            Ok(contacts) => Ok(contacts),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn purge_trashed_contacts(&self, deleted_before: DateTime<Utc>) -> Result<u64, DomainError> {
+        let result = sqlx::query("DELETE FROM contacts WHERE deleted_at < $1")
+            .bind(deleted_before)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(done) => Ok(done.rows_affected()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn search_contacts(&self, tenant: &Tenant, query: &ContactQuery) -> Result<ContactPage, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM contacts");
+        push_contact_filters(&mut count, tenant, query);
+        let total: i64 = match count.build_query_scalar().fetch_one(&mut *tx).await {
+            Ok(total) => total,
+            Err(e) => return Err(e.into()),
+        };
+
+        let sort = sort_expression(query.sort);
+        let (direction, comparison) = match query.direction {
+            SortDirection::Asc => ("ASC", ">"),
+            SortDirection::Desc => ("DESC", "<"),
+        };
+
+        let mut select = QueryBuilder::new("SELECT * FROM contacts");
+        push_contact_filters(&mut select, tenant, query);
+
+        if let Some(cursor) = &query.cursor {
+            select.push(format!(" AND ({}, id) {} (", sort, comparison));
+            match query.sort {
+                ContactSortField::CreatedAt | ContactSortField::UpdatedAt => {
+                    select.push_bind(cursor.value.clone()).push("::timestamptz");
+                }
+                _ => {
+                    select.push_bind(cursor.value.clone());
+                }
+            }
+            select.push(", ").push_bind(cursor.id).push(")");
+        }
+
+        // One extra row tells us whether another page exists.
+        select
+            .push(format!(" ORDER BY {} {}, id {}", sort, direction, direction))
+            .push(" LIMIT ")
+            .push_bind(query.limit + 1);
+        if query.cursor.is_none() {
+            select.push(" OFFSET ").push_bind(query.offset);
+        }
+
+        let mut contacts = match select.build_query_as::<Contact>().fetch_all(&mut *tx).await {
+            Ok(contacts) => contacts,
+            Err(e) => return Err(e.into()),
+        };
+        tx.commit().await?;
+
+        let next_cursor = if contacts.len() as i64 > query.limit {
+            contacts.truncate(query.limit as usize);
+            contacts.last().map(|last| ContactCursor {
+                sort: query.sort,
+                value: sort_value(last, query.sort),
+                id: last.id,
+            })
+        } else {
+            None
+        };
+
+        Ok(ContactPage {
+            contacts,
+            total,
+            next_cursor,
+        })
+    }
+
+    async fn search_contacts_by_text(
+        &self,
+        tenant: &Tenant,
+        text: &str,
+        query: &ContactQuery,
+    ) -> Result<ContactSearchPage, DomainError> {
+        // The threshold is set per transaction so pooled connections keep the default.
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query("SELECT set_config('pg_trgm.word_similarity_threshold', $1, true)")
+            .bind(WORD_SIMILARITY_THRESHOLD)
+            .execute(&mut *tx)
+            .await;
+        if let Err(e) = result {
+            return Err(e.into());
+        }
+
+        let mut count = QueryBuilder::new("SELECT COUNT(*) FROM contacts");
+        push_contact_filters(&mut count, tenant, query);
+        push_text_match(&mut count, text);
+        let total: i64 = match count.build_query_scalar().fetch_one(&mut *tx).await {
+            Ok(total) => total,
+            Err(e) => return Err(e.into()),
+        };
+
+        // Names weigh more than email/phone, which weigh more than addresses
+        // (see the migration); trigram similarity lifts typo matches.
+        let mut select = QueryBuilder::new("SELECT contacts.*, (ts_rank(contacts.search_vector, ts.query) + COALESCE(a.rank, 0)");
+        select
+            .push(" + GREATEST(word_similarity(ts.term, contacts.search_text), COALESCE(a.similarity, 0)))::real AS rank,")
+            .push(" ts_headline('simple', concat_ws(' ', first_name, last_name, email, phone, a.text), ts.query,")
+            .push(" 'StartSel=<mark>, StopSel=</mark>, HighlightAll=true') AS snippet")
+            .push(" FROM contacts CROSS JOIN (SELECT websearch_to_tsquery('simple', ")
+            .push_bind(text.to_string())
+            .push(") AS query, ")
+            .push_bind(text.to_lowercase())
+            .push("::text AS term) ts")
+            .push(" LEFT JOIN LATERAL (SELECT MAX(ts_rank(search_vector, ts.query)) AS rank,")
+            .push(" MAX(word_similarity(ts.term, search_text)) AS similarity,")
+            .push(" string_agg(concat_ws(' ', street, city, province, country, postal_code), ' ') AS text")
+            .push(" FROM addresses WHERE addresses.contact_id = contacts.id) a ON true");
+        push_contact_filters(&mut select, tenant, query);
+        push_text_match(&mut select, text);
+        select
+            .push(" ORDER BY rank DESC, contacts.id LIMIT ")
+            .push_bind(query.limit)
+            .push(" OFFSET ")
+            .push_bind(query.offset);
+
+        let rows = match select.build_query_as::<ContactSearchRow>().fetch_all(&mut *tx).await {
+            Ok(rows) => rows,
+            Err(e) => return Err(e.into()),
+        };
+        tx.commit().await?;
+
+        Ok(ContactSearchPage {
+            hits: rows
+                .into_iter()
+                .map(|row| ContactSearchHit {
+                    contact: row.contact,
+                    rank: row.rank,
+                    snippet: row.snippet,
+                })
+                .collect(),
+            total,
+        })
+    }
+
+    async fn find_address_by_id(&self, tenant: &Tenant, id: &Uuid) -> Result<Option<Address>, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query_as::<_, Address>("SELECT * FROM addresses WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&mut *tx)
+            .await;
+        tx.commit().await?;
 
         match result {
             Ok(a) => Ok(a),
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn update_address(&self, address: &Address) -> Result<Address, String> {
+    async fn find_addresses_by_contact_id(&self, tenant: &Tenant, contact_id: &Uuid) -> Result<Vec<Address>, DomainError> {
+        self.find_addresses_by_contact_ids(tenant, &[*contact_id]).await
+    }
+
+    async fn find_addresses_by_contact_ids(
+        &self,
+        tenant: &Tenant,
+        contact_ids: &[Uuid],
+    ) -> Result<Vec<Address>, DomainError> {
+        let mut tx = self.begin(tenant).await?;
         let result = sqlx::query_as::<_, Address>(
-            "UPDATE addresses 
-             SET street = $1, city = $2, province = $3, country = $4, postal_code = $5, updated_at = $6
-             WHERE id = $7 
-             RETURNING *"
+            "SELECT * FROM addresses WHERE contact_id = ANY($1) 
+             ORDER BY contact_id, is_primary DESC, created_at, id"
         )
-        .bind(&address.street)
-        .bind(&address.city)
-        .bind(&address.province)
-        .bind(&address.country)
-        .bind(&address.postal_code)
-        .bind(address.updated_at)
-        .bind(address.id)
-        .fetch_one(&self.pool)
+        .bind(contact_ids)
+        .fetch_all(&mut *tx)
         .await;
+        tx.commit().await?;
 
         match result {
-            Ok(a) => Ok(a),
-            Err(e) => Err(e.to_string()),
+            Ok(addresses) => Ok(addresses),
+            Err(e) => Err(e.into()),
         }
     }
-    
-    async fn delete_address(&self, id: &Uuid) -> Result<(), String> {
-         let result = sqlx::query("DELETE FROM addresses WHERE id = $1")
+
+    async fn find_email_by_id(&self, tenant: &Tenant, id: &Uuid) -> Result<Option<ContactEmail>, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query_as::<_, ContactEmail>("SELECT * FROM contact_emails WHERE id = $1")
             .bind(id)
-            .execute(&self.pool)
+            .fetch_optional(&mut *tx)
             .await;
+        tx.commit().await?;
 
         match result {
-            Ok(_) => Ok(()),
-            Err(e) => Err(e.to_string()),
+            Ok(e) => Ok(e),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn find_address_by_id(&self, id: &Uuid) -> Result<Option<Address>, String> {
-        let result = sqlx::query_as::<_, Address>("SELECT * FROM addresses WHERE id = $1")
+    async fn find_emails_by_contact_id(&self, tenant: &Tenant, contact_id: &Uuid) -> Result<Vec<ContactEmail>, DomainError> {
+        self.find_emails_by_contact_ids(tenant, &[*contact_id]).await
+    }
+
+    async fn find_emails_by_contact_ids(
+        &self,
+        tenant: &Tenant,
+        contact_ids: &[Uuid],
+    ) -> Result<Vec<ContactEmail>, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query_as::<_, ContactEmail>(
+            "SELECT * FROM contact_emails WHERE contact_id = ANY($1) 
+             ORDER BY contact_id, is_primary DESC, created_at, id"
+        )
+        .bind(contact_ids)
+        .fetch_all(&mut *tx)
+        .await;
+        tx.commit().await?;
+
+        match result {
+            Ok(emails) => Ok(emails),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_phone_by_id(&self, tenant: &Tenant, id: &Uuid) -> Result<Option<ContactPhone>, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query_as::<_, ContactPhone>("SELECT * FROM contact_phones WHERE id = $1")
             .bind(id)
-            .fetch_optional(&self.pool)
+            .fetch_optional(&mut *tx)
             .await;
+        tx.commit().await?;
 
         match result {
-            Ok(a) => Ok(a),
-            Err(e) => Err(e.to_string()),
+            Ok(p) => Ok(p),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn find_addresses_by_contact_id(&self, contact_id: &Uuid) -> Result<Vec<Address>, String> {
-        let result = sqlx::query_as::<_, Address>("SELECT * FROM addresses WHERE contact_id = $1")
-            .bind(contact_id)
-            .fetch_all(&self.pool)
-            .await;
+    async fn find_phones_by_contact_id(&self, tenant: &Tenant, contact_id: &Uuid) -> Result<Vec<ContactPhone>, DomainError> {
+        self.find_phones_by_contact_ids(tenant, &[*contact_id]).await
+    }
+
+    async fn find_phones_by_contact_ids(
+        &self,
+        tenant: &Tenant,
+        contact_ids: &[Uuid],
+    ) -> Result<Vec<ContactPhone>, DomainError> {
+        let mut tx = self.begin(tenant).await?;
+        let result = sqlx::query_as::<_, ContactPhone>(
+            "SELECT * FROM contact_phones WHERE contact_id = ANY($1) 
+             ORDER BY contact_id, is_primary DESC, created_at, id"
+        )
+        .bind(contact_ids)
+        .fetch_all(&mut *tx)
+        .await;
+        tx.commit().await?;
 
         match result {
-            Ok(addresses) => Ok(addresses),
-            Err(e) => Err(e.to_string()),
+            Ok(phones) => Ok(phones),
+            Err(e) => Err(e.into()),
         }
     }
 }
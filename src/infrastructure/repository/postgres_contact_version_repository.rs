@@ -0,0 +1,50 @@
+use crate::domain::{
+    entity::contact_version_entity::ContactVersion,
+    error::DomainError,
+    repository::contact_version_repository::ContactVersionRepository,
+};
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct PostgresContactVersionRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresContactVersionRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ContactVersionRepository for PostgresContactVersionRepository {
+    async fn find_versions_by_contact_id(&self, contact_id: &Uuid) -> Result<Vec<ContactVersion>, DomainError> {
+        let result = sqlx::query_as::<_, ContactVersion>(
+            "SELECT * FROM contact_versions WHERE contact_id = $1 ORDER BY version"
+        )
+        .bind(contact_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(versions) => Ok(versions),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_version(&self, contact_id: &Uuid, version: i32) -> Result<Option<ContactVersion>, DomainError> {
+        let result = sqlx::query_as::<_, ContactVersion>(
+            "SELECT * FROM contact_versions WHERE contact_id = $1 AND version = $2"
+        )
+        .bind(contact_id)
+        .bind(version)
+        .fetch_optional(&self.pool)
+        .await;
+
+        match result {
+            Ok(v) => Ok(v),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::domain::{
+    entity::custom_field_entity::CustomFieldDefinition,
+    error::DomainError,
+    repository::custom_field_repository::CustomFieldRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct PostgresCustomFieldRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresCustomFieldRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl CustomFieldRepository for PostgresCustomFieldRepository {
+    async fn create_field(&self, field: &CustomFieldDefinition) -> Result<CustomFieldDefinition, DomainError> {
+        let result = sqlx::query_as::<_, CustomFieldDefinition>(
+            "INSERT INTO custom_field_definitions 
+                 (id, user_id, name, field_type, required, enum_values, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) 
+             RETURNING *"
+        )
+        .bind(field.id)
+        .bind(field.user_id)
+        .bind(&field.name)
+        .bind(field.field_type)
+        .bind(field.required)
+        .bind(&field.enum_values)
+        .bind(field.created_at)
+        .bind(field.updated_at)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(f) => Ok(f),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update_field(&self, field: &CustomFieldDefinition) -> Result<CustomFieldDefinition, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let previous: String = sqlx::query_scalar("SELECT name FROM custom_field_definitions WHERE id = $1 FOR UPDATE")
+            .bind(field.id)
+            .fetch_one(&mut *tx)
+            .await?;
+
+        let updated = sqlx::query_as::<_, CustomFieldDefinition>(
+            "UPDATE custom_field_definitions 
+             SET name = $1, required = $2, enum_values = $3, updated_at = $4 
+             WHERE id = $5 
+             RETURNING *"
+        )
+        .bind(&field.name)
+        .bind(field.required)
+        .bind(&field.enum_values)
+        .bind(field.updated_at)
+        .bind(field.id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if previous != field.name {
+            sqlx::query(
+                "UPDATE contacts 
+                 SET custom_fields = (custom_fields - $2) || jsonb_build_object($3::text, custom_fields -> $2), 
+                     version = version + 1, updated_at = $4 
+                 WHERE user_id = $1 AND custom_fields ? $2"
+            )
+            .bind(field.user_id)
+            .bind(&previous)
+            .bind(&field.name)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(updated)
+    }
+
+    async fn delete_field(&self, id: &Uuid) -> Result<(), DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let deleted: Option<(Uuid, String)> =
+            sqlx::query_as("DELETE FROM custom_field_definitions WHERE id = $1 RETURNING user_id, name")
+                .bind(id)
+                .fetch_optional(&mut *tx)
+                .await?;
+
+        if let Some((user_id, name)) = deleted {
+            sqlx::query(
+                "UPDATE contacts SET custom_fields = custom_fields - $2, version = version + 1, updated_at = $3 
+                 WHERE user_id = $1 AND custom_fields ? $2"
+            )
+            .bind(user_id)
+            .bind(&name)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    async fn find_field_by_id(&self, id: &Uuid) -> Result<Option<CustomFieldDefinition>, DomainError> {
+        let result = sqlx::query_as::<_, CustomFieldDefinition>("SELECT * FROM custom_field_definitions WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await;
+
+        match result {
+            Ok(f) => Ok(f),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_fields_by_user_id(&self, user_id: &Uuid) -> Result<Vec<CustomFieldDefinition>, DomainError> {
+        let result = sqlx::query_as::<_, CustomFieldDefinition>(
+            "SELECT * FROM custom_field_definitions WHERE user_id = $1 ORDER BY name"
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(fields) => Ok(fields),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
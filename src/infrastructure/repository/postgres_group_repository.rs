@@ -0,0 +1,128 @@
+use crate::domain::{
+    entity::group_entity::Group,
+    error::DomainError,
+    repository::group_repository::GroupRepository,
+};
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct PostgresGroupRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresGroupRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl GroupRepository for PostgresGroupRepository {
+    async fn create_group(&self, group: &Group) -> Result<Group, DomainError> {
+        let result = sqlx::query_as::<_, Group>(
+            "INSERT INTO contact_groups (id, user_id, name, description, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6) 
+             RETURNING *"
+        )
+        .bind(group.id)
+        .bind(group.user_id)
+        .bind(&group.name)
+        .bind(&group.description)
+        .bind(group.created_at)
+        .bind(group.updated_at)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(g) => Ok(g),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update_group(&self, group: &Group) -> Result<Group, DomainError> {
+        let result = sqlx::query_as::<_, Group>(
+            "UPDATE contact_groups 
+             SET name = $1, description = $2, updated_at = $3 
+             WHERE id = $4 
+             RETURNING *"
+        )
+        .bind(&group.name)
+        .bind(&group.description)
+        .bind(group.updated_at)
+        .bind(group.id)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(g) => Ok(g),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete_group(&self, id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM contact_groups WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_group_by_id(&self, id: &Uuid) -> Result<Option<Group>, DomainError> {
+        let result = sqlx::query_as::<_, Group>("SELECT * FROM contact_groups WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await;
+
+        match result {
+            Ok(g) => Ok(g),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_groups_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Group>, DomainError> {
+        let result = sqlx::query_as::<_, Group>(
+            "SELECT * FROM contact_groups WHERE user_id = $1 ORDER BY lower(name)"
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(groups) => Ok(groups),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn add_contact_to_group(&self, group_id: &Uuid, contact_id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "INSERT INTO contact_group_members (group_id, contact_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        )
+        .bind(group_id)
+        .bind(contact_id)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn remove_contact_from_group(&self, group_id: &Uuid, contact_id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM contact_group_members WHERE group_id = $1 AND contact_id = $2")
+            .bind(group_id)
+            .bind(contact_id)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,203 @@
+use crate::domain::{
+    entity::organization_entity::{Organization, OrganizationMember, OrganizationRole},
+    error::DomainError,
+    repository::organization_repository::{MemberOrganization, OrganizationRepository},
+};
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct PostgresOrganizationRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresOrganizationRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct MemberOrganizationRow {
+    #[sqlx(flatten)]
+    organization: Organization,
+    role: OrganizationRole,
+}
+
+#[async_trait]
+impl OrganizationRepository for PostgresOrganizationRepository {
+    async fn create_organization(
+        &self,
+        organization: &Organization,
+        owner: &OrganizationMember,
+    ) -> Result<Organization, DomainError> {
+        let mut tx = self.pool.begin().await?;
+        let created = sqlx::query_as::<_, Organization>(
+            "INSERT INTO organizations (id, name, created_at, updated_at)
+             VALUES ($1, $2, $3, $4)
+             RETURNING *"
+        )
+        .bind(organization.id)
+        .bind(&organization.name)
+        .bind(organization.created_at)
+        .bind(organization.updated_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        sqlx::query(
+            "INSERT INTO organization_members (organization_id, user_id, role, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5)"
+        )
+        .bind(created.id)
+        .bind(owner.user_id)
+        .bind(owner.role)
+        .bind(owner.created_at)
+        .bind(owner.updated_at)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(created)
+    }
+
+    async fn update_organization(&self, organization: &Organization) -> Result<Organization, DomainError> {
+        let result = sqlx::query_as::<_, Organization>(
+            "UPDATE organizations SET name = $1, updated_at = $2 WHERE id = $3 RETURNING *"
+        )
+        .bind(&organization.name)
+        .bind(organization.updated_at)
+        .bind(organization.id)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(o) => Ok(o),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete_organization(&self, id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM organizations WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_organization_by_id(&self, id: &Uuid) -> Result<Option<Organization>, DomainError> {
+        let result = sqlx::query_as::<_, Organization>("SELECT * FROM organizations WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await;
+
+        match result {
+            Ok(o) => Ok(o),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_organizations_by_user_id(&self, user_id: &Uuid) -> Result<Vec<MemberOrganization>, DomainError> {
+        let result = sqlx::query_as::<_, MemberOrganizationRow>(
+            "SELECT o.*, m.role
+             FROM organizations o JOIN organization_members m ON m.organization_id = o.id
+             WHERE m.user_id = $1
+             ORDER BY o.name, o.id"
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(rows) => Ok(rows
+                .into_iter()
+                .map(|row| MemberOrganization { organization: row.organization, role: row.role })
+                .collect()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn add_member(&self, member: &OrganizationMember) -> Result<OrganizationMember, DomainError> {
+        let result = sqlx::query_as::<_, OrganizationMember>(
+            "INSERT INTO organization_members (organization_id, user_id, role, created_at, updated_at)
+             VALUES ($1, $2, $3, $4, $5)
+             RETURNING *"
+        )
+        .bind(member.organization_id)
+        .bind(member.user_id)
+        .bind(member.role)
+        .bind(member.created_at)
+        .bind(member.updated_at)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(m) => Ok(m),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update_member(&self, member: &OrganizationMember) -> Result<OrganizationMember, DomainError> {
+        let result = sqlx::query_as::<_, OrganizationMember>(
+            "UPDATE organization_members SET role = $1, updated_at = $2
+             WHERE organization_id = $3 AND user_id = $4
+             RETURNING *"
+        )
+        .bind(member.role)
+        .bind(member.updated_at)
+        .bind(member.organization_id)
+        .bind(member.user_id)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(m) => Ok(m),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn remove_member(&self, organization_id: &Uuid, user_id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM organization_members WHERE organization_id = $1 AND user_id = $2")
+            .bind(organization_id)
+            .bind(user_id)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_member(&self, organization_id: &Uuid, user_id: &Uuid) -> Result<Option<OrganizationMember>, DomainError> {
+        let result = sqlx::query_as::<_, OrganizationMember>(
+            "SELECT * FROM organization_members WHERE organization_id = $1 AND user_id = $2"
+        )
+        .bind(organization_id)
+        .bind(user_id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        match result {
+            Ok(m) => Ok(m),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_members(&self, organization_id: &Uuid) -> Result<Vec<OrganizationMember>, DomainError> {
+        let result = sqlx::query_as::<_, OrganizationMember>(
+            "SELECT * FROM organization_members WHERE organization_id = $1 ORDER BY created_at, user_id"
+        )
+        .bind(organization_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(members) => Ok(members),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
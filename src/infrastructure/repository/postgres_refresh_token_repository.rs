@@ -0,0 +1,105 @@
+use crate::domain::{
+    entity::refresh_token_entity::RefreshToken,
+    error::DomainError,
+    repository::refresh_token_repository::RefreshTokenRepository,
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct PostgresRefreshTokenRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresRefreshTokenRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl RefreshTokenRepository for PostgresRefreshTokenRepository {
+    async fn create_refresh_token(&self, token: &RefreshToken) -> Result<RefreshToken, DomainError> {
+        let result = sqlx::query_as::<_, RefreshToken>(
+            "INSERT INTO refresh_tokens (id, user_id, family_id, token_hash, expires_at, revoked_at, replaced_by, created_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) 
+             RETURNING *"
+        )
+        .bind(token.id)
+        .bind(token.user_id)
+        .bind(token.family_id)
+        .bind(&token.token_hash)
+        .bind(token.expires_at)
+        .bind(token.revoked_at)
+        .bind(token.replaced_by)
+        .bind(token.created_at)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(t) => Ok(t),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_refresh_token_by_hash(&self, token_hash: &str) -> Result<Option<RefreshToken>, DomainError> {
+        let result = sqlx::query_as::<_, RefreshToken>("SELECT * FROM refresh_tokens WHERE token_hash = $1")
+            .bind(token_hash)
+            .fetch_optional(&self.pool)
+            .await;
+
+        match result {
+            Ok(t) => Ok(t),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn revoke_refresh_token(&self, id: &Uuid, replaced_by: Option<Uuid>) -> Result<bool, DomainError> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens 
+             SET revoked_at = $1, replaced_by = $2 
+             WHERE id = $3 AND revoked_at IS NULL"
+        )
+        .bind(Utc::now())
+        .bind(replaced_by)
+        .bind(id)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(r) => Ok(r.rows_affected() == 1),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn revoke_token_family(&self, family_id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = $1 WHERE family_id = $2 AND revoked_at IS NULL"
+        )
+        .bind(Utc::now())
+        .bind(family_id)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn revoke_refresh_tokens_by_user_id(&self, user_id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = $1 WHERE user_id = $2 AND revoked_at IS NULL"
+        )
+        .bind(Utc::now())
+        .bind(user_id)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
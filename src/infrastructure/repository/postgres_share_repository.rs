@@ -0,0 +1,168 @@
+use crate::domain::{
+    entity::{
+        contact_entity::Contact,
+        share_entity::{ContactShare, SharePermission},
+    },
+    error::DomainError,
+    repository::share_repository::{ShareRepository, SharedContact},
+};
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct PostgresShareRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresShareRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct SharedContactRow {
+    #[sqlx(flatten)]
+    contact: Contact,
+    permission: SharePermission,
+}
+
+// Shares that reach contact `c`: its own and those of its groups.
+const SHARES_OF_CONTACT: &str = "(s.contact_id = c.id 
+     OR s.group_id IN (SELECT gm.group_id FROM contact_group_members gm WHERE gm.contact_id = c.id))";
+
+#[async_trait]
+impl ShareRepository for PostgresShareRepository {
+    async fn create_share(&self, share: &ContactShare) -> Result<ContactShare, DomainError> {
+        let result = sqlx::query_as::<_, ContactShare>(
+            "INSERT INTO contact_shares 
+                 (id, owner_id, grantee_id, contact_id, group_id, permission, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8) 
+             RETURNING *"
+        )
+        .bind(share.id)
+        .bind(share.owner_id)
+        .bind(share.grantee_id)
+        .bind(share.contact_id)
+        .bind(share.group_id)
+        .bind(share.permission)
+        .bind(share.created_at)
+        .bind(share.updated_at)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(s) => Ok(s),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update_share(&self, share: &ContactShare) -> Result<ContactShare, DomainError> {
+        let result = sqlx::query_as::<_, ContactShare>(
+            "UPDATE contact_shares SET permission = $1, updated_at = $2 WHERE id = $3 RETURNING *"
+        )
+        .bind(share.permission)
+        .bind(share.updated_at)
+        .bind(share.id)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(s) => Ok(s),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete_share(&self, id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM contact_shares WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_share_by_id(&self, id: &Uuid) -> Result<Option<ContactShare>, DomainError> {
+        let result = sqlx::query_as::<_, ContactShare>("SELECT * FROM contact_shares WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await;
+
+        match result {
+            Ok(s) => Ok(s),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_shares_by_contact_id(&self, contact_id: &Uuid) -> Result<Vec<ContactShare>, DomainError> {
+        let result = sqlx::query_as::<_, ContactShare>(
+            "SELECT * FROM contact_shares WHERE contact_id = $1 ORDER BY created_at, id"
+        )
+        .bind(contact_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(shares) => Ok(shares),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_shares_by_group_id(&self, group_id: &Uuid) -> Result<Vec<ContactShare>, DomainError> {
+        let result = sqlx::query_as::<_, ContactShare>(
+            "SELECT * FROM contact_shares WHERE group_id = $1 ORDER BY created_at, id"
+        )
+        .bind(group_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(shares) => Ok(shares),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_permission(&self, contact_id: &Uuid, grantee_id: &Uuid) -> Result<Option<SharePermission>, DomainError> {
+        // NULL without any share, otherwise whether one of them grants edit.
+        let result: Result<Option<bool>, sqlx::Error> = sqlx::query_scalar(&format!(
+            "SELECT bool_or(s.permission = 'edit') 
+             FROM contacts c JOIN contact_shares s ON {SHARES_OF_CONTACT} 
+             WHERE c.id = $1 AND s.grantee_id = $2"
+        ))
+        .bind(contact_id)
+        .bind(grantee_id)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(Some(true)) => Ok(Some(SharePermission::Edit)),
+            Ok(Some(false)) => Ok(Some(SharePermission::Read)),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_shared_contacts(&self, grantee_id: &Uuid) -> Result<Vec<SharedContact>, DomainError> {
+        let result = sqlx::query_as::<_, SharedContactRow>(&format!(
+            "SELECT c.*, CASE WHEN bool_or(s.permission = 'edit') THEN 'edit' ELSE 'read' END::varchar AS permission 
+             FROM contacts c JOIN contact_shares s ON {SHARES_OF_CONTACT} 
+             WHERE s.grantee_id = $1 AND c.deleted_at IS NULL 
+             GROUP BY c.id 
+             ORDER BY c.first_name, c.id"
+        ))
+        .bind(grantee_id)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(rows) => Ok(rows
+                .into_iter()
+                .map(|row| SharedContact { contact: row.contact, permission: row.permission })
+                .collect()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
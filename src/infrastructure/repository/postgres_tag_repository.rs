@@ -0,0 +1,138 @@
+use crate::domain::{
+    entity::tag_entity::{ContactTag, Tag},
+    error::DomainError,
+    repository::tag_repository::TagRepository,
+};
+use async_trait::async_trait;
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct PostgresTagRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresTagRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TagRepository for PostgresTagRepository {
+    async fn create_tag(&self, tag: &Tag) -> Result<Tag, DomainError> {
+        let result = sqlx::query_as::<_, Tag>(
+            "INSERT INTO tags (id, user_id, name, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5) 
+             RETURNING *"
+        )
+        .bind(tag.id)
+        .bind(tag.user_id)
+        .bind(&tag.name)
+        .bind(tag.created_at)
+        .bind(tag.updated_at)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(t) => Ok(t),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update_tag(&self, tag: &Tag) -> Result<Tag, DomainError> {
+        let result = sqlx::query_as::<_, Tag>(
+            "UPDATE tags SET name = $1, updated_at = $2 WHERE id = $3 RETURNING *"
+        )
+        .bind(&tag.name)
+        .bind(tag.updated_at)
+        .bind(tag.id)
+        .fetch_one(&self.pool)
+        .await;
+
+        match result {
+            Ok(t) => Ok(t),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete_tag(&self, id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM tags WHERE id = $1")
+            .bind(id)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_tag_by_id(&self, id: &Uuid) -> Result<Option<Tag>, DomainError> {
+        let result = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE id = $1")
+            .bind(id)
+            .fetch_optional(&self.pool)
+            .await;
+
+        match result {
+            Ok(t) => Ok(t),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_tags_by_user_id(&self, user_id: &Uuid) -> Result<Vec<Tag>, DomainError> {
+        let result = sqlx::query_as::<_, Tag>("SELECT * FROM tags WHERE user_id = $1 ORDER BY lower(name)")
+            .bind(user_id)
+            .fetch_all(&self.pool)
+            .await;
+
+        match result {
+            Ok(tags) => Ok(tags),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn add_tag_to_contact(&self, contact_id: &Uuid, tag_id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "INSERT INTO contact_tags (contact_id, tag_id) VALUES ($1, $2) ON CONFLICT DO NOTHING"
+        )
+        .bind(contact_id)
+        .bind(tag_id)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn remove_tag_from_contact(&self, contact_id: &Uuid, tag_id: &Uuid) -> Result<(), DomainError> {
+        let result = sqlx::query("DELETE FROM contact_tags WHERE contact_id = $1 AND tag_id = $2")
+            .bind(contact_id)
+            .bind(tag_id)
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_tags_by_contact_ids(&self, contact_ids: &[Uuid]) -> Result<Vec<ContactTag>, DomainError> {
+        let result = sqlx::query_as::<_, ContactTag>(
+            "SELECT ct.contact_id, t.* FROM contact_tags ct
+             JOIN tags t ON t.id = ct.tag_id
+             WHERE ct.contact_id = ANY($1)
+             ORDER BY ct.contact_id, lower(t.name)"
+        )
+        .bind(contact_ids)
+        .fetch_all(&self.pool)
+        .await;
+
+        match result {
+            Ok(tags) => Ok(tags),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
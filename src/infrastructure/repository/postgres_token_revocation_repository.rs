@@ -0,0 +1,93 @@
+use crate::domain::{
+    entity::token_revocation_entity::{RevokedToken, UserTokenRevocation},
+    error::DomainError,
+    repository::token_revocation_repository::TokenRevocationRepository,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use sqlx::{Pool, Postgres};
+use uuid::Uuid;
+
+pub struct PostgresTokenRevocationRepository {
+    pool: Pool<Postgres>,
+}
+
+impl PostgresTokenRevocationRepository {
+    pub fn new(pool: Pool<Postgres>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl TokenRevocationRepository for PostgresTokenRevocationRepository {
+    async fn revoke_token(&self, token: &RevokedToken) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "INSERT INTO revoked_tokens (jti, user_id, expires_at, revoked_at) 
+             VALUES ($1, $2, $3, $4) 
+             ON CONFLICT (jti) DO NOTHING"
+        )
+        .bind(token.jti)
+        .bind(token.user_id)
+        .bind(token.expires_at)
+        .bind(token.revoked_at)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn revoke_user_tokens(&self, user_id: &Uuid, revoked_before: DateTime<Utc>) -> Result<(), DomainError> {
+        let result = sqlx::query(
+            "INSERT INTO user_token_revocations (user_id, revoked_before) 
+             VALUES ($1, $2) 
+             ON CONFLICT (user_id) DO UPDATE SET revoked_before = EXCLUDED.revoked_before"
+        )
+        .bind(user_id)
+        .bind(revoked_before)
+        .execute(&self.pool)
+        .await;
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_active_revoked_tokens(&self) -> Result<Vec<RevokedToken>, DomainError> {
+        let result = sqlx::query_as::<_, RevokedToken>("SELECT * FROM revoked_tokens WHERE expires_at > $1")
+            .bind(Utc::now())
+            .fetch_all(&self.pool)
+            .await;
+
+        match result {
+            Ok(tokens) => Ok(tokens),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn find_user_token_revocations(&self) -> Result<Vec<UserTokenRevocation>, DomainError> {
+        let result = sqlx::query_as::<_, UserTokenRevocation>("SELECT * FROM user_token_revocations")
+            .fetch_all(&self.pool)
+            .await;
+
+        match result {
+            Ok(revocations) => Ok(revocations),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn delete_expired_revoked_tokens(&self) -> Result<u64, DomainError> {
+        let result = sqlx::query("DELETE FROM revoked_tokens WHERE expires_at <= $1")
+            .bind(Utc::now())
+            .execute(&self.pool)
+            .await;
+
+        match result {
+            Ok(r) => Ok(r.rows_affected()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -1,4 +1,6 @@
-use crate::domain::{entity::user_entity::User, repository::user_repository::UserRepository};
+use crate::domain::{
+    entity::user_entity::User, error::DomainError, repository::user_repository::UserRepository,
+};
 use async_trait::async_trait;
 use sqlx::{Pool, Postgres};
 use uuid::Uuid;
@@ -15,16 +17,17 @@ impl PostgresUserRepository {
 
 #[async_trait]
 impl UserRepository for PostgresUserRepository {
-    async fn create_user(&self, user: &User) -> Result<User, String> {
+    async fn create_user(&self, user: &User) -> Result<User, DomainError> {
         let result = sqlx::query_as::<_, User>(
-            "INSERT INTO users (id, username, email, password_hash, created_at, updated_at) 
-             VALUES ($1, $2, $3, $4, $5, $6) 
+            "INSERT INTO users (id, username, email, password_hash, phone_region, created_at, updated_at) 
+             VALUES ($1, $2, $3, $4, $5, $6, $7) 
              RETURNING *"
         )
         .bind(user.id)
         .bind(&user.username)
         .bind(&user.email)
         .bind(&user.password_hash)
+        .bind(&user.phone_region)
         .bind(user.created_at)
         .bind(user.updated_at)
         .fetch_one(&self.pool)
@@ -32,11 +35,11 @@ impl UserRepository for PostgresUserRepository {
 
         match result {
             Ok(u) => Ok(u),
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn find_user_by_email(&self, email: &str) -> Result<Option<User>, String> {
+    async fn find_user_by_email(&self, email: &str) -> Result<Option<User>, DomainError> {
         let result = sqlx::query_as::<_, User>("SELECT * FROM users WHERE email = $1")
             .bind(email)
             .fetch_optional(&self.pool)
@@ -44,11 +47,11 @@ impl UserRepository for PostgresUserRepository {
 
         match result {
             Ok(u) => Ok(u),
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(e.into()),
         }
     }
 
-    async fn find_user_by_id(&self, id: &Uuid) -> Result<Option<User>, String> {
+    async fn find_user_by_id(&self, id: &Uuid) -> Result<Option<User>, DomainError> {
         let result = sqlx::query_as::<_, User>("SELECT * FROM users WHERE id = $1")
             .bind(id)
             .fetch_optional(&self.pool)
@@ -56,7 +59,25 @@ impl UserRepository for PostgresUserRepository {
 
         match result {
             Ok(u) => Ok(u),
-            Err(e) => Err(e.to_string()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn update_user(&self, user: &User) -> Result<User, DomainError> {
+        let result = sqlx::query_as::<_, User>(
+            "UPDATE users SET username = $1, phone_region = $2, updated_at = $3 WHERE id = $4 RETURNING *"
+        )
+        .bind(&user.username)
+        .bind(&user.phone_region)
+        .bind(user.updated_at)
+        .bind(user.id)
+        .fetch_optional(&self.pool)
+        .await;
+
+        match result {
+            Ok(Some(u)) => Ok(u),
+            Ok(None) => Err(DomainError::NotFound("User not found".to_string())),
+            Err(e) => Err(e.into()),
         }
     }
 }
@@ -0,0 +1,116 @@
+// Country and postal code handling for contact addresses. Countries are
+// stored as ISO 3166-1 alpha-2 codes; postal codes in their country's
+// canonical spelling where the format is known.
+use super::phone;
+use crate::domain::error::DomainError;
+
+// Accepted postal code formats per country: `9` is a digit, `A` a letter,
+// anything else must appear as is. Spaces are optional on input and put back
+// on output. Countries not listed accept any postal code.
+const POSTAL_CODE_FORMATS: &[(&str, &[&str])] = &[
+    ("AT", &["9999"]),
+    ("AU", &["9999"]),
+    ("BE", &["9999"]),
+    ("BR", &["99999-999"]),
+    ("CA", &["A9A 9A9"]),
+    ("CH", &["9999"]),
+    ("CN", &["999999"]),
+    ("DE", &["99999"]),
+    ("DK", &["9999"]),
+    ("ES", &["99999"]),
+    ("FR", &["99999"]),
+    ("GB", &["A9 9AA", "A99 9AA", "A9A 9AA", "AA9 9AA", "AA99 9AA", "AA9A 9AA"]),
+    ("ID", &["99999"]),
+    ("IN", &["999999"]),
+    ("IT", &["99999"]),
+    ("JP", &["999-9999"]),
+    ("KR", &["99999"]),
+    ("MX", &["99999"]),
+    ("MY", &["99999"]),
+    ("NL", &["9999 AA"]),
+    ("NO", &["9999"]),
+    ("NZ", &["9999"]),
+    ("PH", &["9999"]),
+    ("PL", &["99-999"]),
+    ("PT", &["9999-999"]),
+    ("RU", &["999999"]),
+    ("SE", &["999 99"]),
+    ("SG", &["999999"]),
+    ("TH", &["99999"]),
+    ("US", &["99999", "99999-9999"]),
+    ("VN", &["999999"]),
+    ("ZA", &["9999"]),
+];
+
+// Upper-cased ISO 3166-1 alpha-2 code, e.g. "id" to "ID".
+pub fn normalize_country(raw: &str) -> Result<String, DomainError> {
+    match phone::parse_region(raw) {
+        Some(_) => Ok(raw.trim().to_ascii_uppercase()),
+        None => Err(DomainError::invalid_field(
+            "country",
+            "Use an ISO 3166-1 alpha-2 country code, e.g. \"ID\"",
+        )),
+    }
+}
+
+// `raw` in the canonical format for `country`, which must already be
+// normalized.
+pub fn normalize_postal_code(country: &str, raw: &str) -> Result<String, DomainError> {
+    let code = raw.trim().to_ascii_uppercase();
+    let Some((_, formats)) = POSTAL_CODE_FORMATS.iter().find(|(c, _)| *c == country) else {
+        return Ok(code);
+    };
+
+    let compact: Vec<char> = code.chars().filter(|c| !c.is_whitespace()).collect();
+    for format in *formats {
+        if let Some(formatted) = apply_format(format, &compact) {
+            return Ok(formatted);
+        }
+    }
+    Err(DomainError::invalid_field(
+        "postal_code",
+        format!("Expected a {} postal code like {}", country, formats.join(" or ")),
+    ))
+}
+
+fn apply_format(format: &str, compact: &[char]) -> Option<String> {
+    let mut input = compact.iter();
+    let mut out = String::with_capacity(format.len());
+    for f in format.chars() {
+        if f == ' ' {
+            out.push(' ');
+            continue;
+        }
+        let c = *input.next()?;
+        let fits = match f {
+            '9' => c.is_ascii_digit(),
+            'A' => c.is_ascii_uppercase(),
+            literal => c == literal,
+        };
+        if !fits {
+            return None;
+        }
+        out.push(c);
+    }
+    input.next().is_none().then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_postal_code_per_country() {
+        assert_eq!(normalize_country(" id ").unwrap(), "ID");
+        assert!(normalize_country("Indonesia").is_err());
+
+        assert_eq!(normalize_postal_code("ID", " 40115 ").unwrap(), "40115");
+        assert!(normalize_postal_code("ID", "4011").is_err());
+        assert_eq!(normalize_postal_code("US", "94105-1234").unwrap(), "94105-1234");
+        assert_eq!(normalize_postal_code("GB", "sw1a1aa").unwrap(), "SW1A 1AA");
+        assert_eq!(normalize_postal_code("CA", "k1a 0b1").unwrap(), "K1A 0B1");
+        assert!(normalize_postal_code("JP", "1000001").is_err());
+        // No known format: anything goes.
+        assert_eq!(normalize_postal_code("AR", "C1002 AAP").unwrap(), "C1002 AAP");
+    }
+}
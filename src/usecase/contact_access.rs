@@ -0,0 +1,28 @@
+// Lookups shared by the usecases working on someone's contacts.
+use crate::domain::{
+    entity::contact_entity::Contact,
+    error::DomainError,
+    repository::contact_repository::{ContactRepository, Tenant},
+};
+use uuid::Uuid;
+
+// A live personal contact of the user, for the personal-only features
+// (tags, groups, shares). A contact only shared with the user is Forbidden;
+// any other is not found.
+pub async fn find_owned_contact(
+    contact_repo: &dyn ContactRepository,
+    user_id: Uuid,
+    contact_id: Uuid,
+) -> Result<Contact, DomainError> {
+    let tenant = Tenant::personal(user_id);
+    let contact = contact_repo
+        .find_contact_by_id(&tenant, &contact_id)
+        .await?
+        .filter(|c| c.deleted_at.is_none())
+        .ok_or_else(|| DomainError::NotFound("Contact not found".to_string()))?;
+
+    if !tenant.owns(&contact) {
+        return Err(DomainError::Forbidden("Only the owner of this contact can do this".to_string()));
+    }
+    Ok(contact)
+}
@@ -0,0 +1,181 @@
+// CSV reader/writer for bulk contact moves. One row is one contact with at
+// most one address; exports carry the contact's primary address.
+use crate::domain::entity::{address_entity::Address, contact_entity::Contact};
+use crate::domain::error::DomainError;
+use serde::{Deserialize, Serialize};
+
+// Column order of exported files, and the default header for each field on
+// import.
+pub const CSV_FIELDS: [&str; 9] = [
+    "first_name",
+    "last_name",
+    "email",
+    "phone",
+    "street",
+    "city",
+    "province",
+    "postal_code",
+    "country",
+];
+
+// Query of `POST /contacts/import/csv`. Each field names the header that
+// holds it; unset fields fall back to a header equal to the field name.
+// Headers match case-insensitively; unknown fields are rejected.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CsvImportOptions {
+    // Validate every row without creating anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub street: Option<String>,
+    pub city: Option<String>,
+    pub province: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+}
+
+impl CsvImportOptions {
+    fn header_for<'a>(&'a self, field: &'a str) -> (&'a str, bool) {
+        let mapped = match field {
+            "first_name" => &self.first_name,
+            "last_name" => &self.last_name,
+            "email" => &self.email,
+            "phone" => &self.phone,
+            "street" => &self.street,
+            "city" => &self.city,
+            "province" => &self.province,
+            "postal_code" => &self.postal_code,
+            "country" => &self.country,
+            _ => &None,
+        };
+        match mapped {
+            Some(header) => (header.as_str(), true),
+            None => (field, false),
+        }
+    }
+}
+
+// One row, indexed like CSV_FIELDS. Blank cells are None.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CsvContact {
+    values: [Option<String>; 9],
+}
+
+impl CsvContact {
+    pub fn get(&self, field: &str) -> Option<String> {
+        let index = CSV_FIELDS.iter().position(|f| *f == field)?;
+        self.values[index].clone()
+    }
+
+    // True when any of the address columns has a value.
+    pub fn has_address(&self) -> bool {
+        self.values[4..].iter().any(Option::is_some)
+    }
+}
+
+// Reads every data row of `input`. A header row is required; a mapping that
+// names a missing header, or a file without a first name column, rejects the
+// whole upload. Each entry is the row or the reason it could not be read.
+pub fn read_contacts(input: &[u8], options: &CsvImportOptions) -> Result<Vec<Result<CsvContact, String>>, DomainError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input);
+
+    let headers: Vec<String> = match reader.headers() {
+        Ok(headers) => headers.iter().map(str::to_lowercase).collect(),
+        Err(e) => return Err(DomainError::validation(format!("Unreadable CSV header: {}", e))),
+    };
+
+    let mut columns = [None; 9];
+    for (i, field) in CSV_FIELDS.iter().enumerate() {
+        let (header, mapped) = options.header_for(field);
+        columns[i] = headers.iter().position(|h| *h == header.to_lowercase());
+        if mapped && columns[i].is_none() {
+            return Err(DomainError::invalid_field(*field, format!("Column '{}' not found", header)));
+        }
+    }
+    if columns[0].is_none() {
+        return Err(DomainError::invalid_field("first_name", "Column 'first_name' not found"));
+    }
+
+    let rows = reader
+        .records()
+        .map(|record| {
+            let record = record.map_err(|e| format!("Unreadable row: {}", e))?;
+            let mut contact = CsvContact::default();
+            for (value, column) in contact.values.iter_mut().zip(columns) {
+                *value = column
+                    .and_then(|c| record.get(c))
+                    .filter(|v| !v.is_empty())
+                    .map(str::to_string);
+            }
+            Ok(contact)
+        })
+        .collect();
+    Ok(rows)
+}
+
+pub fn write_header() -> Vec<u8> {
+    write_record(&CSV_FIELDS)
+}
+
+pub fn write_contact(out: &mut Vec<u8>, contact: &Contact, address: Option<&Address>) {
+    let text = |v: &Option<String>| v.clone().unwrap_or_default();
+    let record = [
+        contact.first_name.clone(),
+        text(&contact.last_name),
+        text(&contact.email),
+        text(&contact.phone),
+        address.map(|a| text(&a.street)).unwrap_or_default(),
+        address.map(|a| text(&a.city)).unwrap_or_default(),
+        address.map(|a| text(&a.province)).unwrap_or_default(),
+        address.map(|a| text(&a.postal_code)).unwrap_or_default(),
+        address.map(|a| a.country.clone()).unwrap_or_default(),
+    ];
+    out.extend(write_record(&record));
+}
+
+fn write_record<T: AsRef<[u8]>>(fields: &[T]) -> Vec<u8> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    // Writing into a Vec cannot fail.
+    writer.write_record(fields).expect("in-memory CSV write");
+    writer.into_inner().expect("in-memory CSV flush")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_contacts_applies_mapping() {
+        let input = "\u{feff}Given Name,Surname,E-mail,Town\n\
+                     Budi,\"Santoso, Jr.\",budi@example.com,Jakarta\n\
+                     ,Nobody,,\n";
+        let options = CsvImportOptions {
+            first_name: Some("given name".to_string()),
+            last_name: Some("Surname".to_string()),
+            email: Some("E-mail".to_string()),
+            city: Some("Town".to_string()),
+            ..Default::default()
+        };
+
+        let rows = read_contacts(input.as_bytes(), &options).unwrap();
+        let first = rows[0].as_ref().unwrap();
+        assert_eq!(first.get("first_name").as_deref(), Some("Budi"));
+        assert_eq!(first.get("last_name").as_deref(), Some("Santoso, Jr."));
+        assert_eq!(first.get("city").as_deref(), Some("Jakarta"));
+        assert!(first.has_address());
+        assert_eq!(rows[1].as_ref().unwrap().get("first_name"), None);
+
+        let missing = CsvImportOptions {
+            phone: Some("Mobile".to_string()),
+            ..options
+        };
+        assert!(read_contacts(input.as_bytes(), &missing).is_err());
+    }
+}
@@ -1,24 +1,68 @@
+use super::address;
+use super::contact_csv::{self, CsvContact, CsvImportOptions};
+use super::custom_field_usecase;
+use super::duplicates::{self, DuplicateReason};
+use super::phone;
+use super::vcard::{self, VCard, VCardEntry, VCardVersion};
 use crate::domain::{
-    entity::{address_entity::Address, contact_entity::Contact},
-    repository::contact_repository::ContactRepository,
+    entity::{
+        address_entity::{Address, AddressKind},
+        contact_entity::Contact,
+        contact_method_entity::{ContactEmail, ContactLabel, ContactPhone},
+        contact_version_entity::{ContactChange, ContactSnapshot},
+        custom_field_entity::CustomFields,
+        share_entity::SharePermission,
+    },
+    error::{DomainError, FieldErrors},
+    repository::{
+        contact_repository::{
+            ContactChanges, ContactCursor, ContactQuery, ContactRepository, ContactSortField, PhoneFilter, SortDirection,
+            Tenant,
+        },
+        contact_version_repository::ContactVersionRepository,
+        custom_field_repository::CustomFieldRepository,
+        share_repository::ShareRepository,
+        tag_repository::TagRepository,
+        user_repository::UserRepository,
+    },
 };
-use chrono::Utc;
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, Utc};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::types::Json;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use uuid::Uuid;
 use validator::Validate;
 
-#[derive(Debug, Serialize, Deserialize, Validate)]
+// `email` and `phone` are shorthands for a single primary entry labelled
+// `other`; use `emails` and `phones` for more than one.
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
 pub struct CreateContactRequest {
     #[validate(length(min = 1, message = "First name is required"))]
     pub first_name: String,
     pub last_name: Option<String>,
     #[validate(email(message = "Invalid email format"))]
     pub email: Option<String>,
-    #[validate(length(min = 3, message = "Phone must be at least 3 characters"))]
+    // Any common format; stored as E.164. National numbers are read in the
+    // user's `phone_region`.
+    #[validate(length(max = 64, message = "Phone must be at most 64 characters"))]
     pub phone: Option<String>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub emails: Vec<CreateEmailRequest>,
+    #[serde(default)]
+    #[validate(nested)]
+    pub phones: Vec<CreatePhoneRequest>,
+    // Checked against the user's custom field definitions.
+    #[serde(default)]
+    pub custom_fields: CustomFields,
 }
 
+// `email` and `phone` replace the primary entry; the others are managed
+// under `/contacts/:id/emails` and `/contacts/:id/phones`.
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct UpdateContactRequest {
     #[validate(length(min = 1, message = "First name is required"))]
@@ -26,17 +70,40 @@ pub struct UpdateContactRequest {
     pub last_name: Option<String>,
     #[validate(email(message = "Invalid email format"))]
     pub email: Option<String>,
+    #[validate(length(max = 64, message = "Phone must be at most 64 characters"))]
     pub phone: Option<String>,
+    // Merged into the current values; a null removes that field's value.
+    pub custom_fields: Option<CustomFields>,
+}
+
+// Body of `PATCH /contacts/:id`. Both formats operate on a document shaped
+// like CreateContactRequest, so an explicit null clears an optional field
+// while an absent one is left alone.
+#[derive(Debug)]
+pub enum ContactPatch {
+    // application/merge-patch+json (RFC 7396)
+    Merge(Value),
+    // application/json-patch+json (RFC 6902)
+    Json(json_patch::Patch),
 }
 
+const PATCHABLE_FIELDS: [&str; 5] = ["first_name", "last_name", "email", "phone", "custom_fields"];
+
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct CreateAddressRequest {
     pub street: Option<String>,
     pub city: Option<String>,
     pub province: Option<String>,
+    // ISO 3166-1 alpha-2 code, e.g. "ID".
     #[validate(length(min = 1, message = "Country is required"))]
     pub country: String,
+    // Checked against the country's format where it is known.
     pub postal_code: Option<String>,
+    #[serde(default)]
+    pub kind: AddressKind,
+    // A contact's first address is primary even without this.
+    #[serde(default)]
+    pub primary: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
@@ -44,18 +111,133 @@ pub struct UpdateAddressRequest {
     pub street: Option<String>,
     pub city: Option<String>,
     pub province: Option<String>,
+    #[validate(length(min = 1, message = "Country is required"))]
     pub country: Option<String>,
     pub postal_code: Option<String>,
+    pub kind: Option<AddressKind>,
+    // Only `true` is accepted, as for UpdateEmailRequest.
+    pub primary: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateEmailRequest {
+    #[validate(email(message = "Invalid email format"))]
+    #[validate(length(max = 100, message = "Email must be at most 100 characters"))]
+    pub email: String,
+    #[serde(default)]
+    pub label: ContactLabel,
+    // A contact's first email is primary even without this.
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateEmailRequest {
+    #[validate(email(message = "Invalid email format"))]
+    #[validate(length(max = 100, message = "Email must be at most 100 characters"))]
+    pub email: Option<String>,
+    pub label: Option<ContactLabel>,
+    // Only `true` is accepted: a contact keeps a primary email until another
+    // one is made primary.
+    pub primary: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreatePhoneRequest {
+    // Parsed like CreateContactRequest::phone.
+    #[validate(length(min = 1, max = 64, message = "Phone must be between 1 and 64 characters"))]
+    pub phone: String,
+    #[serde(default)]
+    pub label: ContactLabel,
+    #[serde(default)]
+    pub primary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdatePhoneRequest {
+    #[validate(length(min = 1, max = 64, message = "Phone must be between 1 and 64 characters"))]
+    pub phone: Option<String>,
+    pub label: Option<ContactLabel>,
+    pub primary: Option<bool>,
+}
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const CSV_EXPORT_PAGE_SIZE: i64 = 500;
+
+#[derive(Debug, Default, Serialize, Deserialize, Validate)]
+pub struct ContactListQuery {
+    // Ranked full-text search across contact and address fields; results are
+    // ordered by relevance, so it cannot be combined with sort or cursor.
+    pub q: Option<String>,
+    pub name: Option<String>,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    // Tag name and group id, see ContactQuery.
+    pub tag: Option<String>,
+    pub group: Option<Uuid>,
+    // JSON object of custom field values the contact must have, e.g.
+    // `{"tier":"gold"}`.
+    pub custom_fields: Option<String>,
+    pub sort: Option<ContactSortField>,
+    pub order: Option<SortDirection>,
+    #[validate(range(min = 1, max = 100, message = "Limit must be between 1 and 100"))]
+    pub limit: Option<i64>,
+    #[validate(range(min = 0, message = "Offset must not be negative"))]
+    pub offset: Option<i64>,
+    // Opaque `next_cursor` from a previous page; cannot be combined with offset.
+    pub cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContactListResponse {
+    pub data: Vec<ContactResponse>,
+    pub total: i64,
+    pub limit: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub offset: Option<i64>,
+    pub next_cursor: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ContactResponse {
     pub id: Uuid,
+    // Set for contacts of an organization.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub organization_id: Option<Uuid>,
+    // Also sent as the ETag; echo it in If-Match to make writes conditional.
+    pub version: i32,
     pub first_name: String,
     pub last_name: Option<String>,
+    // The primary email and phone.
     pub email: Option<String>,
     pub phone: Option<String>,
+    // The phone number as entered.
+    pub phone_raw: Option<String>,
+    pub emails: Vec<EmailResponse>,
+    pub phones: Vec<PhoneResponse>,
     pub addresses: Vec<AddressResponse>,
+    pub tags: Vec<String>,
+    pub custom_fields: CustomFields,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub deleted_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub search: Option<SearchMatch>,
+}
+
+// A contact someone else owns, with what the caller may do with it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SharedContactResponse {
+    #[serde(flatten)]
+    pub contact: ContactResponse,
+    pub permission: SharePermission,
+    pub shared_by: Uuid,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchMatch {
+    pub rank: f32,
+    // Matched text with hits wrapped in <mark></mark>.
+    pub snippet: String,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -66,17 +248,46 @@ pub struct AddressResponse {
     pub province: Option<String>,
     pub country: String,
     pub postal_code: Option<String>,
+    pub kind: AddressKind,
+    pub primary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EmailResponse {
+    pub id: Uuid,
+    pub email: String,
+    pub label: ContactLabel,
+    pub primary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PhoneResponse {
+    pub id: Uuid,
+    // E.164.
+    pub phone: String,
+    pub phone_raw: Option<String>,
+    pub label: ContactLabel,
+    pub primary: bool,
 }
 
 impl From<Contact> for ContactResponse {
     fn from(c: Contact) -> Self {
         Self {
             id: c.id,
+            organization_id: c.organization_id,
+            version: c.version,
             first_name: c.first_name,
             last_name: c.last_name,
             email: c.email,
             phone: c.phone,
+            phone_raw: c.phone_raw,
+            emails: vec![],
+            phones: vec![],
             addresses: vec![], // Populated separately if needed
+            tags: vec![],
+            custom_fields: c.custom_fields.0,
+            deleted_at: c.deleted_at,
+            search: None,
         }
     }
 }
@@ -90,58 +301,505 @@ impl From<Address> for AddressResponse {
             province: a.province,
             country: a.country,
             postal_code: a.postal_code,
+            kind: a.kind,
+            primary: a.is_primary,
+        }
+    }
+}
+
+impl From<ContactEmail> for EmailResponse {
+    fn from(e: ContactEmail) -> Self {
+        Self {
+            id: e.id,
+            email: e.email,
+            label: e.label,
+            primary: e.is_primary,
+        }
+    }
+}
+
+impl From<ContactPhone> for PhoneResponse {
+    fn from(p: ContactPhone) -> Self {
+        Self {
+            id: p.id,
+            phone: p.phone,
+            phone_raw: p.phone_raw,
+            label: p.label,
+            primary: p.is_primary,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct FieldChange {
+    // Contact field name, or `<collection>[<id>].<field>` for the fields of
+    // an address, email or phone (e.g. `emails[<id>].label`).
+    pub field: String,
+    pub old: Value,
+    pub new: Value,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ContactVersionResponse {
+    pub version: i32,
+    pub action: ContactChange,
+    pub actor_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    // Differences from the previous version.
+    pub changes: Vec<FieldChange>,
+}
+
+// Contacts that look like the same person, most likely first.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DuplicateGroupResponse {
+    pub confidence: f32,
+    pub reasons: Vec<DuplicateReason>,
+    pub contacts: Vec<ContactResponse>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct MergeContactsRequest {
+    // The contact that survives. Its own names win; empty ones are filled
+    // from the sources in order. Its primary email and phone stay primary.
+    pub target_id: Uuid,
+    // Moved to the trash once their addresses, emails, phones, tags and
+    // groups move to the target.
+    #[validate(length(min = 1, max = 50, message = "Between 1 and 50 source contacts are required"))]
+    pub source_ids: Vec<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ImportItemResult {
+    // 1-based position of the item in the uploaded file.
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub contact_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "FieldErrors::is_empty", default)]
+    pub errors: FieldErrors,
+}
+
+// Outcome of a bulk import. Items are independent: one bad item does not
+// stop the others. On a dry run nothing is written and `created` counts the
+// items that would have been.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ImportReport {
+    pub dry_run: bool,
+    pub created: usize,
+    pub failed: usize,
+    pub results: Vec<ImportItemResult>,
+}
+
+impl ImportReport {
+    fn push(&mut self, index: usize, result: Result<Option<Uuid>, DomainError>) {
+        let item = match result {
+            Ok(contact_id) => {
+                self.created += 1;
+                ImportItemResult { index, contact_id, error: None, errors: FieldErrors::new() }
+            }
+            Err(e) => {
+                self.failed += 1;
+                let (error, errors) = match e {
+                    DomainError::Validation { message, fields } => (message, fields),
+                    // Driver messages stay out of the report, as they do in
+                    // error responses.
+                    DomainError::Infrastructure(detail) => {
+                        tracing::error!("import item {} failed: {}", index, detail);
+                        ("Internal error".to_string(), FieldErrors::new())
+                    }
+                    e => (e.to_string(), FieldErrors::new()),
+                };
+                ImportItemResult { index, contact_id: None, error: Some(error), errors }
+            }
+        };
+        self.results.push(item);
+    }
+}
+
+fn vcard_requests(card: VCard) -> (CreateContactRequest, Vec<CreateAddressRequest>) {
+    // Only the first preferred entry becomes primary.
+    let first_preferred = |entries: &[VCardEntry]| entries.iter().position(|e| e.preferred);
+    let email_primary = first_preferred(&card.emails);
+    let phone_primary = first_preferred(&card.phones);
+    let contact = CreateContactRequest {
+        first_name: card.first_name.unwrap_or_default(),
+        last_name: card.last_name,
+        emails: card
+            .emails
+            .into_iter()
+            .enumerate()
+            .map(|(i, e)| CreateEmailRequest { email: e.value, label: e.label, primary: Some(i) == email_primary })
+            .collect(),
+        phones: card
+            .phones
+            .into_iter()
+            .enumerate()
+            .map(|(i, p)| CreatePhoneRequest { phone: p.value, label: p.label, primary: Some(i) == phone_primary })
+            .collect(),
+        ..Default::default()
+    };
+    let address_primary = card.addresses.iter().position(|a| a.preferred);
+    let addresses = card
+        .addresses
+        .into_iter()
+        .enumerate()
+        .map(|(i, a)| CreateAddressRequest {
+            street: a.street,
+            city: a.city,
+            province: a.province,
+            country: a.country.unwrap_or_default(),
+            postal_code: a.postal_code,
+            kind: a.kind,
+            primary: Some(i) == address_primary,
+        })
+        .collect();
+    (contact, addresses)
+}
+
+fn csv_requests(row: CsvContact) -> (CreateContactRequest, Vec<CreateAddressRequest>) {
+    let contact = CreateContactRequest {
+        first_name: row.get("first_name").unwrap_or_default(),
+        last_name: row.get("last_name"),
+        email: row.get("email"),
+        phone: row.get("phone"),
+        ..Default::default()
+    };
+    let mut addresses = Vec::new();
+    if row.has_address() {
+        addresses.push(CreateAddressRequest {
+            street: row.get("street"),
+            city: row.get("city"),
+            province: row.get("province"),
+            country: row.get("country").unwrap_or_default(),
+            postal_code: row.get("postal_code"),
+            kind: AddressKind::Other,
+            primary: false,
+        });
+    }
+    (contact, addresses)
+}
+
+// The normalized country code and postal code of an address; a blank
+// postal code is dropped.
+fn checked_location(country: &str, postal_code: Option<String>) -> Result<(String, Option<String>), DomainError> {
+    let country = address::normalize_country(country)?;
+    let postal_code = match postal_code.filter(|p| !p.trim().is_empty()) {
+        Some(postal_code) => Some(address::normalize_postal_code(&country, &postal_code)?),
+        None => None,
+    };
+    Ok((country, postal_code))
+}
+
+// Flattens a snapshot into `field`, `collection[<id>].field` and
+// `custom_fields.<name>` entries.
+fn snapshot_fields(snapshot: &ContactSnapshot) -> BTreeMap<String, Value> {
+    let mut fields = BTreeMap::new();
+    let Ok(Value::Object(contact)) = serde_json::to_value(snapshot) else {
+        return fields;
+    };
+    for (name, value) in contact {
+        let items = match value {
+            Value::Array(items) => items,
+            Value::Object(values) => {
+                for (field, value) in values {
+                    fields.insert(format!("{}.{}", name, field), value);
+                }
+                continue;
+            }
+            value => {
+                fields.insert(name, value);
+                continue;
+            }
+        };
+        for item in items {
+            let Value::Object(mut values) = item else {
+                continue;
+            };
+            let id = values.remove("id").unwrap_or_default();
+            let id = id.as_str().unwrap_or_default();
+            for (field, value) in values {
+                fields.insert(format!("{}[{}].{}", name, id, field), value);
+            }
+        }
+    }
+    // The contact-level email and phone only repeat the primary entries.
+    if snapshot.emails.is_some() {
+        fields.remove("email");
+    }
+    if snapshot.phones.is_some() {
+        fields.remove("phone");
+        fields.remove("phone_raw");
+    }
+    fields
+}
+
+// Field-level diff; a field missing on one side (a new or removed address,
+// email or phone, or the first version) counts as null.
+fn diff_snapshots(old: Option<&ContactSnapshot>, new: &ContactSnapshot) -> Vec<FieldChange> {
+    let mut old = old.map(snapshot_fields).unwrap_or_default();
+    let mut changes = Vec::new();
+
+    for (field, new_value) in snapshot_fields(new) {
+        let old_value = old.remove(&field).unwrap_or(Value::Null);
+        if old_value != new_value {
+            changes.push(FieldChange { field, old: old_value, new: new_value });
+        }
+    }
+    for (field, old_value) in old {
+        if !old_value.is_null() {
+            changes.push(FieldChange { field, old: old_value, new: Value::Null });
+        }
+    }
+
+    changes.sort_by(|a, b| a.field.cmp(&b.field));
+    changes
+}
+
+// Re-keys the field errors of `e` under `prefix`, e.g. `phone` to
+// `phones[0].phone`.
+fn prefix_fields(e: DomainError, prefix: &str) -> DomainError {
+    match e {
+        DomainError::Validation { message, fields } => DomainError::Validation {
+            message,
+            fields: fields
+                .into_iter()
+                .map(|(field, messages)| (format!("{}.{}", prefix, field), messages))
+                .collect(),
+        },
+        e => e,
+    }
+}
+
+// Position of the entry flagged primary, or the first one if none is.
+fn primary_index(flags: impl Iterator<Item = bool>, field: &str) -> Result<usize, DomainError> {
+    let flagged: Vec<usize> = flags.enumerate().filter(|(_, primary)| *primary).map(|(i, _)| i).collect();
+    if flagged.len() > 1 {
+        return Err(DomainError::invalid_field(field, "Only one entry can be primary"));
+    }
+    Ok(flagged.first().copied().unwrap_or(0))
+}
+
+// Emails of a new contact, from either the `email` shorthand or the
+// `emails` list.
+fn new_emails(
+    contact_id: Uuid,
+    email: Option<String>,
+    emails: Vec<CreateEmailRequest>,
+) -> Result<Vec<ContactEmail>, DomainError> {
+    let requests = match email {
+        Some(_) if !emails.is_empty() => {
+            return Err(DomainError::invalid_field("email", "Use either email or emails, not both"));
         }
+        Some(email) => vec![CreateEmailRequest { email, label: ContactLabel::Other, primary: true }],
+        None => emails,
+    };
+    let primary = primary_index(requests.iter().map(|r| r.primary), "emails")?;
+
+    let now = Utc::now();
+    Ok(requests
+        .into_iter()
+        .enumerate()
+        .map(|(i, req)| ContactEmail {
+            id: Uuid::new_v4(),
+            contact_id,
+            email: req.email,
+            label: req.label,
+            is_primary: i == primary,
+            // Spaced apart so listings keep the request order.
+            created_at: now + Duration::microseconds(i as i64),
+            updated_at: now,
+        })
+        .collect())
+}
+
+fn new_address(contact_id: Uuid, req: CreateAddressRequest, created_at: DateTime<Utc>) -> Result<Address, DomainError> {
+    let (country, postal_code) = checked_location(&req.country, req.postal_code)?;
+    Ok(Address {
+        id: Uuid::new_v4(),
+        contact_id,
+        street: req.street,
+        city: req.city,
+        province: req.province,
+        country,
+        postal_code,
+        kind: req.kind,
+        is_primary: req.primary,
+        created_at,
+        updated_at: created_at,
+    })
+}
+
+// What an edit through the contact's own `email` or `phone` field does to
+// the primary entry.
+enum PrimaryChange<T> {
+    Keep,
+    Set(T),
+    // The next oldest entry, if any, becomes primary.
+    Remove,
+}
+
+fn email_change(contact: &Contact, email: Option<String>) -> PrimaryChange<String> {
+    match email {
+        Some(email) if contact.email.as_ref() == Some(&email) => PrimaryChange::Keep,
+        Some(email) => PrimaryChange::Set(email),
+        None if contact.email.is_some() => PrimaryChange::Remove,
+        None => PrimaryChange::Keep,
+    }
+}
+
+fn patched_request(document: Value) -> Result<CreateContactRequest, DomainError> {
+    let Value::Object(fields) = &document else {
+        return Err(DomainError::validation("Patched contact must be a JSON object"));
+    };
+    if let Some(unknown) = fields.keys().find(|k| !PATCHABLE_FIELDS.contains(&k.as_str())) {
+        return Err(DomainError::invalid_field(unknown.clone(), "Unknown field"));
+    }
+    if fields.get("first_name").is_none_or(Value::is_null) {
+        return Err(DomainError::invalid_field("first_name", "First name is required"));
+    }
+    let mut document = document;
+    if document.get("custom_fields").is_some_and(Value::is_null) {
+        document["custom_fields"] = Value::Object(CustomFields::new());
+    }
+
+    serde_json::from_value(document).map_err(|e| DomainError::validation(format!("Invalid patched contact: {}", e)))
+}
+
+fn check_version(contact: &Contact, if_match: Option<&[i32]>) -> Result<(), DomainError> {
+    match if_match {
+        Some(versions) if !versions.contains(&contact.version) => Err(DomainError::PreconditionFailed(
+            "Contact has been modified since it was read".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn encode_cursor(cursor: &ContactCursor) -> String {
+    URL_SAFE_NO_PAD.encode(serde_json::to_vec(cursor).expect("cursor serializes"))
+}
+
+fn decode_cursor(encoded: &str, sort: ContactSortField) -> Result<ContactCursor, DomainError> {
+    let invalid = || DomainError::invalid_field("cursor", "Invalid cursor");
+
+    let bytes = URL_SAFE_NO_PAD.decode(encoded).map_err(|_| invalid())?;
+    let cursor: ContactCursor = serde_json::from_slice(&bytes).map_err(|_| invalid())?;
+
+    // A cursor only makes sense for the ordering it was issued under.
+    if cursor.sort != sort {
+        return Err(DomainError::invalid_field("cursor", "Cursor does not match the requested sort"));
     }
+    Ok(cursor)
+}
+
+// What a caller needs to be allowed to do with a contact. Sharing grants
+// read or edit; deleting, merging and the trash stay with the owner.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Access {
+    Read,
+    Edit,
+    Own,
 }
 
 pub struct ContactUsecase {
     repo: Arc<dyn ContactRepository>,
+    tag_repo: Arc<dyn TagRepository>,
+    version_repo: Arc<dyn ContactVersionRepository>,
+    user_repo: Arc<dyn UserRepository>,
+    field_repo: Arc<dyn CustomFieldRepository>,
+    share_repo: Arc<dyn ShareRepository>,
 }
 
 impl ContactUsecase {
-    pub fn new(repo: Arc<dyn ContactRepository>) -> Self {
-        Self { repo }
+    pub fn new(
+        repo: Arc<dyn ContactRepository>,
+        tag_repo: Arc<dyn TagRepository>,
+        version_repo: Arc<dyn ContactVersionRepository>,
+        user_repo: Arc<dyn UserRepository>,
+        field_repo: Arc<dyn CustomFieldRepository>,
+        share_repo: Arc<dyn ShareRepository>,
+    ) -> Self {
+        Self { repo, tag_repo, version_repo, user_repo, field_repo, share_repo }
     }
 
     pub async fn create_contact(
         &self,
-        user_id: Uuid,
+        tenant: Tenant,
+        req: CreateContactRequest,
+    ) -> Result<ContactResponse, DomainError> {
+        let (created_contact, changes) = self.insert_contact(tenant, req, Vec::new()).await?;
+
+        // Ordered like the repository lists them: primary first.
+        let mut response: ContactResponse = created_contact.into();
+        response.emails = changes.emails.into_iter().map(EmailResponse::from).collect();
+        response.emails.sort_by_key(|e| !e.primary);
+        response.phones = changes.phones.into_iter().map(PhoneResponse::from).collect();
+        response.phones.sort_by_key(|p| !p.primary);
+        Ok(response)
+    }
+
+    // Saves a new contact with its emails, phones and `addresses` in one
+    // write, returning what was saved.
+    async fn insert_contact(
+        &self,
+        tenant: Tenant,
         req: CreateContactRequest,
-    ) -> Result<ContactResponse, String> {
-        req.validate().map_err(|e| e.to_string())?;
+        addresses: Vec<CreateAddressRequest>,
+    ) -> Result<(Contact, ContactChanges), DomainError> {
+        req.validate()?;
+
+        // Everything is checked before the write.
+        let contact_id = Uuid::new_v4();
+        let emails = new_emails(contact_id, req.email, req.emails)?;
+        let phones = self.new_phones(tenant.user_id, contact_id, req.phone, req.phones).await?;
+        let custom_fields = self.checked_custom_fields(tenant.user_id, req.custom_fields).await?;
+        let now = Utc::now();
+        let addresses = addresses
+            .into_iter()
+            .enumerate()
+            .map(|(i, req)| new_address(contact_id, req, now + Duration::microseconds(i as i64)))
+            .collect::<Result<Vec<_>, _>>()?;
 
+        // `email` and `phone` are filled in from the primary entries.
         let new_contact = Contact {
-            id: Uuid::new_v4(),
-            user_id,
+            id: contact_id,
+            user_id: tenant.user_id,
+            organization_id: tenant.organization_id,
             first_name: req.first_name,
             last_name: req.last_name,
-            email: req.email,
-            phone: req.phone,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+            email: None,
+            phone: None,
+            phone_raw: None,
+            custom_fields,
+            created_at: now,
+            updated_at: now,
+            version: 1,
+            deleted_at: None,
+        };
+        let changes = ContactChanges {
+            addresses,
+            emails,
+            phones,
+            ..Default::default()
         };
 
-        let created_contact = self.repo.create_contact(&new_contact).await?;
-        Ok(created_contact.into())
+        let created_contact = self.repo.create_contact(&tenant, &new_contact, &changes).await?;
+        Ok((created_contact, changes))
     }
 
+    // `if_match` lists the versions the client is willing to overwrite; None
+    // accepts any.
     pub async fn update_contact(
         &self,
-        user_id: Uuid,
+        tenant: Tenant,
         contact_id: Uuid,
         req: UpdateContactRequest,
-    ) -> Result<ContactResponse, String> {
-        req.validate().map_err(|e| e.to_string())?;
-
-        let mut contact = self
-            .repo
-            .find_contact_by_id(&contact_id)
-            .await?
-            .ok_or("Contact not found")?;
+        if_match: Option<&[i32]>,
+    ) -> Result<ContactResponse, DomainError> {
+        req.validate()?;
 
-        if contact.user_id != user_id {
-            return Err("Unauthorized".to_string());
-        }
+        let mut contact = self.find_contact(tenant, contact_id, Access::Edit).await?;
+        check_version(&contact, if_match)?;
 
         if let Some(first_name) = req.first_name {
             contact.first_name = first_name;
@@ -149,164 +807,1702 @@ impl ContactUsecase {
         if let Some(last_name) = req.last_name {
             contact.last_name = Some(last_name);
         }
-        if let Some(email) = req.email {
-            contact.email = Some(email);
-        }
-        if let Some(phone) = req.phone {
-            contact.phone = Some(phone);
+        let email = match req.email {
+            Some(email) => email_change(&contact, Some(email)),
+            None => PrimaryChange::Keep,
+        };
+        let phone = match req.phone {
+            Some(phone) => self.phone_change(tenant.user_id, &contact, Some(phone)).await?,
+            None => PrimaryChange::Keep,
+        };
+        let mut custom_fields = contact.custom_fields.0.clone();
+        for (name, value) in req.custom_fields.unwrap_or_default() {
+            match value {
+                Value::Null => custom_fields.remove(&name),
+                value => custom_fields.insert(name, value),
+            };
         }
+        contact.custom_fields = self.checked_custom_fields(contact.user_id, custom_fields).await?;
 
         contact.updated_at = Utc::now();
 
-        let updated_contact = self.repo.update_contact(&contact).await?;
-        Ok(updated_contact.into())
+        let changes = self.primary_changes(tenant, &contact, email, phone).await?;
+        let updated_contact = self.repo.update_contact(&tenant, &contact, &changes, ContactChange::Updated).await?;
+        let mut responses = self.with_relations(tenant, vec![updated_contact]).await?;
+        Ok(responses.remove(0))
     }
 
-    pub async fn delete_contact(&self, user_id: Uuid, contact_id: Uuid) -> Result<(), String> {
-        let contact = self
-            .repo
-            .find_contact_by_id(&contact_id)
-            .await?
-            .ok_or("Contact not found")?;
+    pub async fn patch_contact(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        patch: ContactPatch,
+        if_match: Option<&[i32]>,
+    ) -> Result<ContactResponse, DomainError> {
+        let mut contact = self.find_contact(tenant, contact_id, Access::Edit).await?;
+        check_version(&contact, if_match)?;
 
-        if contact.user_id != user_id {
-            return Err("Unauthorized".to_string());
+        let mut document = serde_json::json!({
+            "first_name": contact.first_name,
+            "last_name": contact.last_name,
+            "email": contact.email,
+            "phone": contact.phone_raw.as_ref().or(contact.phone.as_ref()),
+            "custom_fields": contact.custom_fields.0,
+        });
+        match patch {
+            ContactPatch::Merge(merge) => json_patch::merge(&mut document, &merge),
+            // A failed `test` or a missing path means the patch does not fit the
+            // current state of the contact.
+            ContactPatch::Json(operations) => json_patch::patch(&mut document, &operations)
+                .map_err(|e| DomainError::Conflict(format!("Patch could not be applied: {}", e)))?,
         }
 
-        self.repo.delete_contact(&contact_id).await
+        let req = patched_request(document)?;
+        req.validate()?;
+
+        let email = email_change(&contact, req.email);
+        let phone = self.phone_change(tenant.user_id, &contact, req.phone).await?;
+        contact.custom_fields = self.checked_custom_fields(contact.user_id, req.custom_fields).await?;
+        contact.first_name = req.first_name;
+        contact.last_name = req.last_name;
+        contact.updated_at = Utc::now();
+
+        let changes = self.primary_changes(tenant, &contact, email, phone).await?;
+        let updated_contact = self.repo.update_contact(&tenant, &contact, &changes, ContactChange::Updated).await?;
+        let mut responses = self.with_relations(tenant, vec![updated_contact]).await?;
+        Ok(responses.remove(0))
     }
 
-    pub async fn search_contacts(&self, user_id: Uuid) -> Result<Vec<ContactResponse>, String> {
-        let contacts = self.repo.find_contacts_by_user_id(&user_id).await?;
-        
-        // For each contact, we might want to fetch addresses.
-        // Doing this in a loop resembles N+1 query problem, but allowed for simplicity here.
-        // In optimized real world, we would use a join query in repository.
-        
-        let mut responses = Vec::new();
-        for contact in contacts {
-            let addresses = self.repo.find_addresses_by_contact_id(&contact.id).await?;
-            let mut response: ContactResponse = contact.into();
-            response.addresses = addresses.into_iter().map(Into::into).collect();
-            responses.push(response);
-        }
+    // Moves the contact to the trash; see `restore_contact` and `purge_contact`.
+    pub async fn delete_contact(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        if_match: Option<&[i32]>,
+    ) -> Result<(), DomainError> {
+        let contact = self.find_contact(tenant, contact_id, Access::Own).await?;
+        check_version(&contact, if_match)?;
+        self.repo.trash_contact(&tenant, &contact.id, contact.version, Utc::now()).await
+    }
 
-        Ok(responses)
+    pub async fn list_trash(&self, tenant: Tenant) -> Result<Vec<ContactResponse>, DomainError> {
+        let contacts = self.repo.find_trashed_contacts(&tenant).await?;
+        self.with_relations(tenant, contacts).await
     }
 
-    pub async fn get_contact(&self, user_id: Uuid, contact_id: Uuid) -> Result<ContactResponse, String> {
-        let contact = self
-            .repo
-            .find_contact_by_id(&contact_id)
-            .await?
-            .ok_or("Contact not found")?;
+    pub async fn list_shared_contacts(&self, user_id: Uuid) -> Result<Vec<SharedContactResponse>, DomainError> {
+        let shared = self.share_repo.find_shared_contacts(&user_id).await?;
+        let grants: Vec<(SharePermission, Uuid)> =
+            shared.iter().map(|s| (s.permission, s.contact.user_id)).collect();
+        let contacts = self.with_relations(Tenant::personal(user_id), shared.into_iter().map(|s| s.contact).collect()).await?;
+        Ok(contacts
+            .into_iter()
+            .zip(grants)
+            .map(|(contact, (permission, shared_by))| SharedContactResponse { contact, permission, shared_by })
+            .collect())
+    }
 
-        if contact.user_id != user_id {
-            return Err("Unauthorized".to_string());
-        }
+    pub async fn restore_contact(&self, tenant: Tenant, contact_id: Uuid) -> Result<ContactResponse, DomainError> {
+        let contact = self.find_trashed_contact(tenant, contact_id).await?;
+        let restored = self.repo.restore_contact(&tenant, &contact.id).await?;
+        let mut responses = self.with_relations(tenant, vec![restored]).await?;
+        Ok(responses.remove(0))
+    }
 
-        let addresses = self.repo.find_addresses_by_contact_id(&contact.id).await?;
-        let mut response: ContactResponse = contact.into();
-        response.addresses = addresses.into_iter().map(Into::into).collect();
+    // Only trashed contacts can be purged, so a purge is always preceded by a
+    // recoverable delete.
+    pub async fn purge_contact(&self, tenant: Tenant, contact_id: Uuid) -> Result<(), DomainError> {
+        let contact = self.find_trashed_contact(tenant, contact_id).await?;
+        self.repo.delete_contact(&tenant, &contact.id).await
+    }
 
-        Ok(response)
+    pub async fn purge_expired_trash(&self, retention: Duration) -> Result<u64, DomainError> {
+        self.repo.purge_trashed_contacts(Utc::now() - retention).await
     }
 
-    pub async fn create_address(
+    pub async fn search_contacts(
         &self,
-        user_id: Uuid,
-        contact_id: Uuid,
-        req: CreateAddressRequest,
-    ) -> Result<AddressResponse, String> {
-        req.validate().map_err(|e| e.to_string())?;
+        tenant: Tenant,
+        req: ContactListQuery,
+    ) -> Result<ContactListResponse, DomainError> {
+        req.validate()?;
 
-        let contact = self
-            .repo
-            .find_contact_by_id(&contact_id)
-            .await?
-            .ok_or("Contact not found")?;
+        if req.offset.is_some() && req.cursor.is_some() {
+            return Err(DomainError::invalid_field("cursor", "Cannot combine cursor with offset"));
+        }
 
-        // Verify ownership
-        if contact.user_id != user_id {
-            return Err("Unauthorized".to_string());
+        let non_empty = |v: Option<String>| v.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        if let Some(text) = non_empty(req.q.clone()) {
+            return self.search_contacts_by_text(tenant, text, req).await;
         }
 
-        let new_address = Address {
-            id: Uuid::new_v4(),
-            contact_id,
-            street: req.street,
-            city: req.city,
-            province: req.province,
-            country: req.country,
-            postal_code: req.postal_code,
-            created_at: Utc::now(),
-            updated_at: Utc::now(),
+        let sort = req.sort.unwrap_or_default();
+        let cursor = match req.cursor.as_deref() {
+            Some(encoded) => Some(decode_cursor(encoded, sort)?),
+            None => None,
         };
 
-        let created_address = self.repo.create_address(&new_address).await?;
-        Ok(created_address.into())
+        let query = ContactQuery {
+            name: non_empty(req.name),
+            email: non_empty(req.email),
+            phone: self.phone_filter(tenant.user_id, non_empty(req.phone)).await?,
+            tag: non_empty(req.tag),
+            group: req.group,
+            custom_fields: self.custom_field_filter(tenant.user_id, req.custom_fields).await?,
+            sort,
+            direction: req.order.unwrap_or_default(),
+            limit: req.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            offset: req.offset.unwrap_or(0),
+            cursor,
+        };
+
+        let page = self.repo.search_contacts(&tenant, &query).await?;
+        let responses = self.with_relations(tenant, page.contacts).await?;
+
+        Ok(ContactListResponse {
+            data: responses,
+            total: page.total,
+            limit: query.limit,
+            offset: query.cursor.is_none().then_some(query.offset),
+            next_cursor: page.next_cursor.as_ref().map(encode_cursor),
+        })
     }
-    
-    // Additional Update/Delete Address methods can be added similarly
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::domain::entity::contact_entity::Contact;
-    use crate::domain::repository::contact_repository::MockContactRepository;
+    async fn search_contacts_by_text(
+        &self,
+        tenant: Tenant,
+        text: String,
+        req: ContactListQuery,
+    ) -> Result<ContactListResponse, DomainError> {
+        if req.cursor.is_some() {
+            return Err(DomainError::invalid_field("cursor", "Cursor pagination is not supported with q"));
+        }
+        if req.sort.is_some() || req.order.is_some() {
+            return Err(DomainError::invalid_field("sort", "Search results are ordered by relevance"));
+        }
 
-    #[tokio::test]
-    async fn test_create_contact_success() {
-        let mut mock_repo = MockContactRepository::new();
-        let user_id = Uuid::new_v4();
+        let non_empty = |v: Option<String>| v.map(|v| v.trim().to_string()).filter(|v| !v.is_empty());
+        let query = ContactQuery {
+            name: non_empty(req.name),
+            email: non_empty(req.email),
+            phone: self.phone_filter(tenant.user_id, non_empty(req.phone)).await?,
+            tag: non_empty(req.tag),
+            group: req.group,
+            custom_fields: self.custom_field_filter(tenant.user_id, req.custom_fields).await?,
+            limit: req.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            offset: req.offset.unwrap_or(0),
+            ..Default::default()
+        };
 
-        mock_repo
-            .expect_create_contact()
-            .times(1)
-            .returning(|c| Ok(c.clone()));
+        let page = self.repo.search_contacts_by_text(&tenant, &text, &query).await?;
+        let (contacts, matches): (Vec<Contact>, Vec<SearchMatch>) = page
+            .hits
+            .into_iter()
+            .map(|hit| (hit.contact, SearchMatch { rank: hit.rank, snippet: hit.snippet }))
+            .unzip();
 
-        let usecase = ContactUsecase::new(Arc::new(mock_repo));
+        let mut responses = self.with_relations(tenant, contacts).await?;
+        for (response, search) in responses.iter_mut().zip(matches) {
+            response.search = Some(search);
+        }
 
-        let req = CreateContactRequest {
-            first_name: "John".to_string(),
-            last_name: Some("Doe".to_string()),
-            email: Some("john@example.com".to_string()),
-            phone: Some("123456789".to_string()),
-        };
+        Ok(ContactListResponse {
+            data: responses,
+            total: page.total,
+            limit: query.limit,
+            offset: Some(query.offset),
+            next_cursor: None,
+        })
+    }
 
-        let result = usecase.create_contact(user_id, req).await;
-        assert!(result.is_ok());
-        let contact = result.unwrap();
-        assert_eq!(contact.first_name, "John");
+    // Loads the addresses, emails, phones and tags for a whole page of
+    // contacts with one query each.
+    async fn with_relations(&self, tenant: Tenant, contacts: Vec<Contact>) -> Result<Vec<ContactResponse>, DomainError> {
+        if contacts.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let ids: Vec<Uuid> = contacts.iter().map(|c| c.id).collect();
+        let mut addresses_by_contact: HashMap<Uuid, Vec<AddressResponse>> = HashMap::new();
+        for address in self.repo.find_addresses_by_contact_ids(&tenant, &ids).await? {
+            addresses_by_contact.entry(address.contact_id).or_default().push(address.into());
+        }
+        let mut emails_by_contact: HashMap<Uuid, Vec<EmailResponse>> = HashMap::new();
+        for email in self.repo.find_emails_by_contact_ids(&tenant, &ids).await? {
+            emails_by_contact.entry(email.contact_id).or_default().push(email.into());
+        }
+        let mut phones_by_contact: HashMap<Uuid, Vec<PhoneResponse>> = HashMap::new();
+        for phone in self.repo.find_phones_by_contact_ids(&tenant, &ids).await? {
+            phones_by_contact.entry(phone.contact_id).or_default().push(phone.into());
+        }
+        let mut tags_by_contact: HashMap<Uuid, Vec<String>> = HashMap::new();
+        for contact_tag in self.tag_repo.find_tags_by_contact_ids(&ids).await? {
+            tags_by_contact.entry(contact_tag.contact_id).or_default().push(contact_tag.tag.name);
+        }
+
+        Ok(contacts
+            .into_iter()
+            .map(|contact| {
+                let addresses = addresses_by_contact.remove(&contact.id).unwrap_or_default();
+                let emails = emails_by_contact.remove(&contact.id).unwrap_or_default();
+                let phones = phones_by_contact.remove(&contact.id).unwrap_or_default();
+                let tags = tags_by_contact.remove(&contact.id).unwrap_or_default();
+                let mut response: ContactResponse = contact.into();
+                response.addresses = addresses;
+                response.emails = emails;
+                response.phones = phones;
+                response.tags = tags;
+                response
+            })
+            .collect())
     }
 
-    #[tokio::test]
-    async fn test_get_contact_unauthorized() {
-        let mut mock_repo = MockContactRepository::new();
-        let user_id = Uuid::new_v4();
-        let other_user_id = Uuid::new_v4();
-        let contact_id = Uuid::new_v4();
+    pub async fn export_vcard(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        version: VCardVersion,
+    ) -> Result<String, DomainError> {
+        let contact = self.find_contact(tenant, contact_id, Access::Read).await?;
+        let addresses = self.repo.find_addresses_by_contact_id(&tenant, &contact.id).await?;
+        let emails = self.repo.find_emails_by_contact_id(&tenant, &contact.id).await?;
+        let phones = self.repo.find_phones_by_contact_id(&tenant, &contact.id).await?;
 
-        mock_repo
-            .expect_find_contact_by_id()
-            .with(mockall::predicate::eq(contact_id))
-            .times(1)
-            .returning(move |_| Ok(Some(Contact {
-                id: Uuid::new_v4(),
-                user_id: other_user_id, // Different user
-                first_name: "Jane".to_string(),
-                last_name: None,
-                email: None,
-                phone: None,
-                created_at: Utc::now(),
-                updated_at: Utc::now(),
-            })));
+        let mut out = String::new();
+        vcard::write_vcard(&mut out, version, &contact, &addresses, &emails, &phones);
+        Ok(out)
+    }
+
+    pub async fn export_vcards(&self, tenant: Tenant, version: VCardVersion) -> Result<String, DomainError> {
+        let contacts = self.repo.find_contacts(&tenant).await?;
+        let ids: Vec<Uuid> = contacts.iter().map(|c| c.id).collect();
+        let mut addresses_by_contact: HashMap<Uuid, Vec<Address>> = HashMap::new();
+        let mut emails_by_contact: HashMap<Uuid, Vec<ContactEmail>> = HashMap::new();
+        let mut phones_by_contact: HashMap<Uuid, Vec<ContactPhone>> = HashMap::new();
+        if !ids.is_empty() {
+            for address in self.repo.find_addresses_by_contact_ids(&tenant, &ids).await? {
+                addresses_by_contact.entry(address.contact_id).or_default().push(address);
+            }
+            for email in self.repo.find_emails_by_contact_ids(&tenant, &ids).await? {
+                emails_by_contact.entry(email.contact_id).or_default().push(email);
+            }
+            for phone in self.repo.find_phones_by_contact_ids(&tenant, &ids).await? {
+                phones_by_contact.entry(phone.contact_id).or_default().push(phone);
+            }
+        }
+
+        let mut out = String::new();
+        for contact in &contacts {
+            let addresses = addresses_by_contact.remove(&contact.id).unwrap_or_default();
+            let emails = emails_by_contact.remove(&contact.id).unwrap_or_default();
+            let phones = phones_by_contact.remove(&contact.id).unwrap_or_default();
+            vcard::write_vcard(&mut out, version, contact, &addresses, &emails, &phones);
+        }
+        Ok(out)
+    }
+
+    pub async fn import_vcards(&self, tenant: Tenant, input: &str) -> Result<ImportReport, DomainError> {
+        let mut report = ImportReport::default();
+        for (i, card) in vcard::parse_vcards(input).into_iter().enumerate() {
+            let result = match card {
+                Ok(card) => {
+                    let (contact, addresses) = vcard_requests(card);
+                    self.import_contact(tenant, contact, addresses).await.map(Some)
+                }
+                Err(e) => Err(DomainError::validation(e)),
+            };
+            report.push(i + 1, result);
+        }
+        Ok(report)
+    }
+
+    pub async fn import_csv(
+        &self,
+        tenant: Tenant,
+        input: &[u8],
+        options: &CsvImportOptions,
+    ) -> Result<ImportReport, DomainError> {
+        let mut report = ImportReport {
+            dry_run: options.dry_run,
+            ..Default::default()
+        };
+        for (i, row) in contact_csv::read_contacts(input, options)?.into_iter().enumerate() {
+            let result = match row {
+                Ok(row) => {
+                    let (contact, addresses) = csv_requests(row);
+                    if options.dry_run {
+                        match self.validate_import(tenant.user_id, &contact, &addresses).await {
+                            Ok(()) => self.checked_custom_fields(tenant.user_id, contact.custom_fields).await.map(|_| None),
+                            Err(e) => Err(e),
+                        }
+                    } else {
+                        self.import_contact(tenant, contact, addresses).await.map(Some)
+                    }
+                }
+                Err(e) => Err(DomainError::validation(e)),
+            };
+            report.push(i + 1, result);
+        }
+        Ok(report)
+    }
+
+    // Streams the header, then one chunk per page of contacts, so large
+    // address books are never held in memory at once.
+    pub fn export_csv(&self, tenant: Tenant) -> impl Stream<Item = Result<Vec<u8>, DomainError>> + Send + 'static {
+        let repo = self.repo.clone();
+        let pages = stream::try_unfold(Some(None), move |cursor: Option<Option<ContactCursor>>| {
+            let repo = repo.clone();
+            async move {
+                let Some(cursor) = cursor else {
+                    return Ok(None);
+                };
+                let query = ContactQuery {
+                    limit: CSV_EXPORT_PAGE_SIZE,
+                    cursor,
+                    ..Default::default()
+                };
+                let page = repo.search_contacts(&tenant, &query).await?;
 
-        let usecase = ContactUsecase::new(Arc::new(mock_repo));
+                let ids: Vec<Uuid> = page.contacts.iter().map(|c| c.id).collect();
+                let mut primary_address: HashMap<Uuid, Address> = HashMap::new();
+                if !ids.is_empty() {
+                    for address in repo.find_addresses_by_contact_ids(&tenant, &ids).await? {
+                        primary_address.entry(address.contact_id).or_insert(address);
+                    }
+                }
 
-        let result = usecase.get_contact(user_id, contact_id).await;
-        assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), "Unauthorized");
+                let mut chunk = Vec::new();
+                for contact in &page.contacts {
+                    contact_csv::write_contact(&mut chunk, contact, primary_address.get(&contact.id));
+                }
+                Ok(Some((chunk, page.next_cursor.map(Some))))
+            }
+        });
+
+        stream::once(async { Ok(contact_csv::write_header()) })
+            .chain(pages)
+            .try_filter(|chunk| futures::future::ready(!chunk.is_empty()))
+    }
+
+    // Validates the contact and all its addresses up front, to report every
+    // invalid field of an item at once.
+    async fn import_contact(
+        &self,
+        tenant: Tenant,
+        contact: CreateContactRequest,
+        addresses: Vec<CreateAddressRequest>,
+    ) -> Result<Uuid, DomainError> {
+        self.validate_import(tenant.user_id, &contact, &addresses).await?;
+
+        let (created, _) = self.insert_contact(tenant, contact, addresses).await?;
+        Ok(created.id)
+    }
+
+    // Validates a contact, its phone numbers (in the user's region) and all
+    // its addresses, keying list errors by position (`addresses[0].country`).
+    async fn validate_import(
+        &self,
+        user_id: Uuid,
+        contact: &CreateContactRequest,
+        addresses: &[CreateAddressRequest],
+    ) -> Result<(), DomainError> {
+        let mut fields = FieldErrors::new();
+        if let Err(DomainError::Validation { fields: errors, .. }) = contact.validate().map_err(DomainError::from) {
+            fields.extend(errors);
+        }
+
+        let phones = contact
+            .phone
+            .iter()
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| ("phone".to_string(), p))
+            .chain(contact.phones.iter().enumerate().map(|(i, p)| (format!("phones[{}].phone", i), &p.phone)));
+        for (field, raw) in phones {
+            if fields.contains_key(&field) {
+                continue;
+            }
+            if let Err(DomainError::Validation { fields: errors, .. }) = self.normalize_phone(user_id, raw).await {
+                fields.extend(errors.into_values().map(|messages| (field.clone(), messages)));
+            }
+        }
+
+        for (i, address) in addresses.iter().enumerate() {
+            let checked = address
+                .validate()
+                .map_err(DomainError::from)
+                .and_then(|_| checked_location(&address.country, address.postal_code.clone()));
+            if let Err(DomainError::Validation { fields: errors, .. }) = checked {
+                for (field, messages) in errors {
+                    fields.insert(format!("addresses[{}].{}", i, field), messages);
+                }
+            }
+        }
+        if !fields.is_empty() {
+            return Err(DomainError::Validation {
+                message: "Request validation failed".to_string(),
+                fields,
+            });
+        }
+        Ok(())
+    }
+
+    pub async fn find_duplicates(&self, tenant: Tenant) -> Result<Vec<DuplicateGroupResponse>, DomainError> {
+        let contacts = self.repo.find_contacts(&tenant).await?;
+        let clusters = duplicates::find_duplicates(&contacts);
+
+        let grouped: Vec<Contact> = clusters
+            .iter()
+            .flat_map(|cluster| cluster.members.iter().map(|&i| contacts[i].clone()))
+            .collect();
+        let mut responses = self.with_relations(tenant, grouped).await?.into_iter();
+
+        Ok(clusters
+            .into_iter()
+            .map(|cluster| DuplicateGroupResponse {
+                contacts: responses.by_ref().take(cluster.members.len()).collect(),
+                confidence: cluster.confidence,
+                reasons: cluster.reasons,
+            })
+            .collect())
+    }
+
+    pub async fn merge_contacts(
+        &self,
+        tenant: Tenant,
+        req: MergeContactsRequest,
+        if_match: Option<&[i32]>,
+    ) -> Result<ContactResponse, DomainError> {
+        req.validate()?;
+        if req.source_ids.contains(&req.target_id) {
+            return Err(DomainError::invalid_field("source_ids", "The target cannot also be a source"));
+        }
+        if req.source_ids.iter().collect::<HashSet<_>>().len() != req.source_ids.len() {
+            return Err(DomainError::invalid_field("source_ids", "Source contacts must be distinct"));
+        }
+
+        let mut target = self.find_contact(tenant, req.target_id, Access::Own).await?;
+        check_version(&target, if_match)?;
+        let mut sources = Vec::with_capacity(req.source_ids.len());
+        for source_id in &req.source_ids {
+            sources.push(self.find_contact(tenant, *source_id, Access::Own).await?);
+        }
+
+        let mut custom_fields = target.custom_fields.0.clone();
+        for source in &sources {
+            if target.last_name.is_none() {
+                target.last_name = source.last_name.clone();
+            }
+            for (name, value) in &source.custom_fields.0 {
+                custom_fields.entry(name.clone()).or_insert_with(|| value.clone());
+            }
+        }
+        // A source's values may predate the current definitions.
+        target.custom_fields = self.checked_custom_fields(target.user_id, custom_fields).await?;
+        target.updated_at = Utc::now();
+
+        let merged = self.repo.merge_contacts(&tenant, &target, &sources).await?;
+        let mut responses = self.with_relations(tenant, vec![merged]).await?;
+        Ok(responses.remove(0))
+    }
+
+    pub async fn get_contact(&self, tenant: Tenant, contact_id: Uuid) -> Result<ContactResponse, DomainError> {
+        let contact = self.find_contact(tenant, contact_id, Access::Read).await?;
+
+        let mut responses = self.with_relations(tenant, vec![contact]).await?;
+        Ok(responses.remove(0))
+    }
+
+    pub async fn create_address(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        req: CreateAddressRequest,
+    ) -> Result<AddressResponse, DomainError> {
+        req.validate()?;
+
+        let new_address = new_address(contact_id, req, Utc::now())?;
+
+        let contact = self.find_contact(tenant, contact_id, Access::Edit).await?;
+        let changes = ContactChanges {
+            addresses: vec![new_address.clone()],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::AddressCreated).await?;
+
+        // Re-read: the address may just have been promoted.
+        let (_, created_address) = self.find_address(tenant, contact_id, new_address.id, Access::Read).await?;
+        Ok(created_address.into())
+    }
+
+    pub async fn list_addresses(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+    ) -> Result<Vec<AddressResponse>, DomainError> {
+        let contact = self.find_contact(tenant, contact_id, Access::Read).await?;
+
+        let addresses = self.repo.find_addresses_by_contact_id(&tenant, &contact.id).await?;
+        Ok(addresses.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn get_address(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        address_id: Uuid,
+    ) -> Result<AddressResponse, DomainError> {
+        let (_, address) = self.find_address(tenant, contact_id, address_id, Access::Read).await?;
+        Ok(address.into())
+    }
+
+    // PUT semantics: every field is replaced, omitted optional fields are
+    // cleared. The primary address stays primary until another one is made
+    // primary.
+    pub async fn replace_address(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        address_id: Uuid,
+        req: CreateAddressRequest,
+    ) -> Result<AddressResponse, DomainError> {
+        req.validate()?;
+        let (country, postal_code) = checked_location(&req.country, req.postal_code)?;
+
+        let (contact, mut address) = self.find_address(tenant, contact_id, address_id, Access::Edit).await?;
+        address.street = req.street;
+        address.city = req.city;
+        address.province = req.province;
+        address.country = country;
+        address.postal_code = postal_code;
+        address.kind = req.kind;
+        address.is_primary |= req.primary;
+        address.updated_at = Utc::now();
+
+        let changes = ContactChanges {
+            addresses: vec![address.clone()],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::AddressUpdated).await?;
+
+        let (_, updated_address) = self.find_address(tenant, contact_id, address.id, Access::Read).await?;
+        Ok(updated_address.into())
+    }
+
+    // PATCH semantics: only fields present in the request are changed.
+    pub async fn update_address(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        address_id: Uuid,
+        req: UpdateAddressRequest,
+    ) -> Result<AddressResponse, DomainError> {
+        req.validate()?;
+
+        let (contact, mut address) = self.find_address(tenant, contact_id, address_id, Access::Edit).await?;
+        if let Some(street) = req.street {
+            address.street = Some(street);
+        }
+        if let Some(city) = req.city {
+            address.city = Some(city);
+        }
+        if let Some(province) = req.province {
+            address.province = Some(province);
+        }
+        if let Some(country) = req.country {
+            address.country = country;
+        }
+        if let Some(postal_code) = req.postal_code {
+            address.postal_code = Some(postal_code);
+        }
+        if let Some(kind) = req.kind {
+            address.kind = kind;
+        }
+        match req.primary {
+            Some(true) => address.is_primary = true,
+            Some(false) if address.is_primary => {
+                return Err(DomainError::invalid_field("primary", "Make another address primary instead"));
+            }
+            _ => {}
+        }
+        // The postal code is checked against the resulting country, so
+        // changing only one of them can fail.
+        (address.country, address.postal_code) = checked_location(&address.country, address.postal_code.take())?;
+        address.updated_at = Utc::now();
+
+        let changes = ContactChanges {
+            addresses: vec![address.clone()],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::AddressUpdated).await?;
+
+        let (_, updated_address) = self.find_address(tenant, contact_id, address.id, Access::Read).await?;
+        Ok(updated_address.into())
+    }
+
+    pub async fn delete_address(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        address_id: Uuid,
+    ) -> Result<(), DomainError> {
+        let (contact, address) = self.find_address(tenant, contact_id, address_id, Access::Edit).await?;
+        let changes = ContactChanges {
+            deleted_addresses: vec![address.id],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::AddressDeleted).await
+    }
+
+    pub async fn create_email(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        req: CreateEmailRequest,
+    ) -> Result<EmailResponse, DomainError> {
+        req.validate()?;
+
+        let contact = self.find_contact(tenant, contact_id, Access::Edit).await?;
+
+        let new_email = ContactEmail {
+            id: Uuid::new_v4(),
+            contact_id,
+            email: req.email,
+            label: req.label,
+            is_primary: req.primary,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let changes = ContactChanges {
+            emails: vec![new_email.clone()],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::EmailCreated).await?;
+
+        // Re-read: the email may just have been promoted.
+        let (_, created_email) = self.find_email(tenant, contact_id, new_email.id, Access::Read).await?;
+        Ok(created_email.into())
+    }
+
+    pub async fn list_emails(&self, tenant: Tenant, contact_id: Uuid) -> Result<Vec<EmailResponse>, DomainError> {
+        let contact = self.find_contact(tenant, contact_id, Access::Read).await?;
+
+        let emails = self.repo.find_emails_by_contact_id(&tenant, &contact.id).await?;
+        Ok(emails.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn get_email(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        email_id: Uuid,
+    ) -> Result<EmailResponse, DomainError> {
+        let (_, email) = self.find_email(tenant, contact_id, email_id, Access::Read).await?;
+        Ok(email.into())
+    }
+
+    // PATCH semantics, like update_address.
+    pub async fn update_email(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        email_id: Uuid,
+        req: UpdateEmailRequest,
+    ) -> Result<EmailResponse, DomainError> {
+        req.validate()?;
+
+        let (contact, mut email) = self.find_email(tenant, contact_id, email_id, Access::Edit).await?;
+        if let Some(address) = req.email {
+            email.email = address;
+        }
+        if let Some(label) = req.label {
+            email.label = label;
+        }
+        match req.primary {
+            Some(true) => email.is_primary = true,
+            Some(false) if email.is_primary => {
+                return Err(DomainError::invalid_field("primary", "Make another email primary instead"));
+            }
+            _ => {}
+        }
+        email.updated_at = Utc::now();
+
+        let changes = ContactChanges {
+            emails: vec![email.clone()],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::EmailUpdated).await?;
+
+        let (_, updated_email) = self.find_email(tenant, contact_id, email.id, Access::Read).await?;
+        Ok(updated_email.into())
+    }
+
+    // Deleting the primary email promotes the next oldest one.
+    pub async fn delete_email(&self, tenant: Tenant, contact_id: Uuid, email_id: Uuid) -> Result<(), DomainError> {
+        let (contact, email) = self.find_email(tenant, contact_id, email_id, Access::Edit).await?;
+        let changes = ContactChanges {
+            deleted_emails: vec![email.id],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::EmailDeleted).await
+    }
+
+    pub async fn create_phone(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        req: CreatePhoneRequest,
+    ) -> Result<PhoneResponse, DomainError> {
+        req.validate()?;
+
+        let contact = self.find_contact(tenant, contact_id, Access::Edit).await?;
+        let (phone, phone_raw) = self.normalize_phone(tenant.user_id, &req.phone).await?;
+
+        let new_phone = ContactPhone {
+            id: Uuid::new_v4(),
+            contact_id,
+            phone,
+            phone_raw,
+            label: req.label,
+            is_primary: req.primary,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let changes = ContactChanges {
+            phones: vec![new_phone.clone()],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::PhoneCreated).await?;
+
+        // Re-read: the phone may just have been promoted.
+        let (_, created_phone) = self.find_phone(tenant, contact_id, new_phone.id, Access::Read).await?;
+        Ok(created_phone.into())
+    }
+
+    pub async fn list_phones(&self, tenant: Tenant, contact_id: Uuid) -> Result<Vec<PhoneResponse>, DomainError> {
+        let contact = self.find_contact(tenant, contact_id, Access::Read).await?;
+
+        let phones = self.repo.find_phones_by_contact_id(&tenant, &contact.id).await?;
+        Ok(phones.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn get_phone(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        phone_id: Uuid,
+    ) -> Result<PhoneResponse, DomainError> {
+        let (_, phone) = self.find_phone(tenant, contact_id, phone_id, Access::Read).await?;
+        Ok(phone.into())
+    }
+
+    pub async fn update_phone(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        phone_id: Uuid,
+        req: UpdatePhoneRequest,
+    ) -> Result<PhoneResponse, DomainError> {
+        req.validate()?;
+
+        let (contact, mut phone) = self.find_phone(tenant, contact_id, phone_id, Access::Edit).await?;
+        if let Some(raw) = req.phone {
+            (phone.phone, phone.phone_raw) = self.normalize_phone(tenant.user_id, &raw).await?;
+        }
+        if let Some(label) = req.label {
+            phone.label = label;
+        }
+        match req.primary {
+            Some(true) => phone.is_primary = true,
+            Some(false) if phone.is_primary => {
+                return Err(DomainError::invalid_field("primary", "Make another phone primary instead"));
+            }
+            _ => {}
+        }
+        phone.updated_at = Utc::now();
+
+        let changes = ContactChanges {
+            phones: vec![phone.clone()],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::PhoneUpdated).await?;
+
+        let (_, updated_phone) = self.find_phone(tenant, contact_id, phone.id, Access::Read).await?;
+        Ok(updated_phone.into())
+    }
+
+    pub async fn delete_phone(&self, tenant: Tenant, contact_id: Uuid, phone_id: Uuid) -> Result<(), DomainError> {
+        let (contact, phone) = self.find_phone(tenant, contact_id, phone_id, Access::Edit).await?;
+        let changes = ContactChanges {
+            deleted_phones: vec![phone.id],
+            ..Default::default()
+        };
+        self.save_entries(tenant, contact, &changes, ContactChange::PhoneDeleted).await
+    }
+
+    pub async fn contact_history(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+    ) -> Result<Vec<ContactVersionResponse>, DomainError> {
+        let contact = self.find_any_contact(tenant, contact_id, Access::Read).await?;
+        let versions = self.version_repo.find_versions_by_contact_id(&contact.id).await?;
+
+        let mut previous: Option<&ContactSnapshot> = None;
+        let mut history = Vec::with_capacity(versions.len());
+        for version in &versions {
+            history.push(ContactVersionResponse {
+                version: version.version,
+                action: version.action,
+                actor_id: version.actor_id,
+                created_at: version.created_at,
+                changes: diff_snapshots(previous, &version.snapshot),
+            });
+            previous = Some(&version.snapshot);
+        }
+
+        Ok(history)
+    }
+
+    // Restores the contact fields, addresses, emails and phones recorded in
+    // `version`. The revert itself is recorded as a new version.
+    pub async fn revert_contact(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        version: i32,
+    ) -> Result<ContactResponse, DomainError> {
+        let mut contact = self.find_contact(tenant, contact_id, Access::Edit).await?;
+        let Json(snapshot) = self
+            .version_repo
+            .find_version(&contact.id, version)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Version not found".to_string()))?
+            .snapshot;
+
+        // Versions from before emails and phones were lists only know the
+        // primary ones.
+        let email = match snapshot.emails {
+            Some(_) => PrimaryChange::Keep,
+            None => email_change(&contact, snapshot.email),
+        };
+        let phone = match (snapshot.phones.is_some(), snapshot.phone) {
+            (true, _) => PrimaryChange::Keep,
+            (false, Some(phone)) => PrimaryChange::Set((phone, snapshot.phone_raw)),
+            (false, None) => PrimaryChange::Remove,
+        };
+
+        // Values of fields deleted since cannot come back.
+        let definitions = self.field_repo.find_fields_by_user_id(&contact.user_id).await?;
+        let custom_fields = snapshot
+            .custom_fields
+            .into_iter()
+            .filter(|(name, _)| definitions.iter().any(|d| &d.name == name))
+            .collect();
+
+        let now = Utc::now();
+        let mut changes = self.primary_changes(tenant, &contact, email, phone).await?;
+
+        // Entries deleted since are recreated under their original ids.
+        let current = self.repo.find_addresses_by_contact_id(&tenant, &contact.id).await?;
+        changes.deleted_addresses = current
+            .iter()
+            .filter(|address| !snapshot.addresses.iter().any(|a| a.id == address.id))
+            .map(|address| address.id)
+            .collect();
+        for saved in snapshot.addresses {
+            let existing = current.iter().find(|a| a.id == saved.id);
+            changes.addresses.push(Address {
+                id: saved.id,
+                contact_id: contact.id,
+                street: saved.street,
+                city: saved.city,
+                province: saved.province,
+                country: saved.country,
+                postal_code: saved.postal_code,
+                kind: saved.kind,
+                is_primary: saved.is_primary,
+                created_at: existing.map_or(now, |a| a.created_at),
+                updated_at: now,
+            });
+        }
+
+        if let Some(saved_emails) = snapshot.emails {
+            let current = self.repo.find_emails_by_contact_id(&tenant, &contact.id).await?;
+            changes.deleted_emails = current
+                .iter()
+                .filter(|email| !saved_emails.iter().any(|e| e.id == email.id))
+                .map(|email| email.id)
+                .collect();
+            for saved in saved_emails {
+                let existing = current.iter().find(|e| e.id == saved.id);
+                changes.emails.push(ContactEmail {
+                    id: saved.id,
+                    contact_id: contact.id,
+                    email: saved.email,
+                    label: saved.label,
+                    is_primary: saved.is_primary,
+                    created_at: existing.map_or(now, |e| e.created_at),
+                    updated_at: now,
+                });
+            }
+        }
+        if let Some(saved_phones) = snapshot.phones {
+            let current = self.repo.find_phones_by_contact_id(&tenant, &contact.id).await?;
+            changes.deleted_phones = current
+                .iter()
+                .filter(|phone| !saved_phones.iter().any(|p| p.id == phone.id))
+                .map(|phone| phone.id)
+                .collect();
+            for saved in saved_phones {
+                let existing = current.iter().find(|p| p.id == saved.id);
+                changes.phones.push(ContactPhone {
+                    id: saved.id,
+                    contact_id: contact.id,
+                    phone: saved.phone,
+                    phone_raw: saved.phone_raw,
+                    label: saved.label,
+                    is_primary: saved.is_primary,
+                    created_at: existing.map_or(now, |p| p.created_at),
+                    updated_at: now,
+                });
+            }
+        }
+
+        contact.first_name = snapshot.first_name;
+        contact.last_name = snapshot.last_name;
+        contact.custom_fields = Json(custom_fields);
+        contact.updated_at = now;
+        let contact = self.repo.update_contact(&tenant, &contact, &changes, ContactChange::Reverted).await?;
+        let mut responses = self.with_relations(tenant, vec![contact]).await?;
+        Ok(responses.remove(0))
+    }
+
+    // Writes changes to the contact's addresses, emails or phones. Like any
+    // other edit, they bump the contact's version.
+    async fn save_entries(
+        &self,
+        tenant: Tenant,
+        mut contact: Contact,
+        changes: &ContactChanges,
+        action: ContactChange,
+    ) -> Result<(), DomainError> {
+        contact.updated_at = Utc::now();
+        self.repo.update_contact(&tenant, &contact, changes, action).await?;
+        Ok(())
+    }
+
+    // The E.164 number and the trimmed input, stored together.
+    async fn normalize_phone(&self, user_id: Uuid, raw: &str) -> Result<(String, Option<String>), DomainError> {
+        let raw = raw.trim();
+        let region = self.phone_region(user_id, raw).await?;
+        let phone = phone::to_e164(raw, region.as_deref())?;
+        Ok((phone, Some(raw.to_string())))
+    }
+
+    // Phones of a new contact, from either the `phone` shorthand or the
+    // `phones` list. A blank shorthand means no phone.
+    async fn new_phones(
+        &self,
+        user_id: Uuid,
+        contact_id: Uuid,
+        phone: Option<String>,
+        phones: Vec<CreatePhoneRequest>,
+    ) -> Result<Vec<ContactPhone>, DomainError> {
+        let phone = phone.filter(|p| !p.trim().is_empty());
+        let shorthand = phone.is_some();
+        let requests = match phone {
+            Some(_) if !phones.is_empty() => {
+                return Err(DomainError::invalid_field("phone", "Use either phone or phones, not both"));
+            }
+            Some(phone) => vec![CreatePhoneRequest { phone, label: ContactLabel::Other, primary: true }],
+            None => phones,
+        };
+        let primary = primary_index(requests.iter().map(|r| r.primary), "phones")?;
+
+        let now = Utc::now();
+        let mut entries = Vec::with_capacity(requests.len());
+        for (i, req) in requests.into_iter().enumerate() {
+            let (phone, phone_raw) = self
+                .normalize_phone(user_id, &req.phone)
+                .await
+                .map_err(|e| if shorthand { e } else { prefix_fields(e, &format!("phones[{}]", i)) })?;
+            entries.push(ContactPhone {
+                id: Uuid::new_v4(),
+                contact_id,
+                phone,
+                phone_raw,
+                label: req.label,
+                is_primary: i == primary,
+                created_at: now + Duration::microseconds(i as i64),
+                updated_at: now,
+            });
+        }
+        Ok(entries)
+    }
+
+    // An unchanged raw value keeps the stored number as is, so numbers saved
+    // before normalization existed survive edits to other fields.
+    async fn phone_change(
+        &self,
+        user_id: Uuid,
+        contact: &Contact,
+        raw: Option<String>,
+    ) -> Result<PrimaryChange<(String, Option<String>)>, DomainError> {
+        let raw = raw.map(|r| r.trim().to_string()).filter(|r| !r.is_empty());
+        let Some(raw) = raw else {
+            return Ok(match contact.phone {
+                Some(_) => PrimaryChange::Remove,
+                None => PrimaryChange::Keep,
+            });
+        };
+        if contact.phone.is_some() && contact.phone_raw.as_deref() == Some(raw.as_str()) {
+            return Ok(PrimaryChange::Keep);
+        }
+        Ok(PrimaryChange::Set(self.normalize_phone(user_id, &raw).await?))
+    }
+
+    // The entry changes that make the primary email and phone edits of a
+    // contact update.
+    async fn primary_changes(
+        &self,
+        tenant: Tenant,
+        contact: &Contact,
+        email: PrimaryChange<String>,
+        phone: PrimaryChange<(String, Option<String>)>,
+    ) -> Result<ContactChanges, DomainError> {
+        let mut changes = ContactChanges::default();
+
+        if !matches!(email, PrimaryChange::Keep) {
+            let current = self.repo.find_emails_by_contact_id(&tenant, &contact.id).await?.into_iter().find(|e| e.is_primary);
+            match (email, current) {
+                (PrimaryChange::Set(address), Some(mut current)) => {
+                    current.email = address;
+                    current.updated_at = Utc::now();
+                    changes.emails.push(current);
+                }
+                (PrimaryChange::Set(address), None) => changes.emails.push(ContactEmail {
+                    id: Uuid::new_v4(),
+                    contact_id: contact.id,
+                    email: address,
+                    label: ContactLabel::Other,
+                    is_primary: true,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                }),
+                (PrimaryChange::Remove, Some(current)) => changes.deleted_emails.push(current.id),
+                _ => {}
+            }
+        }
+
+        if !matches!(phone, PrimaryChange::Keep) {
+            let current = self.repo.find_phones_by_contact_id(&tenant, &contact.id).await?.into_iter().find(|p| p.is_primary);
+            match (phone, current) {
+                (PrimaryChange::Set((number, raw)), Some(mut current)) => {
+                    current.phone = number;
+                    current.phone_raw = raw;
+                    current.updated_at = Utc::now();
+                    changes.phones.push(current);
+                }
+                (PrimaryChange::Set((number, raw)), None) => changes.phones.push(ContactPhone {
+                    id: Uuid::new_v4(),
+                    contact_id: contact.id,
+                    phone: number,
+                    phone_raw: raw,
+                    label: ContactLabel::Other,
+                    is_primary: true,
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                }),
+                (PrimaryChange::Remove, Some(current)) => changes.deleted_phones.push(current.id),
+                _ => {}
+            }
+        }
+
+        Ok(changes)
+    }
+
+    async fn phone_filter(&self, user_id: Uuid, raw: Option<String>) -> Result<Option<PhoneFilter>, DomainError> {
+        let Some(raw) = raw else {
+            return Ok(None);
+        };
+        let region = self.phone_region(user_id, &raw).await?;
+        let filter = PhoneFilter {
+            digits: phone::search_digits(&raw, region.as_deref()),
+            legacy_digits: phone::legacy_search_digits(&raw, region.as_deref()),
+        };
+        if filter.digits.is_empty() || filter.legacy_digits.is_empty() {
+            return Err(DomainError::invalid_field("phone", "Phone filter must contain digits"));
+        }
+        Ok(Some(filter))
+    }
+
+    // Only looked up for numbers without a country code.
+    async fn phone_region(&self, user_id: Uuid, raw: &str) -> Result<Option<String>, DomainError> {
+        if raw.trim_start().starts_with('+') {
+            return Ok(None);
+        }
+        let user = self.user_repo.find_user_by_id(&user_id).await?;
+        Ok(user.and_then(|u| u.phone_region))
+    }
+
+    // The complete custom field values of a contact, checked against its
+    // owner's current definitions.
+    async fn checked_custom_fields(&self, user_id: Uuid, values: CustomFields) -> Result<Json<CustomFields>, DomainError> {
+        let definitions = self.field_repo.find_fields_by_user_id(&user_id).await?;
+        custom_field_usecase::validate_values(&definitions, values).map(Json)
+    }
+
+    async fn custom_field_filter(&self, user_id: Uuid, filter: Option<String>) -> Result<Option<CustomFields>, DomainError> {
+        let Some(filter) = filter.filter(|f| !f.trim().is_empty()) else {
+            return Ok(None);
+        };
+        let values = match serde_json::from_str(&filter) {
+            Ok(Value::Object(values)) => values,
+            _ => return Err(DomainError::invalid_field("custom_fields", "Expected a JSON object")),
+        };
+        let definitions = self.field_repo.find_fields_by_user_id(&user_id).await?;
+        let values = custom_field_usecase::validate_filter(&definitions, values)?;
+        Ok((!values.is_empty()).then_some(values))
+    }
+
+    // Trashed contacts are hidden everywhere except the trash endpoints.
+    async fn find_contact(&self, tenant: Tenant, contact_id: Uuid, access: Access) -> Result<Contact, DomainError> {
+        let contact = self.find_any_contact(tenant, contact_id, access).await?;
+        if contact.deleted_at.is_some() {
+            return Err(DomainError::NotFound("Contact not found".to_string()));
+        }
+        Ok(contact)
+    }
+
+    async fn find_trashed_contact(&self, tenant: Tenant, contact_id: Uuid) -> Result<Contact, DomainError> {
+        let contact = self.find_any_contact(tenant, contact_id, Access::Own).await?;
+        if contact.deleted_at.is_none() {
+            return Err(DomainError::NotFound("Contact not found in trash".to_string()));
+        }
+        Ok(contact)
+    }
+
+    // Contacts of the tenant allow anything; contacts shared with its user
+    // need a share granting at least `access`, and only allow reading or
+    // editing. Anything else is not found.
+    async fn find_any_contact(&self, tenant: Tenant, contact_id: Uuid, access: Access) -> Result<Contact, DomainError> {
+        let not_found = || DomainError::NotFound("Contact not found".to_string());
+        let contact = self.repo.find_contact_by_id(&tenant, &contact_id).await?.ok_or_else(not_found)?;
+
+        if tenant.owns(&contact) {
+            return Ok(contact);
+        }
+        let permission = self.share_repo.find_permission(&contact.id, &tenant.user_id).await?;
+        match (permission, access) {
+            (None, _) => Err(not_found()),
+            (Some(_), Access::Own) => {
+                Err(DomainError::Forbidden("Only the owner of this contact can do this".to_string()))
+            }
+            (Some(SharePermission::Read), Access::Edit) => {
+                Err(DomainError::Forbidden("This contact is shared with you read-only".to_string()))
+            }
+            _ => Ok(contact),
+        }
+    }
+
+    // Addresses are only reachable through their contact, so access is
+    // checked on the contact and the address must belong to it.
+    async fn find_address(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        address_id: Uuid,
+        access: Access,
+    ) -> Result<(Contact, Address), DomainError> {
+        let contact = self.find_contact(tenant, contact_id, access).await?;
+
+        let address = self
+            .repo
+            .find_address_by_id(&tenant, &address_id)
+            .await?
+            .filter(|a| a.contact_id == contact.id)
+            .ok_or_else(|| DomainError::NotFound("Address not found".to_string()))?;
+
+        Ok((contact, address))
+    }
+
+    async fn find_email(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        email_id: Uuid,
+        access: Access,
+    ) -> Result<(Contact, ContactEmail), DomainError> {
+        let contact = self.find_contact(tenant, contact_id, access).await?;
+
+        let email = self
+            .repo
+            .find_email_by_id(&tenant, &email_id)
+            .await?
+            .filter(|e| e.contact_id == contact.id)
+            .ok_or_else(|| DomainError::NotFound("Email not found".to_string()))?;
+
+        Ok((contact, email))
+    }
+
+    async fn find_phone(
+        &self,
+        tenant: Tenant,
+        contact_id: Uuid,
+        phone_id: Uuid,
+        access: Access,
+    ) -> Result<(Contact, ContactPhone), DomainError> {
+        let contact = self.find_contact(tenant, contact_id, access).await?;
+
+        let phone = self
+            .repo
+            .find_phone_by_id(&tenant, &phone_id)
+            .await?
+            .filter(|p| p.contact_id == contact.id)
+            .ok_or_else(|| DomainError::NotFound("Phone not found".to_string()))?;
+
+        Ok((contact, phone))
+    }
+}
+
+// Periodically purges contacts that have been in the trash longer than
+// `retention`.
+pub fn spawn_trash_purge(usecase: Arc<ContactUsecase>, retention: Duration, interval: std::time::Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            match usecase.purge_expired_trash(retention).await {
+                Ok(0) => {}
+                Ok(purged) => tracing::info!("purged {} contacts from the trash", purged),
+                Err(e) => tracing::warn!("failed to purge trashed contacts: {}", e),
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entity::contact_entity::Contact;
+    use crate::domain::repository::contact_repository::MockContactRepository;
+    use crate::domain::repository::contact_version_repository::MockContactVersionRepository;
+    use crate::domain::repository::custom_field_repository::MockCustomFieldRepository;
+    use crate::domain::repository::share_repository::MockShareRepository;
+    use crate::domain::repository::tag_repository::MockTagRepository;
+    use crate::domain::repository::user_repository::MockUserRepository;
+
+    // A live personal contact; tests override what they need with
+    // struct-update syntax.
+    fn contact_fixture() -> Contact {
+        Contact {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            organization_id: None,
+            first_name: "Jane".to_string(),
+            last_name: None,
+            email: None,
+            phone: None,
+            phone_raw: None,
+            custom_fields: Default::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_contact_success() {
+        let mut mock_repo = MockContactRepository::new();
+        let user_id = Uuid::new_v4();
+
+        // The contact and its entries are saved, and recorded in its
+        // history, in one call.
+        mock_repo
+            .expect_create_contact()
+            .withf(move |tenant, contact, changes| {
+                tenant.user_id == user_id
+                    && contact.first_name == "John"
+                    && changes.emails.len() == 1
+                    && changes.emails[0].email == "john@example.com"
+                    && changes.emails[0].is_primary
+                    && changes.phones.len() == 1
+                    && changes.phones[0].phone == "+6281234567890"
+                    && changes.phones[0].is_primary
+            })
+            .times(1)
+            .returning(|_, c, changes| Ok(Contact {
+                email: Some(changes.emails[0].email.clone()),
+                phone: Some(changes.phones[0].phone.clone()),
+                phone_raw: changes.phones[0].phone_raw.clone(),
+                ..c.clone()
+            }));
+        let mut field_repo = MockCustomFieldRepository::new();
+        field_repo
+            .expect_find_fields_by_user_id()
+            .times(1)
+            .returning(|_| Ok(vec![]));
+
+        let usecase = ContactUsecase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockTagRepository::new()),
+            Arc::new(MockContactVersionRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(field_repo),
+            Arc::new(MockShareRepository::new()),
+        );
+
+        let req = CreateContactRequest {
+            first_name: "John".to_string(),
+            last_name: Some("Doe".to_string()),
+            email: Some("john@example.com".to_string()),
+            phone: Some("+62 812 3456 7890".to_string()),
+            ..Default::default()
+        };
+
+        let result = usecase.create_contact(Tenant::personal(user_id), req).await;
+        assert!(result.is_ok());
+        let contact = result.unwrap();
+        assert_eq!(contact.first_name, "John");
+        assert_eq!(contact.phone.as_deref(), Some("+6281234567890"));
+        assert_eq!(contact.phone_raw.as_deref(), Some("+62 812 3456 7890"));
+        assert_eq!(contact.emails.len(), 1);
+        assert_eq!(contact.phones[0].label, ContactLabel::Other);
+        assert!(contact.phones[0].primary);
+    }
+
+    #[tokio::test]
+    async fn test_create_contact_rejects_two_primary_emails() {
+        let usecase = ContactUsecase::new(
+            Arc::new(MockContactRepository::new()),
+            Arc::new(MockTagRepository::new()),
+            Arc::new(MockContactVersionRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockCustomFieldRepository::new()),
+            Arc::new(MockShareRepository::new()),
+        );
+        let email = |address: &str| CreateEmailRequest {
+            email: address.to_string(),
+            label: ContactLabel::Work,
+            primary: true,
+        };
+
+        let req = CreateContactRequest {
+            first_name: "John".to_string(),
+            emails: vec![email("john@work.com"), email("john@home.com")],
+            ..Default::default()
+        };
+        match usecase.create_contact(Tenant::personal(Uuid::new_v4()), req).await {
+            Err(DomainError::Validation { fields, .. }) => {
+                assert_eq!(fields["emails"], vec!["Only one entry can be primary"]);
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_get_unshared_contact_is_not_found() {
+        let mut mock_repo = MockContactRepository::new();
+        let user_id = Uuid::new_v4();
+        let other_user_id = Uuid::new_v4();
+        let contact_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_find_contact_by_id()
+            .withf(move |_, id| *id == contact_id)
+            .times(1)
+            .returning(move |_, _| Ok(Some(Contact { user_id: other_user_id, ..contact_fixture() }))); // Different user
+
+        // Not shared with the caller either.
+        let mut share_repo = MockShareRepository::new();
+        share_repo
+            .expect_find_permission()
+            .withf(move |_, grantee_id| *grantee_id == user_id)
+            .times(1)
+            .returning(|_, _| Ok(None));
+
+        let usecase = ContactUsecase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockTagRepository::new()),
+            Arc::new(MockContactVersionRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockCustomFieldRepository::new()),
+            Arc::new(share_repo),
+        );
+
+        let result = usecase.get_contact(Tenant::personal(user_id), contact_id).await;
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_create_contact_reports_invalid_fields() {
+        let usecase = ContactUsecase::new(
+            Arc::new(MockContactRepository::new()),
+            Arc::new(MockTagRepository::new()),
+            Arc::new(MockContactVersionRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockCustomFieldRepository::new()),
+            Arc::new(MockShareRepository::new()),
+        );
+
+        let req = CreateContactRequest {
+            first_name: "".to_string(),
+            last_name: None,
+            email: Some("not-an-email".to_string()),
+            phone: None,
+            ..Default::default()
+        };
+
+        match usecase.create_contact(Tenant::personal(Uuid::new_v4()), req).await {
+            Err(DomainError::Validation { fields, .. }) => {
+                assert_eq!(fields["first_name"], vec!["First name is required"]);
+                assert_eq!(fields["email"], vec!["Invalid email format"]);
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_search_contacts_batches_relation_loading() {
+        use crate::domain::entity::tag_entity::{ContactTag, Tag};
+        use crate::domain::repository::contact_repository::ContactPage;
+
+        let mut mock_repo = MockContactRepository::new();
+        let user_id = Uuid::new_v4();
+        let contacts: Vec<Contact> = (0..3)
+            .map(|i| Contact { user_id, first_name: format!("Contact {}", i), ..contact_fixture() })
+            .collect();
+        let first_id = contacts[0].id;
+
+        mock_repo
+            .expect_search_contacts()
+            .times(1)
+            .returning(move |_, _| Ok(ContactPage {
+                contacts: contacts.clone(),
+                total: 3,
+                next_cursor: None,
+            }));
+        mock_repo
+            .expect_find_addresses_by_contact_ids()
+            .withf(|_, ids| ids.len() == 3)
+            .times(1)
+            .returning(move |_, _| Ok(vec![Address {
+                id: Uuid::new_v4(),
+                contact_id: first_id,
+                street: None,
+                city: Some("Jakarta".to_string()),
+                province: None,
+                country: "ID".to_string(),
+                postal_code: None,
+                kind: AddressKind::Home,
+                is_primary: true,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }]));
+        mock_repo.expect_find_addresses_by_contact_id().times(0);
+        mock_repo
+            .expect_find_emails_by_contact_ids()
+            .withf(|_, ids| ids.len() == 3)
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+        mock_repo
+            .expect_find_phones_by_contact_ids()
+            .withf(|_, ids| ids.len() == 3)
+            .times(1)
+            .returning(|_, _| Ok(vec![]));
+
+        let mut tag_repo = MockTagRepository::new();
+        tag_repo
+            .expect_find_tags_by_contact_ids()
+            .withf(|ids| ids.len() == 3)
+            .times(1)
+            .returning(move |_| Ok(vec![ContactTag {
+                contact_id: first_id,
+                tag: Tag {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    name: "vip".to_string(),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                },
+            }]));
+
+        let usecase = ContactUsecase::new(
+            Arc::new(mock_repo),
+            Arc::new(tag_repo),
+            Arc::new(MockContactVersionRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockCustomFieldRepository::new()),
+            Arc::new(MockShareRepository::new()),
+        );
+
+        let result = usecase.search_contacts(Tenant::personal(user_id), ContactListQuery::default()).await.unwrap();
+        assert_eq!(result.data.len(), 3);
+        assert_eq!(result.data[0].addresses.len(), 1);
+        assert_eq!(result.data[0].tags, vec!["vip"]);
+        assert!(result.data[1].addresses.is_empty());
+        assert!(result.data[1].tags.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_get_address_of_other_contact_not_found() {
+        let mut mock_repo = MockContactRepository::new();
+        let user_id = Uuid::new_v4();
+        let contact_id = Uuid::new_v4();
+        let address_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_find_contact_by_id()
+            .returning(move |_, _| Ok(Some(Contact { id: contact_id, user_id, ..contact_fixture() })));
+        mock_repo
+            .expect_find_address_by_id()
+            .returning(move |_, _| Ok(Some(Address {
+                id: address_id,
+                contact_id: Uuid::new_v4(), // Belongs to a different contact
+                street: None,
+                city: None,
+                province: None,
+                country: "ID".to_string(),
+                postal_code: None,
+                kind: AddressKind::Other,
+                is_primary: true,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })));
+
+        let usecase = ContactUsecase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockTagRepository::new()),
+            Arc::new(MockContactVersionRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockCustomFieldRepository::new()),
+            Arc::new(MockShareRepository::new()),
+        );
+
+        let result = usecase.get_address(Tenant::personal(user_id), contact_id, address_id).await;
+        assert!(matches!(result, Err(DomainError::NotFound(_))));
+    }
+
+    #[tokio::test]
+    async fn test_purge_expired_trash_uses_retention_cutoff() {
+        let mut mock_repo = MockContactRepository::new();
+        mock_repo
+            .expect_purge_trashed_contacts()
+            .withf(|cutoff| {
+                let expected = Utc::now() - Duration::days(30);
+                (*cutoff - expected).num_seconds().abs() < 5
+            })
+            .times(1)
+            .returning(|_| Ok(2));
+
+        let usecase = ContactUsecase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockTagRepository::new()),
+            Arc::new(MockContactVersionRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockCustomFieldRepository::new()),
+            Arc::new(MockShareRepository::new()),
+        );
+
+        assert_eq!(usecase.purge_expired_trash(Duration::days(30)).await.unwrap(), 2);
+    }
+
+    #[test]
+    fn test_diff_snapshots_reports_field_and_address_changes() {
+        use crate::domain::entity::contact_version_entity::AddressSnapshot;
+
+        let address_id = Uuid::new_v4();
+        let address = |city: &str| AddressSnapshot {
+            id: address_id,
+            street: None,
+            city: Some(city.to_string()),
+            province: None,
+            country: "ID".to_string(),
+            postal_code: None,
+            kind: AddressKind::Home,
+            is_primary: true,
+        };
+        let before = ContactSnapshot {
+            first_name: "Jane".to_string(),
+            last_name: None,
+            email: Some("jane@old.com".to_string()),
+            phone: None,
+            phone_raw: None,
+            addresses: vec![address("Bandung")],
+            emails: None,
+            phones: None,
+            custom_fields: Default::default(),
+        };
+        let after = ContactSnapshot {
+            email: Some("jane@new.com".to_string()),
+            addresses: vec![address("Jakarta")],
+            ..before.clone()
+        };
+
+        let changes = diff_snapshots(Some(&before), &after);
+        let fields: Vec<&str> = changes.iter().map(|c| c.field.as_str()).collect();
+        assert_eq!(fields, vec![format!("addresses[{}].city", address_id).as_str(), "email"]);
+        assert_eq!(changes[1].old, "jane@old.com");
+        assert_eq!(changes[1].new, "jane@new.com");
+
+        // The first version lists every non-null field as added
+        assert_eq!(diff_snapshots(None, &before).len(), 6);
+
+        use crate::domain::entity::contact_version_entity::EmailSnapshot;
+        let email_id = Uuid::new_v4();
+        let no_emails = ContactSnapshot {
+            emails: Some(vec![]),
+            ..before.clone()
+        };
+        let with_email = ContactSnapshot {
+            emails: Some(vec![EmailSnapshot {
+                id: email_id,
+                email: "jane@old.com".to_string(),
+                label: ContactLabel::Home,
+                is_primary: true,
+            }]),
+            ..before.clone()
+        };
+        let changes = diff_snapshots(Some(&no_emails), &with_email);
+        let fields: Vec<String> = changes.iter().map(|c| c.field.clone()).collect();
+        assert_eq!(
+            fields,
+            ["email", "is_primary", "label"].map(|f| format!("emails[{}].{}", email_id, f))
+        );
+        assert_eq!(changes[2].new, "home");
+    }
+
+    #[tokio::test]
+    async fn test_update_contact_with_stale_if_match_fails() {
+        let mut mock_repo = MockContactRepository::new();
+        let user_id = Uuid::new_v4();
+        let contact_id = Uuid::new_v4();
+
+        mock_repo
+            .expect_find_contact_by_id()
+            .returning(move |_, _| Ok(Some(Contact { id: contact_id, user_id, version: 2, ..contact_fixture() })));
+        mock_repo.expect_update_contact().times(0);
+
+        let usecase = ContactUsecase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockTagRepository::new()),
+            Arc::new(MockContactVersionRepository::new()),
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockCustomFieldRepository::new()),
+            Arc::new(MockShareRepository::new()),
+        );
+
+        let req = UpdateContactRequest {
+            first_name: Some("Janet".to_string()),
+            last_name: None,
+            email: None,
+            phone: None,
+            custom_fields: None,
+        };
+        let result = usecase.update_contact(Tenant::personal(user_id), contact_id, req, Some(&[1])).await;
+        assert!(matches!(result, Err(DomainError::PreconditionFailed(_))));
     }
 }
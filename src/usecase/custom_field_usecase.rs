@@ -0,0 +1,316 @@
+use crate::domain::{
+    entity::custom_field_entity::{CustomFieldDefinition, CustomFieldType, CustomFields},
+    error::{DomainError, FieldErrors},
+    repository::custom_field_repository::CustomFieldRepository,
+};
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateCustomFieldRequest {
+    // Key of the value in `custom_fields`: lowercase letters, digits and
+    // underscores, starting with a letter.
+    #[validate(length(min = 1, max = 50, message = "Field name must be between 1 and 50 characters"))]
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: CustomFieldType,
+    #[serde(default)]
+    pub required: bool,
+    // Required for `enum` fields, rejected for the others.
+    #[serde(default)]
+    pub enum_values: Vec<String>,
+}
+
+// The type cannot change once values exist. Stricter rules (`required`,
+// fewer enum values) apply to each contact on its next write.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateCustomFieldRequest {
+    #[validate(length(min = 1, max = 50, message = "Field name must be between 1 and 50 characters"))]
+    pub name: Option<String>,
+    pub required: Option<bool>,
+    pub enum_values: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CustomFieldResponse {
+    pub id: Uuid,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: CustomFieldType,
+    pub required: bool,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub enum_values: Vec<String>,
+}
+
+impl From<CustomFieldDefinition> for CustomFieldResponse {
+    fn from(f: CustomFieldDefinition) -> Self {
+        Self {
+            id: f.id,
+            name: f.name,
+            field_type: f.field_type,
+            required: f.required,
+            enum_values: f.enum_values,
+        }
+    }
+}
+
+fn check_name(name: &str) -> Result<(), DomainError> {
+    let mut chars = name.chars();
+    let valid = chars.next().is_some_and(|c| c.is_ascii_lowercase())
+        && chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+    if !valid {
+        return Err(DomainError::invalid_field(
+            "name",
+            "Use lowercase letters, digits and underscores, starting with a letter",
+        ));
+    }
+    Ok(())
+}
+
+fn check_enum_values(field_type: CustomFieldType, values: &[String]) -> Result<(), DomainError> {
+    let invalid = |message: &str| Err(DomainError::invalid_field("enum_values", message));
+    if field_type != CustomFieldType::Enum {
+        return match values.is_empty() {
+            true => Ok(()),
+            false => invalid("Only enum fields take values"),
+        };
+    }
+    if values.is_empty() {
+        return invalid("Enum fields need at least one value");
+    }
+    if values.iter().any(|v| v.trim().is_empty()) {
+        return invalid("Enum values must not be blank");
+    }
+    if values.iter().collect::<HashSet<_>>().len() != values.len() {
+        return invalid("Enum values must be distinct");
+    }
+    Ok(())
+}
+
+// A single value in stored form, or why it does not fit the definition.
+fn coerce(definition: &CustomFieldDefinition, value: Value) -> Result<Value, String> {
+    match (definition.field_type, value) {
+        (CustomFieldType::Text, value @ Value::String(_)) => Ok(value),
+        (CustomFieldType::Number, value @ Value::Number(_)) => Ok(value),
+        (CustomFieldType::Boolean, value @ Value::Bool(_)) => Ok(value),
+        (CustomFieldType::Date, Value::String(s)) if NaiveDate::parse_from_str(s.trim(), "%Y-%m-%d").is_ok() => {
+            Ok(Value::String(s.trim().to_string()))
+        }
+        (CustomFieldType::Enum, Value::String(s)) if definition.enum_values.contains(&s) => Ok(Value::String(s)),
+        (CustomFieldType::Text, _) => Err("Expected a string".to_string()),
+        (CustomFieldType::Number, _) => Err("Expected a number".to_string()),
+        (CustomFieldType::Boolean, _) => Err("Expected true or false".to_string()),
+        (CustomFieldType::Date, _) => Err("Expected a date (YYYY-MM-DD)".to_string()),
+        (CustomFieldType::Enum, _) => Err(format!("Expected one of: {}", definition.enum_values.join(", "))),
+    }
+}
+
+fn check_values(
+    definitions: &[CustomFieldDefinition],
+    values: CustomFields,
+    fields: &mut FieldErrors,
+) -> CustomFields {
+    let mut stored = CustomFields::new();
+    for (name, value) in values {
+        if value.is_null() {
+            continue;
+        }
+        let key = format!("custom_fields.{}", name);
+        let Some(definition) = definitions.iter().find(|d| d.name == name) else {
+            fields.insert(key, vec!["Unknown custom field".to_string()]);
+            continue;
+        };
+        match coerce(definition, value) {
+            Ok(value) => {
+                stored.insert(name, value);
+            }
+            Err(message) => {
+                fields.insert(key, vec![message]);
+            }
+        }
+    }
+    stored
+}
+
+fn into_result(stored: CustomFields, fields: FieldErrors) -> Result<CustomFields, DomainError> {
+    if !fields.is_empty() {
+        return Err(DomainError::Validation {
+            message: "Request validation failed".to_string(),
+            fields,
+        });
+    }
+    Ok(stored)
+}
+
+// Checks a contact's complete set of values against the owner's
+// definitions and returns them in stored form. Nulls mean "no value".
+pub fn validate_values(definitions: &[CustomFieldDefinition], values: CustomFields) -> Result<CustomFields, DomainError> {
+    let mut fields = FieldErrors::new();
+    let stored = check_values(definitions, values, &mut fields);
+    for definition in definitions.iter().filter(|d| d.required) {
+        if !stored.contains_key(&definition.name) {
+            fields
+                .entry(format!("custom_fields.{}", definition.name))
+                .or_insert_with(|| vec!["Field is required".to_string()]);
+        }
+    }
+    into_result(stored, fields)
+}
+
+// Like validate_values for a listing filter, where every field is optional.
+pub fn validate_filter(definitions: &[CustomFieldDefinition], values: CustomFields) -> Result<CustomFields, DomainError> {
+    let mut fields = FieldErrors::new();
+    let stored = check_values(definitions, values, &mut fields);
+    into_result(stored, fields)
+}
+
+pub struct CustomFieldUsecase {
+    field_repo: Arc<dyn CustomFieldRepository>,
+}
+
+impl CustomFieldUsecase {
+    pub fn new(field_repo: Arc<dyn CustomFieldRepository>) -> Self {
+        Self { field_repo }
+    }
+
+    pub async fn create_field(
+        &self,
+        user_id: Uuid,
+        req: CreateCustomFieldRequest,
+    ) -> Result<CustomFieldResponse, DomainError> {
+        req.validate()?;
+        check_name(&req.name)?;
+        check_enum_values(req.field_type, &req.enum_values)?;
+
+        let new_field = CustomFieldDefinition {
+            id: Uuid::new_v4(),
+            user_id,
+            name: req.name,
+            field_type: req.field_type,
+            required: req.required,
+            enum_values: req.enum_values,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let created_field = self.field_repo.create_field(&new_field).await.map_err(already_exists)?;
+        Ok(created_field.into())
+    }
+
+    pub async fn list_fields(&self, user_id: Uuid) -> Result<Vec<CustomFieldResponse>, DomainError> {
+        let fields = self.field_repo.find_fields_by_user_id(&user_id).await?;
+        Ok(fields.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn get_field(&self, user_id: Uuid, field_id: Uuid) -> Result<CustomFieldResponse, DomainError> {
+        let field = self.find_owned_field(user_id, field_id).await?;
+        Ok(field.into())
+    }
+
+    pub async fn update_field(
+        &self,
+        user_id: Uuid,
+        field_id: Uuid,
+        req: UpdateCustomFieldRequest,
+    ) -> Result<CustomFieldResponse, DomainError> {
+        req.validate()?;
+
+        let mut field = self.find_owned_field(user_id, field_id).await?;
+        if let Some(name) = req.name {
+            check_name(&name)?;
+            field.name = name;
+        }
+        if let Some(required) = req.required {
+            field.required = required;
+        }
+        if let Some(enum_values) = req.enum_values {
+            check_enum_values(field.field_type, &enum_values)?;
+            field.enum_values = enum_values;
+        }
+        field.updated_at = Utc::now();
+
+        let updated_field = self.field_repo.update_field(&field).await.map_err(already_exists)?;
+        Ok(updated_field.into())
+    }
+
+    pub async fn delete_field(&self, user_id: Uuid, field_id: Uuid) -> Result<(), DomainError> {
+        let field = self.find_owned_field(user_id, field_id).await?;
+        self.field_repo.delete_field(&field.id).await
+    }
+
+    async fn find_owned_field(&self, user_id: Uuid, field_id: Uuid) -> Result<CustomFieldDefinition, DomainError> {
+        let field = self
+            .field_repo
+            .find_field_by_id(&field_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Custom field not found".to_string()))?;
+
+        if field.user_id != user_id {
+            return Err(DomainError::Forbidden("You do not have access to this custom field".to_string()));
+        }
+
+        Ok(field)
+    }
+}
+
+fn already_exists(e: DomainError) -> DomainError {
+    match e {
+        DomainError::Conflict(_) => DomainError::Conflict("A custom field with this name already exists".to_string()),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn definition(name: &str, field_type: CustomFieldType, required: bool, enum_values: &[&str]) -> CustomFieldDefinition {
+        CustomFieldDefinition {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            name: name.to_string(),
+            field_type,
+            required,
+            enum_values: enum_values.iter().map(|v| v.to_string()).collect(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_validate_values_checks_types_and_required() {
+        let definitions = [
+            definition("company", CustomFieldType::Text, true, &[]),
+            definition("birthday", CustomFieldType::Date, false, &[]),
+            definition("tier", CustomFieldType::Enum, false, &["gold", "silver"]),
+            definition("seats", CustomFieldType::Number, false, &[]),
+        ];
+        let values = |v: Value| v.as_object().unwrap().clone();
+
+        let stored = validate_values(
+            &definitions,
+            values(json!({"company": "Acme", "birthday": " 1990-02-28 ", "tier": "gold", "seats": null})),
+        )
+        .unwrap();
+        assert_eq!(Value::Object(stored), json!({"company": "Acme", "birthday": "1990-02-28", "tier": "gold"}));
+
+        match validate_values(&definitions, values(json!({"birthday": "28/02/1990", "tier": "bronze", "team": "x"}))) {
+            Err(DomainError::Validation { fields, .. }) => {
+                assert_eq!(fields["custom_fields.company"], vec!["Field is required"]);
+                assert_eq!(fields["custom_fields.birthday"], vec!["Expected a date (YYYY-MM-DD)"]);
+                assert_eq!(fields["custom_fields.tier"], vec!["Expected one of: gold, silver"]);
+                assert_eq!(fields["custom_fields.team"], vec!["Unknown custom field"]);
+            }
+            other => panic!("expected validation error, got {:?}", other),
+        }
+
+        // Filters never require a field.
+        assert!(validate_filter(&definitions, values(json!({"seats": 5}))).is_ok());
+    }
+}
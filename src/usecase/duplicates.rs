@@ -0,0 +1,244 @@
+// Likely-duplicate detection over one user's contacts. Pairs are scored on
+// normalized email, normalized phone and trigram name similarity, then joined
+// into clusters so A~B and B~C are reviewed together.
+use crate::domain::entity::contact_entity::Contact;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+const EMAIL_WEIGHT: f32 = 0.95;
+const PHONE_WEIGHT: f32 = 0.85;
+const NAME_WEIGHT: f32 = 0.8;
+// Name similarity below this does not count as a signal at all.
+const MIN_NAME_SIMILARITY: f32 = 0.5;
+// Pairs scoring below this are not reported.
+const MIN_CONFIDENCE: f32 = 0.4;
+// Phone numbers are compared on their trailing digits so national and
+// international spellings of the same number meet.
+const PHONE_SUFFIX_DIGITS: usize = 9;
+const MIN_PHONE_DIGITS: usize = 7;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateReason {
+    Email,
+    Phone,
+    Name,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DuplicateCluster {
+    // Indexes into the scanned slice, ascending.
+    pub members: Vec<usize>,
+    // Highest pair confidence within the cluster, 0..=1.
+    pub confidence: f32,
+    pub reasons: Vec<DuplicateReason>,
+}
+
+#[derive(Default)]
+struct PairScore {
+    email: bool,
+    phone: bool,
+    name: f32,
+}
+
+impl PairScore {
+    fn confidence(&self) -> f32 {
+        let mut miss = 1.0;
+        if self.email {
+            miss *= 1.0 - EMAIL_WEIGHT;
+        }
+        if self.phone {
+            miss *= 1.0 - PHONE_WEIGHT;
+        }
+        if self.name >= MIN_NAME_SIMILARITY {
+            miss *= 1.0 - NAME_WEIGHT * self.name;
+        }
+        1.0 - miss
+    }
+
+    fn reasons(&self) -> impl Iterator<Item = DuplicateReason> {
+        [
+            (self.email, DuplicateReason::Email),
+            (self.phone, DuplicateReason::Phone),
+            (self.name >= MIN_NAME_SIMILARITY, DuplicateReason::Name),
+        ]
+        .into_iter()
+        .filter_map(|(hit, reason)| hit.then_some(reason))
+    }
+}
+
+// Lower-cased, with any `+tag` suffix of the local part dropped.
+pub fn normalize_email(email: &str) -> Option<String> {
+    let email = email.trim().to_lowercase();
+    let (local, domain) = email.split_once('@')?;
+    let local = local.split('+').next().unwrap_or(local);
+    if local.is_empty() || domain.is_empty() {
+        return None;
+    }
+    Some(format!("{}@{}", local, domain))
+}
+
+// Digits only, keeping the trailing PHONE_SUFFIX_DIGITS.
+pub fn normalize_phone(phone: &str) -> Option<String> {
+    let digits: String = phone.chars().filter(char::is_ascii_digit).collect();
+    if digits.len() < MIN_PHONE_DIGITS {
+        return None;
+    }
+    Some(digits[digits.len().saturating_sub(PHONE_SUFFIX_DIGITS)..].to_string())
+}
+
+// Word trigrams as pg_trgm builds them: each word lower-cased and padded
+// with two leading spaces and one trailing space.
+fn trigrams(contact: &Contact) -> HashSet<String> {
+    let name = format!("{} {}", contact.first_name, contact.last_name.as_deref().unwrap_or(""));
+    let mut grams = HashSet::new();
+    for word in name.to_lowercase().split(|c: char| !c.is_alphanumeric()).filter(|w| !w.is_empty()) {
+        let padded: Vec<char> = format!("  {} ", word).chars().collect();
+        for gram in padded.windows(3) {
+            grams.insert(gram.iter().collect());
+        }
+    }
+    grams
+}
+
+pub fn find_duplicates(contacts: &[Contact]) -> Vec<DuplicateCluster> {
+    let mut pairs: HashMap<(usize, usize), PairScore> = HashMap::new();
+
+    let mut by_email: HashMap<String, Vec<usize>> = HashMap::new();
+    let mut by_phone: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, contact) in contacts.iter().enumerate() {
+        if let Some(email) = contact.email.as_deref().and_then(normalize_email) {
+            by_email.entry(email).or_default().push(i);
+        }
+        if let Some(phone) = contact.phone.as_deref().and_then(normalize_phone) {
+            by_phone.entry(phone).or_default().push(i);
+        }
+    }
+    for bucket in by_email.values() {
+        for_each_pair(bucket, |pair| pairs.entry(pair).or_default().email = true);
+    }
+    for bucket in by_phone.values() {
+        for_each_pair(bucket, |pair| pairs.entry(pair).or_default().phone = true);
+    }
+
+    // Count shared trigrams through an inverted index instead of comparing
+    // every pair of names.
+    let grams: Vec<HashSet<String>> = contacts.iter().map(trigrams).collect();
+    let mut index: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, set) in grams.iter().enumerate() {
+        for gram in set {
+            index.entry(gram.as_str()).or_default().push(i);
+        }
+    }
+    let mut shared: HashMap<(usize, usize), usize> = HashMap::new();
+    for holders in index.values() {
+        for_each_pair(holders, |pair| *shared.entry(pair).or_default() += 1);
+    }
+    for ((a, b), common) in shared {
+        let union = grams[a].len() + grams[b].len() - common;
+        let similarity = common as f32 / union as f32;
+        if similarity >= MIN_NAME_SIMILARITY {
+            pairs.entry((a, b)).or_default().name = similarity;
+        }
+    }
+
+    cluster(contacts.len(), pairs)
+}
+
+fn for_each_pair(indexes: &[usize], mut f: impl FnMut((usize, usize))) {
+    for (n, &a) in indexes.iter().enumerate() {
+        for &b in &indexes[n + 1..] {
+            f((a.min(b), a.max(b)));
+        }
+    }
+}
+
+fn cluster(len: usize, pairs: HashMap<(usize, usize), PairScore>) -> Vec<DuplicateCluster> {
+    let mut parent: Vec<usize> = (0..len).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while parent[i] != i {
+            parent[i] = parent[parent[i]];
+            i = parent[i];
+        }
+        i
+    }
+
+    let mut strong = Vec::new();
+    for ((a, b), score) in pairs {
+        let confidence = score.confidence();
+        if confidence >= MIN_CONFIDENCE {
+            let (ra, rb) = (root(&mut parent, a), root(&mut parent, b));
+            parent[ra.max(rb)] = ra.min(rb);
+            strong.push((a, confidence, score));
+        }
+    }
+
+    let mut clusters: HashMap<usize, (BTreeSet<usize>, f32, BTreeSet<DuplicateReason>)> = HashMap::new();
+    for (a, confidence, score) in strong {
+        let entry = clusters.entry(root(&mut parent, a)).or_default();
+        entry.1 = entry.1.max(confidence);
+        entry.2.extend(score.reasons());
+    }
+    for i in 0..len {
+        let r = root(&mut parent, i);
+        if let Some(entry) = clusters.get_mut(&r) {
+            entry.0.insert(i);
+        }
+    }
+
+    let mut clusters: Vec<DuplicateCluster> = clusters
+        .into_values()
+        .map(|(members, confidence, reasons)| DuplicateCluster {
+            members: members.into_iter().collect(),
+            confidence: (confidence * 100.0).round() / 100.0,
+            reasons: reasons.into_iter().collect(),
+        })
+        .collect();
+    clusters.sort_by(|a, b| b.confidence.total_cmp(&a.confidence).then_with(|| a.members.cmp(&b.members)));
+    clusters
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn contact(first: &str, last: Option<&str>, email: Option<&str>, phone: Option<&str>) -> Contact {
+        Contact {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            organization_id: None,
+            first_name: first.to_string(),
+            last_name: last.map(str::to_string),
+            email: email.map(str::to_string),
+            phone: phone.map(str::to_string),
+            phone_raw: None,
+            custom_fields: Default::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+        }
+    }
+
+    #[test]
+    fn test_find_duplicates_clusters_by_signal() {
+        let contacts = vec![
+            contact("Budi", Some("Santoso"), Some("Budi+work@Example.com"), None),
+            contact("B.", None, Some("budi@example.com"), Some("0812-3456-7890")),
+            contact("Pak Budi", None, None, Some("+62 812 3456 7890")),
+            contact("Siti", Some("Rahayu"), None, None),
+            contact("Siti", Some("Rahaju"), None, None),
+            contact("Andi", None, Some("andi@example.com"), None),
+        ];
+
+        let clusters = find_duplicates(&contacts);
+        assert_eq!(clusters.len(), 2);
+        assert_eq!(clusters[0].members, vec![0, 1, 2]);
+        assert_eq!(clusters[0].reasons, vec![DuplicateReason::Email, DuplicateReason::Phone]);
+        assert_eq!(clusters[0].confidence, 0.95);
+        assert_eq!(clusters[1].members, vec![3, 4]);
+        assert_eq!(clusters[1].reasons, vec![DuplicateReason::Name]);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::domain::{
+    entity::group_entity::Group,
+    error::DomainError,
+    repository::{contact_repository::ContactRepository, group_repository::GroupRepository},
+};
+use crate::usecase::contact_access::find_owned_contact;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateGroupRequest {
+    #[validate(length(min = 1, max = 100, message = "Group name must be between 1 and 100 characters"))]
+    pub name: String,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateGroupRequest {
+    #[validate(length(min = 1, max = 100, message = "Group name must be between 1 and 100 characters"))]
+    pub name: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct GroupResponse {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+}
+
+impl From<Group> for GroupResponse {
+    fn from(g: Group) -> Self {
+        Self {
+            id: g.id,
+            name: g.name,
+            description: g.description,
+        }
+    }
+}
+
+pub struct GroupUsecase {
+    group_repo: Arc<dyn GroupRepository>,
+    contact_repo: Arc<dyn ContactRepository>,
+}
+
+impl GroupUsecase {
+    pub fn new(group_repo: Arc<dyn GroupRepository>, contact_repo: Arc<dyn ContactRepository>) -> Self {
+        Self { group_repo, contact_repo }
+    }
+
+    pub async fn create_group(&self, user_id: Uuid, req: CreateGroupRequest) -> Result<GroupResponse, DomainError> {
+        req.validate()?;
+
+        let new_group = Group {
+            id: Uuid::new_v4(),
+            user_id,
+            name: req.name.trim().to_string(),
+            description: req.description,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let created_group = self.group_repo.create_group(&new_group).await.map_err(already_exists)?;
+        Ok(created_group.into())
+    }
+
+    pub async fn list_groups(&self, user_id: Uuid) -> Result<Vec<GroupResponse>, DomainError> {
+        let groups = self.group_repo.find_groups_by_user_id(&user_id).await?;
+        Ok(groups.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn get_group(&self, user_id: Uuid, group_id: Uuid) -> Result<GroupResponse, DomainError> {
+        let group = self.find_owned_group(user_id, group_id).await?;
+        Ok(group.into())
+    }
+
+    // PATCH semantics: only fields present in the request are changed.
+    pub async fn update_group(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+        req: UpdateGroupRequest,
+    ) -> Result<GroupResponse, DomainError> {
+        req.validate()?;
+
+        let mut group = self.find_owned_group(user_id, group_id).await?;
+        if let Some(name) = req.name {
+            group.name = name.trim().to_string();
+        }
+        if let Some(description) = req.description {
+            group.description = Some(description);
+        }
+        group.updated_at = Utc::now();
+
+        let updated_group = self.group_repo.update_group(&group).await.map_err(already_exists)?;
+        Ok(updated_group.into())
+    }
+
+    pub async fn delete_group(&self, user_id: Uuid, group_id: Uuid) -> Result<(), DomainError> {
+        let group = self.find_owned_group(user_id, group_id).await?;
+        self.group_repo.delete_group(&group.id).await
+    }
+
+    pub async fn add_contact(&self, user_id: Uuid, group_id: Uuid, contact_id: Uuid) -> Result<(), DomainError> {
+        let group = self.find_owned_group(user_id, group_id).await?;
+        find_owned_contact(self.contact_repo.as_ref(), user_id, contact_id).await?;
+        self.group_repo.add_contact_to_group(&group.id, &contact_id).await
+    }
+
+    pub async fn remove_contact(&self, user_id: Uuid, group_id: Uuid, contact_id: Uuid) -> Result<(), DomainError> {
+        let group = self.find_owned_group(user_id, group_id).await?;
+        find_owned_contact(self.contact_repo.as_ref(), user_id, contact_id).await?;
+        self.group_repo.remove_contact_from_group(&group.id, &contact_id).await
+    }
+
+    async fn find_owned_group(&self, user_id: Uuid, group_id: Uuid) -> Result<Group, DomainError> {
+        let group = self
+            .group_repo
+            .find_group_by_id(&group_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Group not found".to_string()))?;
+
+        if group.user_id != user_id {
+            return Err(DomainError::Forbidden("You do not have access to this group".to_string()));
+        }
+
+        Ok(group)
+    }
+}
+
+fn already_exists(e: DomainError) -> DomainError {
+    match e {
+        DomainError::Conflict(_) => DomainError::Conflict("A group with this name already exists".to_string()),
+        e => e,
+    }
+}
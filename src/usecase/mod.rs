@@ -1,2 +1,13 @@
+pub mod address;
+pub mod contact_access;
+pub mod contact_csv;
 pub mod contact_usecase;
+pub mod custom_field_usecase;
+pub mod duplicates;
+pub mod group_usecase;
+pub mod organization_usecase;
+pub mod phone;
+pub mod share_usecase;
+pub mod tag_usecase;
 pub mod user_usecase;
+pub mod vcard;
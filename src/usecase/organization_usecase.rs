@@ -0,0 +1,340 @@
+use crate::domain::{
+    entity::organization_entity::{Organization, OrganizationMember, OrganizationRole},
+    error::DomainError,
+    repository::{organization_repository::OrganizationRepository, user_repository::UserRepository},
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateOrganizationRequest {
+    #[validate(length(min = 1, max = 100, message = "Organization name must be between 1 and 100 characters"))]
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct UpdateOrganizationRequest {
+    #[validate(length(min = 1, max = 100, message = "Organization name must be between 1 and 100 characters"))]
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OrganizationResponse {
+    pub id: Uuid,
+    pub name: String,
+    // The caller's role.
+    pub role: OrganizationRole,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct AddMemberRequest {
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    #[serde(default)]
+    pub role: OrganizationRole,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateMemberRequest {
+    pub role: OrganizationRole,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct MemberResponse {
+    pub user_id: Uuid,
+    pub email: String,
+    pub role: OrganizationRole,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct OrganizationUsecase {
+    organization_repo: Arc<dyn OrganizationRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl OrganizationUsecase {
+    pub fn new(organization_repo: Arc<dyn OrganizationRepository>, user_repo: Arc<dyn UserRepository>) -> Self {
+        Self { organization_repo, user_repo }
+    }
+
+    pub async fn create_organization(
+        &self,
+        user_id: Uuid,
+        req: CreateOrganizationRequest,
+    ) -> Result<OrganizationResponse, DomainError> {
+        req.validate()?;
+
+        let organization = Organization {
+            id: Uuid::new_v4(),
+            name: req.name.trim().to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let owner = OrganizationMember {
+            organization_id: organization.id,
+            user_id,
+            role: OrganizationRole::Owner,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let created = self.organization_repo.create_organization(&organization, &owner).await?;
+        Ok(response(created, OrganizationRole::Owner))
+    }
+
+    pub async fn list_organizations(&self, user_id: Uuid) -> Result<Vec<OrganizationResponse>, DomainError> {
+        let organizations = self.organization_repo.find_organizations_by_user_id(&user_id).await?;
+        Ok(organizations.into_iter().map(|o| response(o.organization, o.role)).collect())
+    }
+
+    pub async fn get_organization(&self, user_id: Uuid, organization_id: Uuid) -> Result<OrganizationResponse, DomainError> {
+        let member = self.find_membership(user_id, organization_id).await?;
+        let organization = self.find_organization(organization_id).await?;
+        Ok(response(organization, member.role))
+    }
+
+    pub async fn update_organization(
+        &self,
+        user_id: Uuid,
+        organization_id: Uuid,
+        req: UpdateOrganizationRequest,
+    ) -> Result<OrganizationResponse, DomainError> {
+        req.validate()?;
+
+        let member = self.find_member_with_role(user_id, organization_id, OrganizationRole::Admin).await?;
+        let mut organization = self.find_organization(organization_id).await?;
+        organization.name = req.name.trim().to_string();
+        organization.updated_at = Utc::now();
+
+        let updated = self.organization_repo.update_organization(&organization).await?;
+        Ok(response(updated, member.role))
+    }
+
+    // Deletes the organization's contacts with it.
+    pub async fn delete_organization(&self, user_id: Uuid, organization_id: Uuid) -> Result<(), DomainError> {
+        self.find_member_with_role(user_id, organization_id, OrganizationRole::Owner).await?;
+        self.organization_repo.delete_organization(&organization_id).await
+    }
+
+    pub async fn list_members(&self, user_id: Uuid, organization_id: Uuid) -> Result<Vec<MemberResponse>, DomainError> {
+        self.find_membership(user_id, organization_id).await?;
+        let members = self.organization_repo.find_members(&organization_id).await?;
+        self.with_emails(members).await
+    }
+
+    // Admins add members and admins; only owners add owners.
+    pub async fn add_member(
+        &self,
+        user_id: Uuid,
+        organization_id: Uuid,
+        req: AddMemberRequest,
+    ) -> Result<MemberResponse, DomainError> {
+        req.validate()?;
+
+        let caller = self.find_member_with_role(user_id, organization_id, OrganizationRole::Admin).await?;
+        check_can_grant(&caller, req.role)?;
+        let user = self
+            .user_repo
+            .find_user_by_email(req.email.trim())
+            .await?
+            .ok_or_else(|| DomainError::invalid_field("email", "No user is registered with this email"))?;
+
+        let member = OrganizationMember {
+            organization_id,
+            user_id: user.id,
+            role: req.role,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let added = self.organization_repo.add_member(&member).await.map_err(already_member)?;
+
+        Ok(MemberResponse {
+            user_id: added.user_id,
+            email: user.email,
+            role: added.role,
+            created_at: added.created_at,
+        })
+    }
+
+    pub async fn update_member(
+        &self,
+        user_id: Uuid,
+        organization_id: Uuid,
+        member_id: Uuid,
+        req: UpdateMemberRequest,
+    ) -> Result<MemberResponse, DomainError> {
+        let caller = self.find_member_with_role(user_id, organization_id, OrganizationRole::Admin).await?;
+        let mut member = self.find_member(organization_id, member_id).await?;
+        check_can_grant(&caller, member.role)?;
+        check_can_grant(&caller, req.role)?;
+        if member.role == OrganizationRole::Owner && req.role != OrganizationRole::Owner {
+            self.check_not_last_owner(organization_id).await?;
+        }
+
+        member.role = req.role;
+        member.updated_at = Utc::now();
+        let updated = self.organization_repo.update_member(&member).await?;
+        let mut responses = self.with_emails(vec![updated]).await?;
+        Ok(responses.remove(0))
+    }
+
+    // Admins remove members and admins, owners anyone; everyone can leave.
+    pub async fn remove_member(&self, user_id: Uuid, organization_id: Uuid, member_id: Uuid) -> Result<(), DomainError> {
+        let caller = self.find_membership(user_id, organization_id).await?;
+        let member = self.find_member(organization_id, member_id).await?;
+        if member.user_id != user_id {
+            if caller.role < OrganizationRole::Admin {
+                return Err(DomainError::Forbidden("Only admins can remove members".to_string()));
+            }
+            check_can_grant(&caller, member.role)?;
+        }
+        if member.role == OrganizationRole::Owner {
+            self.check_not_last_owner(organization_id).await?;
+        }
+
+        self.organization_repo.remove_member(&organization_id, &member.user_id).await
+    }
+
+    // The caller's membership, which selecting the organization as the
+    // active tenant requires.
+    pub async fn find_membership(&self, user_id: Uuid, organization_id: Uuid) -> Result<OrganizationMember, DomainError> {
+        self.organization_repo
+            .find_member(&organization_id, &user_id)
+            .await?
+            .ok_or_else(|| DomainError::Forbidden("You are not a member of this organization".to_string()))
+    }
+
+    async fn find_member_with_role(
+        &self,
+        user_id: Uuid,
+        organization_id: Uuid,
+        role: OrganizationRole,
+    ) -> Result<OrganizationMember, DomainError> {
+        let member = self.find_membership(user_id, organization_id).await?;
+        if member.role < role {
+            let message = match role {
+                OrganizationRole::Owner => "Only owners can do this",
+                _ => "Only admins can do this",
+            };
+            return Err(DomainError::Forbidden(message.to_string()));
+        }
+        Ok(member)
+    }
+
+    async fn find_member(&self, organization_id: Uuid, user_id: Uuid) -> Result<OrganizationMember, DomainError> {
+        self.organization_repo
+            .find_member(&organization_id, &user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Member not found".to_string()))
+    }
+
+    async fn find_organization(&self, organization_id: Uuid) -> Result<Organization, DomainError> {
+        self.organization_repo
+            .find_organization_by_id(&organization_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Organization not found".to_string()))
+    }
+
+    async fn check_not_last_owner(&self, organization_id: Uuid) -> Result<(), DomainError> {
+        let members = self.organization_repo.find_members(&organization_id).await?;
+        let owners = members.iter().filter(|m| m.role == OrganizationRole::Owner).count();
+        if owners <= 1 {
+            return Err(DomainError::Conflict("An organization needs at least one owner".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn with_emails(&self, members: Vec<OrganizationMember>) -> Result<Vec<MemberResponse>, DomainError> {
+        let mut responses = Vec::with_capacity(members.len());
+        for member in members {
+            let email = self
+                .user_repo
+                .find_user_by_id(&member.user_id)
+                .await?
+                .map(|u| u.email)
+                .unwrap_or_default();
+            responses.push(MemberResponse {
+                user_id: member.user_id,
+                email,
+                role: member.role,
+                created_at: member.created_at,
+            });
+        }
+        Ok(responses)
+    }
+}
+
+fn response(organization: Organization, role: OrganizationRole) -> OrganizationResponse {
+    OrganizationResponse {
+        id: organization.id,
+        name: organization.name,
+        role,
+    }
+}
+
+// Only owners hand out or take away the owner role.
+fn check_can_grant(caller: &OrganizationMember, role: OrganizationRole) -> Result<(), DomainError> {
+    if role == OrganizationRole::Owner && caller.role != OrganizationRole::Owner {
+        return Err(DomainError::Forbidden("Only owners can manage owners".to_string()));
+    }
+    Ok(())
+}
+
+fn already_member(e: DomainError) -> DomainError {
+    match e {
+        DomainError::Conflict(_) => DomainError::Conflict("This user is already a member".to_string()),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repository::organization_repository::MockOrganizationRepository;
+    use crate::domain::repository::user_repository::MockUserRepository;
+
+    fn member(organization_id: Uuid, user_id: Uuid, role: OrganizationRole) -> OrganizationMember {
+        OrganizationMember {
+            organization_id,
+            user_id,
+            role,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_last_owner_cannot_leave_or_be_demoted() {
+        let organization_id = Uuid::new_v4();
+        let owner_id = Uuid::new_v4();
+        let admin_id = Uuid::new_v4();
+
+        let mut organization_repo = MockOrganizationRepository::new();
+        organization_repo.expect_find_member().returning(move |_, user_id| {
+            let role = if *user_id == owner_id { OrganizationRole::Owner } else { OrganizationRole::Admin };
+            Ok(Some(member(organization_id, *user_id, role)))
+        });
+        organization_repo.expect_find_members().returning(move |_| {
+            Ok(vec![
+                member(organization_id, owner_id, OrganizationRole::Owner),
+                member(organization_id, admin_id, OrganizationRole::Admin),
+            ])
+        });
+        organization_repo.expect_update_member().never();
+        organization_repo.expect_remove_member().never();
+
+        let usecase = OrganizationUsecase::new(Arc::new(organization_repo), Arc::new(MockUserRepository::new()));
+
+        let result = usecase.remove_member(owner_id, organization_id, owner_id).await;
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+        let demote = UpdateMemberRequest { role: OrganizationRole::Member };
+        let result = usecase.update_member(owner_id, organization_id, owner_id, demote).await;
+        assert!(matches!(result, Err(DomainError::Conflict(_))));
+        // Admins cannot touch owners at all.
+        let result = usecase.remove_member(admin_id, organization_id, owner_id).await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+    }
+}
@@ -0,0 +1,76 @@
+// Phone number handling. Contacts store the E.164 form for matching and
+// the caller's original spelling for display.
+use crate::domain::error::DomainError;
+use phonenumber::{country, Mode};
+
+// Parses an ISO 3166-1 alpha-2 region such as "ID" or "us".
+pub fn parse_region(code: &str) -> Option<country::Id> {
+    code.trim().to_ascii_uppercase().parse().ok()
+}
+
+// Numbers written with a leading `+` need no region; national numbers are
+// read in `region`.
+pub fn to_e164(raw: &str, region: Option<&str>) -> Result<String, DomainError> {
+    let invalid = |message: &str| DomainError::invalid_field("phone", message);
+
+    let region = region.and_then(parse_region);
+    if region.is_none() && !raw.trim_start().starts_with('+') {
+        return Err(invalid(
+            "Phone number must start with a country code (+...) when no default region is set",
+        ));
+    }
+
+    match phonenumber::parse(region, raw) {
+        Ok(number) if phonenumber::is_valid(&number) => Ok(number.format().mode(Mode::E164).to_string()),
+        _ => Err(invalid("Invalid phone number")),
+    }
+}
+
+// Digits a stored E.164 number must contain to match a phone filter. A full
+// number is normalized first; a fragment loses its formatting and any trunk
+// prefix zeros.
+pub fn search_digits(raw: &str, region: Option<&str>) -> String {
+    if let Ok(e164) = to_e164(raw, region) {
+        return e164.trim_start_matches('+').to_string();
+    }
+    fragment_digits(raw)
+}
+
+// Digits a number stored before normalization, still as typed, must contain
+// to match a phone filter. A full number gives its national number without
+// trunk prefix, which national and international spellings share.
+pub fn legacy_search_digits(raw: &str, region: Option<&str>) -> String {
+    match phonenumber::parse(region.and_then(parse_region), raw) {
+        Ok(number) if phonenumber::is_valid(&number) => number.national().value().to_string(),
+        _ => fragment_digits(raw),
+    }
+}
+
+fn fragment_digits(raw: &str) -> String {
+    raw.chars()
+        .filter(char::is_ascii_digit)
+        .collect::<String>()
+        .trim_start_matches('0')
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_e164_uses_region_for_national_numbers() {
+        assert_eq!(to_e164("0812-3456-7890", Some("ID")).unwrap(), "+6281234567890");
+        assert_eq!(to_e164("+62 812 3456 7890", None).unwrap(), "+6281234567890");
+        assert_eq!(to_e164("(415) 555-2671", Some("us")).unwrap(), "+14155552671");
+        assert!(to_e164("0812-3456-7890", None).is_err());
+        assert!(to_e164("abc", Some("ID")).is_err());
+        assert!(to_e164("123", Some("ID")).is_err());
+
+        assert_eq!(search_digits("0812 3456", Some("ID")), "8123456");
+        assert_eq!(search_digits("0812-3456-7890", Some("ID")), "6281234567890");
+        assert_eq!(legacy_search_digits("+62 812-3456-7890", None), "81234567890");
+        assert_eq!(legacy_search_digits("0812-3456-7890", Some("ID")), "81234567890");
+        assert_eq!(legacy_search_digits("0812 3456", Some("ID")), "8123456");
+    }
+}
@@ -0,0 +1,262 @@
+use crate::domain::{
+    entity::share_entity::{ContactShare, SharePermission},
+    error::DomainError,
+    repository::{
+        contact_repository::ContactRepository, group_repository::GroupRepository,
+        share_repository::ShareRepository, user_repository::UserRepository,
+    },
+};
+use crate::usecase::contact_access::find_owned_contact;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateShareRequest {
+    // The colleague to share with, by the email they registered with.
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+    pub permission: SharePermission,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateShareRequest {
+    pub permission: SharePermission,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ShareResponse {
+    pub id: Uuid,
+    pub grantee_id: Uuid,
+    pub grantee_email: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub contact_id: Option<Uuid>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub group_id: Option<Uuid>,
+    pub permission: SharePermission,
+    pub created_at: DateTime<Utc>,
+}
+
+pub struct ShareUsecase {
+    share_repo: Arc<dyn ShareRepository>,
+    contact_repo: Arc<dyn ContactRepository>,
+    group_repo: Arc<dyn GroupRepository>,
+    user_repo: Arc<dyn UserRepository>,
+}
+
+impl ShareUsecase {
+    pub fn new(
+        share_repo: Arc<dyn ShareRepository>,
+        contact_repo: Arc<dyn ContactRepository>,
+        group_repo: Arc<dyn GroupRepository>,
+        user_repo: Arc<dyn UserRepository>,
+    ) -> Self {
+        Self { share_repo, contact_repo, group_repo, user_repo }
+    }
+
+    pub async fn share_contact(
+        &self,
+        user_id: Uuid,
+        contact_id: Uuid,
+        req: CreateShareRequest,
+    ) -> Result<ShareResponse, DomainError> {
+        find_owned_contact(self.contact_repo.as_ref(), user_id, contact_id).await?;
+        self.create_share(user_id, Some(contact_id), None, req).await
+    }
+
+    // Shares every contact in the group, including ones added later.
+    pub async fn share_group(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+        req: CreateShareRequest,
+    ) -> Result<ShareResponse, DomainError> {
+        self.find_owned_group(user_id, group_id).await?;
+        self.create_share(user_id, None, Some(group_id), req).await
+    }
+
+    pub async fn list_contact_shares(&self, user_id: Uuid, contact_id: Uuid) -> Result<Vec<ShareResponse>, DomainError> {
+        find_owned_contact(self.contact_repo.as_ref(), user_id, contact_id).await?;
+        let shares = self.share_repo.find_shares_by_contact_id(&contact_id).await?;
+        self.with_grantees(shares).await
+    }
+
+    pub async fn list_group_shares(&self, user_id: Uuid, group_id: Uuid) -> Result<Vec<ShareResponse>, DomainError> {
+        self.find_owned_group(user_id, group_id).await?;
+        let shares = self.share_repo.find_shares_by_group_id(&group_id).await?;
+        self.with_grantees(shares).await
+    }
+
+    pub async fn update_share(
+        &self,
+        user_id: Uuid,
+        share_id: Uuid,
+        req: UpdateShareRequest,
+    ) -> Result<ShareResponse, DomainError> {
+        let mut share = self.find_share(user_id, share_id).await?;
+        if share.owner_id != user_id {
+            return Err(DomainError::Forbidden("Only the owner can change a share".to_string()));
+        }
+
+        share.permission = req.permission;
+        share.updated_at = Utc::now();
+        let updated = self.share_repo.update_share(&share).await?;
+        let mut responses = self.with_grantees(vec![updated]).await?;
+        Ok(responses.remove(0))
+    }
+
+    // The owner revokes a share; the grantee can give it up.
+    pub async fn delete_share(&self, user_id: Uuid, share_id: Uuid) -> Result<(), DomainError> {
+        let share = self.find_share(user_id, share_id).await?;
+        self.share_repo.delete_share(&share.id).await
+    }
+
+    async fn create_share(
+        &self,
+        user_id: Uuid,
+        contact_id: Option<Uuid>,
+        group_id: Option<Uuid>,
+        req: CreateShareRequest,
+    ) -> Result<ShareResponse, DomainError> {
+        req.validate()?;
+
+        let grantee = self
+            .user_repo
+            .find_user_by_email(req.email.trim())
+            .await?
+            .ok_or_else(|| DomainError::invalid_field("email", "No user is registered with this email"))?;
+        if grantee.id == user_id {
+            return Err(DomainError::invalid_field("email", "You cannot share with yourself"));
+        }
+
+        let share = ContactShare {
+            id: Uuid::new_v4(),
+            owner_id: user_id,
+            grantee_id: grantee.id,
+            contact_id,
+            group_id,
+            permission: req.permission,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let created = self.share_repo.create_share(&share).await.map_err(already_exists)?;
+
+        Ok(ShareResponse {
+            id: created.id,
+            grantee_id: created.grantee_id,
+            grantee_email: grantee.email,
+            contact_id: created.contact_id,
+            group_id: created.group_id,
+            permission: created.permission,
+            created_at: created.created_at,
+        })
+    }
+
+    async fn with_grantees(&self, shares: Vec<ContactShare>) -> Result<Vec<ShareResponse>, DomainError> {
+        let mut responses = Vec::with_capacity(shares.len());
+        for share in shares {
+            let grantee_email = self
+                .user_repo
+                .find_user_by_id(&share.grantee_id)
+                .await?
+                .map(|u| u.email)
+                .unwrap_or_default();
+            responses.push(ShareResponse {
+                id: share.id,
+                grantee_id: share.grantee_id,
+                grantee_email,
+                contact_id: share.contact_id,
+                group_id: share.group_id,
+                permission: share.permission,
+                created_at: share.created_at,
+            });
+        }
+        Ok(responses)
+    }
+
+    // Visible to both sides of the share.
+    async fn find_share(&self, user_id: Uuid, share_id: Uuid) -> Result<ContactShare, DomainError> {
+        let share = self
+            .share_repo
+            .find_share_by_id(&share_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Share not found".to_string()))?;
+
+        if share.owner_id != user_id && share.grantee_id != user_id {
+            return Err(DomainError::Forbidden("You do not have access to this share".to_string()));
+        }
+
+        Ok(share)
+    }
+
+    async fn find_owned_group(&self, user_id: Uuid, group_id: Uuid) -> Result<(), DomainError> {
+        let group = self
+            .group_repo
+            .find_group_by_id(&group_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Group not found".to_string()))?;
+
+        if group.user_id != user_id {
+            return Err(DomainError::Forbidden("You do not have access to this group".to_string()));
+        }
+
+        Ok(())
+    }
+}
+
+fn already_exists(e: DomainError) -> DomainError {
+    match e {
+        DomainError::Conflict(_) => DomainError::Conflict("This user already has a share here".to_string()),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::repository::contact_repository::MockContactRepository;
+    use crate::domain::repository::group_repository::MockGroupRepository;
+    use crate::domain::repository::share_repository::MockShareRepository;
+    use crate::domain::repository::user_repository::MockUserRepository;
+
+    #[tokio::test]
+    async fn test_update_share_is_owner_only() {
+        let owner_id = Uuid::new_v4();
+        let grantee_id = Uuid::new_v4();
+        let share_id = Uuid::new_v4();
+
+        let mut share_repo = MockShareRepository::new();
+        share_repo.expect_find_share_by_id().returning(move |_| {
+            Ok(Some(ContactShare {
+                id: share_id,
+                owner_id,
+                grantee_id,
+                contact_id: Some(Uuid::new_v4()),
+                group_id: None,
+                permission: SharePermission::Read,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            }))
+        });
+        share_repo.expect_update_share().never();
+        share_repo.expect_delete_share().times(1).returning(|_| Ok(()));
+
+        let usecase = ShareUsecase::new(
+            Arc::new(share_repo),
+            Arc::new(MockContactRepository::new()),
+            Arc::new(MockGroupRepository::new()),
+            Arc::new(MockUserRepository::new()),
+        );
+
+        // The grantee can neither upgrade their own share nor see anyone
+        // else's, but may drop it.
+        let upgrade = UpdateShareRequest { permission: SharePermission::Edit };
+        let result = usecase.update_share(grantee_id, share_id, upgrade).await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        let result = usecase.delete_share(Uuid::new_v4(), share_id).await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+        assert!(usecase.delete_share(grantee_id, share_id).await.is_ok());
+    }
+}
@@ -0,0 +1,168 @@
+use crate::domain::{
+    entity::tag_entity::Tag,
+    error::DomainError,
+    repository::{contact_repository::ContactRepository, tag_repository::TagRepository},
+};
+use crate::usecase::contact_access::find_owned_contact;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CreateTagRequest {
+    #[validate(length(min = 1, max = 50, message = "Tag name must be between 1 and 50 characters"))]
+    pub name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TagResponse {
+    pub id: Uuid,
+    pub name: String,
+}
+
+impl From<Tag> for TagResponse {
+    fn from(t: Tag) -> Self {
+        Self { id: t.id, name: t.name }
+    }
+}
+
+pub struct TagUsecase {
+    tag_repo: Arc<dyn TagRepository>,
+    contact_repo: Arc<dyn ContactRepository>,
+}
+
+impl TagUsecase {
+    pub fn new(tag_repo: Arc<dyn TagRepository>, contact_repo: Arc<dyn ContactRepository>) -> Self {
+        Self { tag_repo, contact_repo }
+    }
+
+    pub async fn create_tag(&self, user_id: Uuid, req: CreateTagRequest) -> Result<TagResponse, DomainError> {
+        req.validate()?;
+
+        let new_tag = Tag {
+            id: Uuid::new_v4(),
+            user_id,
+            name: req.name.trim().to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let created_tag = self.tag_repo.create_tag(&new_tag).await.map_err(already_exists)?;
+        Ok(created_tag.into())
+    }
+
+    pub async fn list_tags(&self, user_id: Uuid) -> Result<Vec<TagResponse>, DomainError> {
+        let tags = self.tag_repo.find_tags_by_user_id(&user_id).await?;
+        Ok(tags.into_iter().map(Into::into).collect())
+    }
+
+    pub async fn get_tag(&self, user_id: Uuid, tag_id: Uuid) -> Result<TagResponse, DomainError> {
+        let tag = self.find_owned_tag(user_id, tag_id).await?;
+        Ok(tag.into())
+    }
+
+    pub async fn rename_tag(
+        &self,
+        user_id: Uuid,
+        tag_id: Uuid,
+        req: CreateTagRequest,
+    ) -> Result<TagResponse, DomainError> {
+        req.validate()?;
+
+        let mut tag = self.find_owned_tag(user_id, tag_id).await?;
+        tag.name = req.name.trim().to_string();
+        tag.updated_at = Utc::now();
+
+        let updated_tag = self.tag_repo.update_tag(&tag).await.map_err(already_exists)?;
+        Ok(updated_tag.into())
+    }
+
+    pub async fn delete_tag(&self, user_id: Uuid, tag_id: Uuid) -> Result<(), DomainError> {
+        let tag = self.find_owned_tag(user_id, tag_id).await?;
+        self.tag_repo.delete_tag(&tag.id).await
+    }
+
+    pub async fn tag_contact(&self, user_id: Uuid, contact_id: Uuid, tag_id: Uuid) -> Result<(), DomainError> {
+        find_owned_contact(self.contact_repo.as_ref(), user_id, contact_id).await?;
+        let tag = self.find_owned_tag(user_id, tag_id).await?;
+        self.tag_repo.add_tag_to_contact(&contact_id, &tag.id).await
+    }
+
+    pub async fn untag_contact(&self, user_id: Uuid, contact_id: Uuid, tag_id: Uuid) -> Result<(), DomainError> {
+        find_owned_contact(self.contact_repo.as_ref(), user_id, contact_id).await?;
+        let tag = self.find_owned_tag(user_id, tag_id).await?;
+        self.tag_repo.remove_tag_from_contact(&contact_id, &tag.id).await
+    }
+
+    async fn find_owned_tag(&self, user_id: Uuid, tag_id: Uuid) -> Result<Tag, DomainError> {
+        let tag = self
+            .tag_repo
+            .find_tag_by_id(&tag_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("Tag not found".to_string()))?;
+
+        if tag.user_id != user_id {
+            return Err(DomainError::Forbidden("You do not have access to this tag".to_string()));
+        }
+
+        Ok(tag)
+    }
+}
+
+fn already_exists(e: DomainError) -> DomainError {
+    match e {
+        DomainError::Conflict(_) => DomainError::Conflict("A tag with this name already exists".to_string()),
+        e => e,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entity::contact_entity::Contact;
+    use crate::domain::repository::contact_repository::MockContactRepository;
+    use crate::domain::repository::tag_repository::MockTagRepository;
+
+    #[tokio::test]
+    async fn test_tag_contact_rejects_other_users_tag() {
+        let user_id = Uuid::new_v4();
+        let contact_id = Uuid::new_v4();
+        let mut contact_repo = MockContactRepository::new();
+        let mut tag_repo = MockTagRepository::new();
+
+        contact_repo
+            .expect_find_contact_by_id()
+            .returning(move |_, _| Ok(Some(Contact {
+                id: contact_id,
+                user_id,
+                organization_id: None,
+                first_name: "Jane".to_string(),
+                last_name: None,
+                email: None,
+                phone: None,
+                phone_raw: None,
+                custom_fields: Default::default(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                version: 1,
+                deleted_at: None,
+            })));
+        tag_repo
+            .expect_find_tag_by_id()
+            .returning(|id| Ok(Some(Tag {
+                id: *id,
+                user_id: Uuid::new_v4(), // Someone else's tag
+                name: "vip".to_string(),
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })));
+        tag_repo.expect_add_tag_to_contact().times(0);
+
+        let usecase = TagUsecase::new(Arc::new(tag_repo), Arc::new(contact_repo));
+
+        let result = usecase.tag_contact(user_id, contact_id, Uuid::new_v4()).await;
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+    }
+}
@@ -1,8 +1,14 @@
+use crate::domain::entity::refresh_token_entity::RefreshToken;
+use crate::domain::entity::token_revocation_entity::RevokedToken;
 use crate::domain::entity::user_entity::User;
+use crate::domain::error::DomainError;
+use crate::domain::repository::refresh_token_repository::RefreshTokenRepository;
+use crate::domain::repository::token_revocation_repository::TokenRevocationRepository;
 use crate::domain::repository::user_repository::UserRepository;
-use crate::infrastructure::auth::jwt::JwtService;
+use crate::infrastructure::auth::jwt::{Claims, JwtService};
 use crate::infrastructure::auth::password::PasswordService;
-use chrono::Utc;
+use crate::usecase::phone;
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use uuid::Uuid;
@@ -16,6 +22,27 @@ pub struct RegisterRequest {
     pub email: String,
     #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
     pub password: String,
+    // ISO 3166-1 alpha-2 region for contact phone numbers entered without a
+    // country code, e.g. "ID".
+    pub phone_region: Option<String>,
+}
+
+// Body of `PATCH /users/me`. Absent fields are left alone;
+// `phone_region: null` clears the region.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateUserRequest {
+    #[serde(default, deserialize_with = "present")]
+    pub phone_region: Option<Option<String>>,
+}
+
+// Tells a field sent as null (Some(None)) from a missing one (None, through
+// `#[serde(default)]`).
+fn present<'de, T, D>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    T: Deserialize<'de>,
+    D: serde::Deserializer<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -24,9 +51,22 @@ pub struct LoginRequest {
     pub password: String,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RefreshRequest {
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct LogoutRequest {
+    // When present, the refresh token family is revoked as well so the
+    // client cannot silently mint a new access token.
+    pub refresh_token: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AuthResponse {
     pub token: String,
+    pub refresh_token: String,
     pub user: UserResponse,
 }
 
@@ -35,6 +75,7 @@ pub struct UserResponse {
     pub id: Uuid,
     pub username: String,
     pub email: String,
+    pub phone_region: Option<String>,
     pub created_at: chrono::DateTime<Utc>,
 }
 
@@ -44,38 +85,58 @@ impl From<User> for UserResponse {
             id: user.id,
             username: user.username,
             email: user.email,
+            phone_region: user.phone_region,
             created_at: user.created_at,
         }
     }
 }
 
+fn normalize_region(code: &str) -> Result<String, DomainError> {
+    match phone::parse_region(code) {
+        Some(region) => Ok(region.as_ref().to_string()),
+        None => Err(DomainError::invalid_field("phone_region", "Unknown region code")),
+    }
+}
+
 pub struct UserUsecase {
     user_repo: Arc<dyn UserRepository>,
+    refresh_token_repo: Arc<dyn RefreshTokenRepository>,
+    token_revocation_repo: Arc<dyn TokenRevocationRepository>,
     jwt_service: Arc<JwtService>,
 }
 
 impl UserUsecase {
-    pub fn new(user_repo: Arc<dyn UserRepository>, jwt_service: Arc<JwtService>) -> Self {
+    pub fn new(
+        user_repo: Arc<dyn UserRepository>,
+        refresh_token_repo: Arc<dyn RefreshTokenRepository>,
+        token_revocation_repo: Arc<dyn TokenRevocationRepository>,
+        jwt_service: Arc<JwtService>,
+    ) -> Self {
         Self {
             user_repo,
+            refresh_token_repo,
+            token_revocation_repo,
             jwt_service,
         }
     }
 
-    pub async fn register(&self, req: RegisterRequest) -> Result<UserResponse, String> {
-        req.validate().map_err(|e| e.to_string())?;
+    pub async fn register(&self, req: RegisterRequest) -> Result<UserResponse, DomainError> {
+        req.validate()?;
+        let phone_region = req.phone_region.as_deref().map(normalize_region).transpose()?;
 
         if self.user_repo.find_user_by_email(&req.email).await?.is_some() {
-            return Err("Email already exists".to_string());
+            return Err(DomainError::Conflict("Email already exists".to_string()));
         }
 
-        let password_hash = PasswordService::hash_password(&req.password)?;
+        let password_hash =
+            PasswordService::hash_password(&req.password).map_err(DomainError::Infrastructure)?;
 
         let new_user = User {
             id: Uuid::new_v4(),
             username: req.username,
             email: req.email,
             password_hash,
+            phone_region,
             created_at: Utc::now(),
             updated_at: Utc::now(),
         };
@@ -84,29 +145,173 @@ impl UserUsecase {
         Ok(created_user.into())
     }
 
-    pub async fn login(&self, req: LoginRequest) -> Result<AuthResponse, String> {
+    pub async fn login(&self, req: LoginRequest) -> Result<AuthResponse, DomainError> {
         let user = self
             .user_repo
             .find_user_by_email(&req.email)
             .await?
-            .ok_or("Invalid credentials")?;
+            .ok_or_else(|| DomainError::Unauthorized("Invalid credentials".to_string()))?;
+
+        if !PasswordService::verify_password(&req.password, &user.password_hash)
+            .map_err(DomainError::Infrastructure)?
+        {
+            return Err(DomainError::Unauthorized("Invalid credentials".to_string()));
+        }
+
+        let token = self.jwt_service
+            .generate_token(user.id)
+            .map_err(DomainError::Infrastructure)?;
+        let refresh_token = self.issue_refresh_token(user.id, Uuid::new_v4(), None).await?;
+
+        Ok(AuthResponse {
+            token,
+            refresh_token,
+            user: user.into(),
+        })
+    }
+
+    pub async fn get_user(&self, user_id: Uuid) -> Result<UserResponse, DomainError> {
+        let user = self
+            .user_repo
+            .find_user_by_id(&user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+
+        Ok(user.into())
+    }
+
+    pub async fn update_user(&self, user_id: Uuid, req: UpdateUserRequest) -> Result<UserResponse, DomainError> {
+        let mut user = self
+            .user_repo
+            .find_user_by_id(&user_id)
+            .await?
+            .ok_or_else(|| DomainError::NotFound("User not found".to_string()))?;
+
+        if let Some(phone_region) = req.phone_region {
+            user.phone_region = phone_region.as_deref().map(normalize_region).transpose()?;
+        }
+        user.updated_at = Utc::now();
+
+        let updated_user = self.user_repo.update_user(&user).await?;
+        Ok(updated_user.into())
+    }
+
+    pub async fn refresh(&self, req: RefreshRequest) -> Result<AuthResponse, DomainError> {
+        let token_hash = self.jwt_service.hash_refresh_token(&req.refresh_token);
+        let stored = self
+            .refresh_token_repo
+            .find_refresh_token_by_hash(&token_hash)
+            .await?
+            .ok_or_else(|| DomainError::Unauthorized("Invalid refresh token".to_string()))?;
 
-        if !PasswordService::verify_password(&req.password, &user.password_hash)? {
-            return Err("Invalid credentials".to_string());
+        // A token that was already rotated is being presented again: either the
+        // client or an attacker holds a stale copy, so kill the whole family.
+        if stored.revoked_at.is_some() {
+            self.refresh_token_repo.revoke_token_family(&stored.family_id).await?;
+            return Err(DomainError::Unauthorized("Refresh token reuse detected".to_string()));
         }
 
-        let token = self.jwt_service.generate_token(user.id)?;
+        if stored.expires_at <= Utc::now() {
+            return Err(DomainError::Unauthorized("Refresh token expired".to_string()));
+        }
+
+        let user = self
+            .user_repo
+            .find_user_by_id(&stored.user_id)
+            .await?
+            .ok_or_else(|| DomainError::Unauthorized("Invalid refresh token".to_string()))?;
+
+        let new_id = Uuid::new_v4();
+        if !self
+            .refresh_token_repo
+            .revoke_refresh_token(&stored.id, Some(new_id))
+            .await?
+        {
+            // Lost a race with a concurrent refresh of the same token.
+            self.refresh_token_repo.revoke_token_family(&stored.family_id).await?;
+            return Err(DomainError::Unauthorized("Refresh token reuse detected".to_string()));
+        }
+
+        let token = self.jwt_service
+            .generate_token(user.id)
+            .map_err(DomainError::Infrastructure)?;
+        let refresh_token = self
+            .issue_refresh_token(user.id, stored.family_id, Some(new_id))
+            .await?;
 
         Ok(AuthResponse {
             token,
+            refresh_token,
             user: user.into(),
         })
     }
+
+    pub async fn logout(&self, claims: &Claims, req: LogoutRequest) -> Result<(), DomainError> {
+        let invalid = |message: &str| DomainError::Unauthorized(message.to_string());
+        let user_id = Uuid::parse_str(&claims.sub).map_err(|_| invalid("Invalid user ID in token"))?;
+        let jti = Uuid::parse_str(&claims.jti).map_err(|_| invalid("Invalid token id"))?;
+        let expires_at = DateTime::from_timestamp(claims.exp as i64, 0)
+            .ok_or_else(|| invalid("Invalid token expiry"))?;
+
+        let revoked = RevokedToken {
+            jti,
+            user_id,
+            expires_at,
+            revoked_at: Utc::now(),
+        };
+        self.token_revocation_repo.revoke_token(&revoked).await?;
+        self.jwt_service.revocations().revoke_token(jti, expires_at);
+
+        if let Some(raw_token) = req.refresh_token {
+            let token_hash = self.jwt_service.hash_refresh_token(&raw_token);
+            if let Some(stored) = self.refresh_token_repo.find_refresh_token_by_hash(&token_hash).await? {
+                if stored.user_id == user_id {
+                    self.refresh_token_repo.revoke_token_family(&stored.family_id).await?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn logout_all(&self, user_id: Uuid) -> Result<(), DomainError> {
+        let revoked_before = Utc::now();
+
+        self.token_revocation_repo.revoke_user_tokens(&user_id, revoked_before).await?;
+        self.jwt_service.revocations().revoke_user_tokens(user_id, revoked_before);
+        self.refresh_token_repo.revoke_refresh_tokens_by_user_id(&user_id).await
+    }
+
+    async fn issue_refresh_token(
+        &self,
+        user_id: Uuid,
+        family_id: Uuid,
+        id: Option<Uuid>,
+    ) -> Result<String, DomainError> {
+        let raw_token = self.jwt_service.generate_refresh_token();
+        let now = Utc::now();
+
+        let refresh_token = RefreshToken {
+            id: id.unwrap_or_else(Uuid::new_v4),
+            user_id,
+            family_id,
+            token_hash: self.jwt_service.hash_refresh_token(&raw_token),
+            expires_at: now + self.jwt_service.refresh_token_ttl(),
+            revoked_at: None,
+            replaced_by: None,
+            created_at: now,
+        };
+
+        self.refresh_token_repo.create_refresh_token(&refresh_token).await?;
+        Ok(raw_token)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::domain::repository::refresh_token_repository::MockRefreshTokenRepository;
+    use crate::domain::repository::token_revocation_repository::MockTokenRevocationRepository;
     use crate::domain::repository::user_repository::MockUserRepository;
 
     #[tokio::test]
@@ -125,12 +330,18 @@ mod tests {
             .times(1)
             .returning(|u| Ok(u.clone()));
 
-        let usecase = UserUsecase::new(Arc::new(mock_repo), jwt_service);
+        let usecase = UserUsecase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockRefreshTokenRepository::new()),
+            Arc::new(MockTokenRevocationRepository::new()),
+            jwt_service,
+        );
 
         let req = RegisterRequest {
             username: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password: "password123".to_string(),
+            phone_region: None,
         };
 
         let result = usecase.register(req).await;
@@ -152,20 +363,154 @@ mod tests {
                 username: "existing".to_string(),
                 email: "test@example.com".to_string(),
                 password_hash: "hash".to_string(),
+                phone_region: None,
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
             })));
 
-        let usecase = UserUsecase::new(Arc::new(mock_repo), jwt_service);
+        let usecase = UserUsecase::new(
+            Arc::new(mock_repo),
+            Arc::new(MockRefreshTokenRepository::new()),
+            Arc::new(MockTokenRevocationRepository::new()),
+            jwt_service,
+        );
 
         let req = RegisterRequest {
             username: "testuser".to_string(),
             email: "test@example.com".to_string(),
             password: "password123".to_string(),
+            phone_region: None,
         };
 
         let result = usecase.register(req).await;
-        assert!(result.is_err());
-        assert_eq!(result.err().unwrap(), "Email already exists");
+        assert_eq!(
+            result.err().unwrap(),
+            DomainError::Conflict("Email already exists".to_string())
+        );
+    }
+
+    fn stored_refresh_token(jwt_service: &JwtService, raw: &str, user_id: Uuid) -> RefreshToken {
+        RefreshToken {
+            id: Uuid::new_v4(),
+            user_id,
+            family_id: Uuid::new_v4(),
+            token_hash: jwt_service.hash_refresh_token(raw),
+            expires_at: Utc::now() + chrono::Duration::days(1),
+            revoked_at: None,
+            replaced_by: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_refresh_rotates_token() {
+        let mut mock_user_repo = MockUserRepository::new();
+        let mut mock_token_repo = MockRefreshTokenRepository::new();
+        let jwt_service = Arc::new(JwtService::new());
+        let user_id = Uuid::new_v4();
+        let stored = stored_refresh_token(&jwt_service, "old-token", user_id);
+        let family_id = stored.family_id;
+
+        mock_token_repo
+            .expect_find_refresh_token_by_hash()
+            .times(1)
+            .returning(move |_| Ok(Some(stored.clone())));
+        mock_token_repo
+            .expect_revoke_refresh_token()
+            .times(1)
+            .returning(|_, _| Ok(true));
+        mock_token_repo
+            .expect_create_refresh_token()
+            .withf(move |t| t.family_id == family_id && t.user_id == user_id)
+            .times(1)
+            .returning(|t| Ok(t.clone()));
+        mock_token_repo.expect_revoke_token_family().times(0);
+
+        mock_user_repo
+            .expect_find_user_by_id()
+            .times(1)
+            .returning(move |_| Ok(Some(User {
+                id: user_id,
+                username: "testuser".to_string(),
+                email: "test@example.com".to_string(),
+                password_hash: "hash".to_string(),
+                phone_region: None,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })));
+
+        let usecase = UserUsecase::new(
+            Arc::new(mock_user_repo),
+            Arc::new(mock_token_repo),
+            Arc::new(MockTokenRevocationRepository::new()),
+            jwt_service,
+        );
+
+        let result = usecase
+            .refresh(RefreshRequest { refresh_token: "old-token".to_string() })
+            .await;
+        assert!(result.is_ok());
+        assert_ne!(result.unwrap().refresh_token, "old-token");
+    }
+
+    #[tokio::test]
+    async fn test_refresh_reuse_revokes_family() {
+        let mut mock_token_repo = MockRefreshTokenRepository::new();
+        let jwt_service = Arc::new(JwtService::new());
+        let mut stored = stored_refresh_token(&jwt_service, "rotated-token", Uuid::new_v4());
+        stored.revoked_at = Some(Utc::now());
+        let family_id = stored.family_id;
+
+        mock_token_repo
+            .expect_find_refresh_token_by_hash()
+            .times(1)
+            .returning(move |_| Ok(Some(stored.clone())));
+        mock_token_repo
+            .expect_revoke_token_family()
+            .with(mockall::predicate::eq(family_id))
+            .times(1)
+            .returning(|_| Ok(()));
+        mock_token_repo.expect_create_refresh_token().times(0);
+
+        let usecase = UserUsecase::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(mock_token_repo),
+            Arc::new(MockTokenRevocationRepository::new()),
+            jwt_service,
+        );
+
+        let result = usecase
+            .refresh(RefreshRequest { refresh_token: "rotated-token".to_string() })
+            .await;
+        assert_eq!(
+            result.err().unwrap(),
+            DomainError::Unauthorized("Refresh token reuse detected".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_logout_revokes_access_token() {
+        let mut mock_revocation_repo = MockTokenRevocationRepository::new();
+        let jwt_service = Arc::new(JwtService::new());
+        let user_id = Uuid::new_v4();
+
+        mock_revocation_repo
+            .expect_revoke_token()
+            .times(1)
+            .returning(|_| Ok(()));
+
+        let usecase = UserUsecase::new(
+            Arc::new(MockUserRepository::new()),
+            Arc::new(MockRefreshTokenRepository::new()),
+            Arc::new(mock_revocation_repo),
+            jwt_service.clone(),
+        );
+
+        let token = jwt_service.generate_token(user_id).unwrap();
+        let claims = jwt_service.verify_token(&token).unwrap();
+
+        let result = usecase.logout(&claims, LogoutRequest::default()).await;
+        assert!(result.is_ok());
+        assert_eq!(jwt_service.verify_token(&token).err().unwrap(), "Token has been revoked");
     }
 }
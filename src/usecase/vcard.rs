@@ -0,0 +1,491 @@
+// Minimal vCard (RFC 6350) reader/writer covering the properties we store:
+// N, FN, EMAIL, TEL and ADR. Cards are written as 3.0 by default, which
+// phones import most reliably, or as 4.0; 2.1, 3.0 and 4.0 are accepted on
+// input.
+use crate::domain::entity::{
+    address_entity::{Address, AddressKind},
+    contact_entity::Contact,
+    contact_method_entity::{ContactEmail, ContactLabel, ContactPhone},
+};
+use serde::{Deserialize, Serialize};
+
+const MAX_LINE_OCTETS: usize = 75;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VCardVersion {
+    #[default]
+    #[serde(rename = "3.0")]
+    V3,
+    #[serde(rename = "4.0")]
+    V4,
+}
+
+// Query of the vCard export endpoints, e.g. `?version=4.0`.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct VCardExportOptions {
+    #[serde(default)]
+    pub version: VCardVersion,
+}
+
+// TYPE=home/work set the kind and PREF marks the preferred address, as
+// for VCardEntry.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VCardAddress {
+    pub street: Option<String>,
+    pub city: Option<String>,
+    pub province: Option<String>,
+    pub postal_code: Option<String>,
+    pub country: Option<String>,
+    pub kind: AddressKind,
+    pub preferred: bool,
+}
+
+// An EMAIL or TEL property. TYPE=home/work/cell set the label and PREF
+// marks the preferred entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VCardEntry {
+    pub value: String,
+    pub label: ContactLabel,
+    pub preferred: bool,
+}
+
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct VCard {
+    pub first_name: Option<String>,
+    pub last_name: Option<String>,
+    pub formatted_name: Option<String>,
+    pub emails: Vec<VCardEntry>,
+    pub phones: Vec<VCardEntry>,
+    pub addresses: Vec<VCardAddress>,
+}
+
+pub fn write_vcard(
+    out: &mut String,
+    version: VCardVersion,
+    contact: &Contact,
+    addresses: &[Address],
+    emails: &[ContactEmail],
+    phones: &[ContactPhone],
+) {
+    let text = |v: &Option<String>| escape(v.as_deref().unwrap_or(""));
+    let full_name = match &contact.last_name {
+        Some(last) if !last.is_empty() => format!("{} {}", contact.first_name, last),
+        _ => contact.first_name.clone(),
+    };
+
+    write_line(out, "BEGIN:VCARD");
+    write_line(
+        out,
+        match version {
+            VCardVersion::V3 => "VERSION:3.0",
+            VCardVersion::V4 => "VERSION:4.0",
+        },
+    );
+    write_line(out, &format!("UID:urn:uuid:{}", contact.id));
+    write_line(out, &format!("N:{};{};;;", text(&contact.last_name), escape(&contact.first_name)));
+    write_line(out, &format!("FN:{}", escape(&full_name)));
+    for email in emails {
+        let params = type_params(version, &["INTERNET"], email.label, email.is_primary);
+        write_line(out, &format!("EMAIL{}:{}", params, escape(&email.email)));
+    }
+    for phone in phones {
+        let params = type_params(version, &[], phone.label, phone.is_primary);
+        // 4.0 numbers are tel: URIs; ones stored before normalization may
+        // not form one and stay text.
+        let is_e164 = phone
+            .phone
+            .strip_prefix('+')
+            .is_some_and(|digits| !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit()));
+        match version {
+            VCardVersion::V4 if is_e164 => write_line(out, &format!("TEL;VALUE=uri{}:tel:{}", params, phone.phone)),
+            VCardVersion::V4 => write_line(out, &format!("TEL;VALUE=text{}:{}", params, escape(&phone.phone))),
+            VCardVersion::V3 => write_line(out, &format!("TEL{}:{}", params, escape(&phone.phone))),
+        }
+    }
+    for address in addresses {
+        let label = match address.kind {
+            AddressKind::Home => ContactLabel::Home,
+            AddressKind::Work => ContactLabel::Work,
+            AddressKind::Other => ContactLabel::Other,
+        };
+        write_line(
+            out,
+            &format!(
+                "ADR{}:;;{};{};{};{};{}",
+                type_params(version, &[], label, address.is_primary),
+                text(&address.street),
+                text(&address.city),
+                text(&address.province),
+                text(&address.postal_code),
+                escape(&address.country),
+            ),
+        );
+    }
+    write_line(out, "END:VCARD");
+}
+
+// Parses every card in `input`. Each entry is the card or the reason it
+// could not be read, in file order.
+pub fn parse_vcards(input: &str) -> Vec<Result<VCard, String>> {
+    let mut cards = Vec::new();
+    let mut current: Option<VCard> = None;
+
+    for line in unfold(input) {
+        let Some((name, params, value)) = split_property(&line) else {
+            continue;
+        };
+
+        match (name.as_str(), current.as_mut()) {
+            ("BEGIN", _) if value.eq_ignore_ascii_case("VCARD") => {
+                let unfinished = current.replace(VCard::default());
+                cards.extend(unfinished.map(|_| Err("Card is missing END:VCARD".to_string())));
+            }
+            ("END", _) if value.eq_ignore_ascii_case("VCARD") => {
+                if let Some(card) = current.take() {
+                    cards.push(finish_card(card));
+                }
+            }
+            ("N", Some(card)) => {
+                let parts = split_components(value);
+                card.last_name = non_empty(parts.first());
+                card.first_name = non_empty(parts.get(1));
+            }
+            ("FN", Some(card)) => card.formatted_name = non_empty(Some(&unescape(value))),
+            ("EMAIL", Some(card)) => card.emails.extend(entry(&params, value)),
+            ("TEL", Some(card)) => card.phones.extend(entry(&params, tel_value(value))),
+            ("ADR", Some(card)) => {
+                // PO box; extended; street; locality; region; postal code; country
+                let parts = split_components(value);
+                let (label, preferred) = parse_types(&params);
+                card.addresses.push(VCardAddress {
+                    street: non_empty(parts.get(2)),
+                    city: non_empty(parts.get(3)),
+                    province: non_empty(parts.get(4)),
+                    postal_code: non_empty(parts.get(5)),
+                    country: non_empty(parts.get(6)),
+                    kind: match label {
+                        ContactLabel::Home => AddressKind::Home,
+                        ContactLabel::Work => AddressKind::Work,
+                        _ => AddressKind::Other,
+                    },
+                    preferred,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    if current.is_some() {
+        cards.push(Err("Card is missing END:VCARD".to_string()));
+    }
+    cards
+}
+
+// Falls back to FN when the structured name has no given name.
+fn finish_card(mut card: VCard) -> Result<VCard, String> {
+    if card.first_name.is_none() {
+        card.first_name = card.formatted_name.clone();
+        if card.first_name.is_some() {
+            card.last_name = None;
+        }
+    }
+    if card.first_name.is_none() {
+        return Err("Card has no name (N or FN)".to_string());
+    }
+    Ok(card)
+}
+
+// The number of a TEL value, which 4.0 writes as a `tel:` URI.
+fn tel_value(value: &str) -> &str {
+    match value.get(..4) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("tel:") => &value[4..],
+        _ => value,
+    }
+}
+
+fn entry(params: &[String], value: &str) -> Option<VCardEntry> {
+    let value = non_empty(Some(&unescape(value)))?;
+    let (label, preferred) = parse_types(params);
+    Some(VCardEntry { value, label, preferred })
+}
+
+// The label and preference flag of a property's parameters.
+fn parse_types(params: &[String]) -> (ContactLabel, bool) {
+    let mut label = ContactLabel::Other;
+    let mut preferred = false;
+    // `TYPE=home,pref`, `TYPE=home;TYPE=pref`, bare 2.1 `HOME` and 4.0
+    // `PREF=1` all occur in the wild.
+    for param in params {
+        let (key, values) = match param.split_once('=') {
+            Some((key, values)) => (key.trim(), values),
+            None => ("TYPE", param.as_str()),
+        };
+        if key.eq_ignore_ascii_case("PREF") {
+            preferred = true;
+            continue;
+        }
+        if !key.eq_ignore_ascii_case("TYPE") {
+            continue;
+        }
+        for kind in values.trim_matches('"').split(',') {
+            match kind.trim().to_ascii_uppercase().as_str() {
+                "HOME" => label = ContactLabel::Home,
+                "WORK" => label = ContactLabel::Work,
+                "CELL" => label = ContactLabel::Mobile,
+                "PREF" => preferred = true,
+                _ => {}
+            }
+        }
+    }
+    (label, preferred)
+}
+
+// The parameters, each with its leading `;`, marking a property's label and
+// preference: `;TYPE=WORK,PREF` in 3.0, `;TYPE=work;PREF=1` in 4.0, which
+// dropped the `pref` and `internet` types. `base` types are 3.0 only.
+fn type_params(version: VCardVersion, base: &[&str], label: ContactLabel, preferred: bool) -> String {
+    let label = match label {
+        ContactLabel::Home => Some("HOME"),
+        ContactLabel::Work => Some("WORK"),
+        ContactLabel::Mobile => Some("CELL"),
+        ContactLabel::Other => None,
+    };
+
+    match version {
+        VCardVersion::V3 => {
+            let mut types = base.to_vec();
+            types.extend(label);
+            if preferred {
+                types.push("PREF");
+            }
+            match types.is_empty() {
+                true => String::new(),
+                false => format!(";TYPE={}", types.join(",")),
+            }
+        }
+        VCardVersion::V4 => {
+            let mut params = String::new();
+            if let Some(label) = label {
+                params.push_str(&format!(";TYPE={}", label.to_ascii_lowercase()));
+            }
+            if preferred {
+                params.push_str(";PREF=1");
+            }
+            params
+        }
+    }
+}
+
+fn non_empty(value: Option<&String>) -> Option<String> {
+    value.map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+// Writes one content line, folded at 75 octets without splitting a UTF-8
+// character.
+fn write_line(out: &mut String, line: &str) {
+    let mut start = 0;
+    let mut limit = MAX_LINE_OCTETS;
+    while line.len() - start > limit {
+        let mut end = start + limit;
+        while !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        out.push_str(&line[start..end]);
+        out.push_str("\r\n ");
+        start = end;
+        // Continuation lines lose one octet to the leading space.
+        limit = MAX_LINE_OCTETS - 1;
+    }
+    out.push_str(&line[start..]);
+    out.push_str("\r\n");
+}
+
+fn unfold(input: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for raw in input.split('\n') {
+        let raw = raw.strip_suffix('\r').unwrap_or(raw);
+        match (raw.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(raw.to_string()),
+        }
+    }
+    lines
+}
+
+// Splits `[group.]NAME[;params]:value` into the upper-cased name, the raw
+// parameters and the raw value.
+fn split_property(line: &str) -> Option<(String, Vec<String>, &str)> {
+    let mut in_quotes = false;
+    let colon = line.char_indices().find_map(|(i, c)| match c {
+        '"' => {
+            in_quotes = !in_quotes;
+            None
+        }
+        ':' if !in_quotes => Some(i),
+        _ => None,
+    })?;
+
+    let (head, value) = (&line[..colon], &line[colon + 1..]);
+    let mut parts = head.split(';');
+    let name = parts.next()?.rsplit('.').next()?;
+    Some((name.trim().to_ascii_uppercase(), parts.map(str::to_string).collect(), value))
+}
+
+// Splits a structured value on unescaped `;` and unescapes each component.
+fn split_components(value: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => {
+                current.push('\\');
+                if let Some(next) = chars.next() {
+                    current.push(next);
+                }
+            }
+            ';' => parts.push(unescape(&std::mem::take(&mut current))),
+            c => current.push(c),
+        }
+    }
+    parts.push(unescape(&current));
+    parts
+}
+
+fn escape(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn unescape(value: &str) -> String {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n' | 'N')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some(next)) => {
+                unescaped.push(next);
+                chars.next();
+            }
+            (c, _) => unescaped.push(c),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn test_written_card_parses_back() {
+        let contact = Contact {
+            id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            organization_id: None,
+            first_name: "Budi".to_string(),
+            last_name: Some("Santoso; Jr.".to_string()),
+            email: Some("budi@example.com".to_string()),
+            phone: Some("+62 812 3456".to_string()),
+            phone_raw: None,
+            custom_fields: Default::default(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            version: 1,
+            deleted_at: None,
+        };
+        let address = Address {
+            id: Uuid::new_v4(),
+            contact_id: contact.id,
+            street: Some("Jl. Sudirman No. 1, Blok ".to_string() + &"A".repeat(80)),
+            city: Some("Jakarta".to_string()),
+            province: None,
+            country: "ID".to_string(),
+            postal_code: Some("10220".to_string()),
+            kind: AddressKind::Work,
+            is_primary: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        let phones = [
+            ContactPhone {
+                id: Uuid::new_v4(),
+                contact_id: contact.id,
+                phone: "+62 812 3456".to_string(),
+                phone_raw: None,
+                label: ContactLabel::Other,
+                is_primary: false,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+            ContactPhone {
+                id: Uuid::new_v4(),
+                contact_id: contact.id,
+                phone: "+62215550100".to_string(),
+                phone_raw: None,
+                label: ContactLabel::Work,
+                is_primary: true,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            },
+        ];
+
+        for version in [VCardVersion::V3, VCardVersion::V4] {
+            let mut out = String::new();
+            write_vcard(&mut out, version, &contact, std::slice::from_ref(&address), &[], &phones);
+            assert!(out.lines().all(|l| l.len() <= MAX_LINE_OCTETS));
+
+            let cards = parse_vcards(&out);
+            let card = cards[0].as_ref().unwrap();
+            assert_eq!(card.first_name.as_deref(), Some("Budi"));
+            assert_eq!(card.last_name.as_deref(), Some("Santoso; Jr."));
+            assert_eq!(card.phones[0].value, "+62 812 3456");
+            assert_eq!(card.phones[1].value, "+62215550100");
+            assert_eq!(card.phones[1].label, ContactLabel::Work);
+            assert!(!card.phones[0].preferred && card.phones[1].preferred);
+            assert_eq!(card.addresses[0].street, address.street);
+            assert_eq!(card.addresses[0].country.as_deref(), Some("ID"));
+            assert_eq!(card.addresses[0].kind, AddressKind::Work);
+            assert!(card.addresses[0].preferred);
+        }
+
+        let mut out = String::new();
+        write_vcard(&mut out, VCardVersion::V4, &contact, &[], &[], &phones);
+        assert!(out.contains("VERSION:4.0\r\n"));
+        assert!(out.contains("TEL;VALUE=uri;TYPE=work;PREF=1:tel:+62215550100\r\n"));
+    }
+
+    #[test]
+    fn test_parse_reports_each_card() {
+        let input = "BEGIN:VCARD\nVERSION:2.1\nFN:Only Formatted\nitem1.TEL;type=CELL:555-0100\nEND:VCARD\n\
+                     BEGIN:VCARD\nVERSION:4.0\nEMAIL:nobody@example.com\nEND:VCARD\n\
+                     BEGIN:VCARD\nN:Doe;Jane\n";
+
+        let cards = parse_vcards(input);
+        assert_eq!(cards.len(), 3);
+        assert_eq!(cards[0].as_ref().unwrap().first_name.as_deref(), Some("Only Formatted"));
+        assert_eq!(
+            cards[0].as_ref().unwrap().phones,
+            vec![VCardEntry { value: "555-0100".to_string(), label: ContactLabel::Mobile, preferred: false }]
+        );
+        assert!(cards[1].is_err());
+        assert!(cards[2].is_err());
+    }
+}
@@ -1,4 +1,10 @@
 use rust_clean_arcitecture::app::create_app;
+use rust_clean_arcitecture::domain::{
+    entity::{address_entity::Address, contact_entity::Contact, contact_version_entity::ContactChange},
+    error::DomainError,
+    repository::contact_repository::{ContactChanges, ContactRepository, Tenant},
+};
+use rust_clean_arcitecture::infrastructure::repository::postgres_contact_repository::PostgresContactRepository;
 use axum::{
     body::Body,
     http::{Request, StatusCode},
@@ -7,6 +13,7 @@ use http_body_util::BodyExt; // for collecting body
 use tower::ServiceExt;
 use sqlx::PgPool;
 use serde_json::{json, Value};
+use uuid::Uuid;
 
 #[sqlx::test]
 async fn test_register_user(pool: PgPool) {
@@ -100,7 +107,7 @@ async fn test_contact_flow(pool: PgPool) {
                 .method("POST")
                 .uri("/users/register")
                 .header("content-type", "application/json")
-                .body(Body::from(json!({"username": "u", "email": "u@e.com", "password": "p"}).to_string())).unwrap()
+                .body(Body::from(json!({"username": "user", "email": "u@e.com", "password": "password"}).to_string())).unwrap()
         ).await.unwrap();
 
     // 2. Login to get token
@@ -109,7 +116,7 @@ async fn test_contact_flow(pool: PgPool) {
                 .method("POST")
                 .uri("/users/login")
                 .header("content-type", "application/json")
-                .body(Body::from(json!({"email": "u@e.com", "password": "p"}).to_string())).unwrap()
+                .body(Body::from(json!({"email": "u@e.com", "password": "password"}).to_string())).unwrap()
         ).await.unwrap();
     
     let body = login_res.into_body().collect().await.unwrap().to_bytes();
@@ -127,7 +134,7 @@ async fn test_contact_flow(pool: PgPool) {
             .body(Body::from(json!({
                 "first_name": "Contact",
                 "email": "c@example.com", 
-                "phone": "123"
+                "phone": "+62 812 3456 7890"
             }).to_string())).unwrap()
         ).await.unwrap();
     
@@ -144,7 +151,7 @@ async fn test_contact_flow(pool: PgPool) {
             .header("content-type", "application/json")
             .header("Authorization", &auth_header)
             .body(Body::from(json!({
-                "country": "Indonesia",
+                "country": "ID",
                 "city": "Jakarta"
             }).to_string())).unwrap()
         ).await.unwrap();
@@ -169,3 +176,1598 @@ async fn test_contact_flow(pool: PgPool) {
    assert!(get_json["addresses"].is_array());
    assert_eq!(get_json["addresses"].as_array().unwrap().len(), 1);
 }
+
+#[sqlx::test]
+async fn test_refresh_token_rotation(pool: PgPool) {
+    let app = create_app(pool).await;
+
+    app.clone()
+        .oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/register")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"username": "user", "email": "r@e.com", "password": "password"}).to_string())).unwrap()
+        ).await.unwrap();
+
+    let login_res = app.clone().oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/login")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"email": "r@e.com", "password": "password"}).to_string())).unwrap()
+        ).await.unwrap();
+
+    let body = login_res.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    let first_refresh = body["refresh_token"].as_str().unwrap().to_string();
+
+    // Rotating the login token yields a new refresh token
+    let refresh_res = app.clone().oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/refresh")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"refresh_token": first_refresh}).to_string())).unwrap()
+        ).await.unwrap();
+
+    assert_eq!(refresh_res.status(), StatusCode::OK);
+    let body = refresh_res.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert!(body["token"].is_string());
+    let second_refresh = body["refresh_token"].as_str().unwrap().to_string();
+    assert_ne!(first_refresh, second_refresh);
+
+    // Replaying the rotated token is rejected...
+    let replay_res = app.clone().oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/refresh")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"refresh_token": first_refresh}).to_string())).unwrap()
+        ).await.unwrap();
+
+    assert_eq!(replay_res.status(), StatusCode::UNAUTHORIZED);
+
+    // ...and takes the rest of the family down with it
+    let revoked_res = app.clone().oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/refresh")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"refresh_token": second_refresh}).to_string())).unwrap()
+        ).await.unwrap();
+
+    assert_eq!(revoked_res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn test_logout_revokes_tokens(pool: PgPool) {
+    let app = create_app(pool).await;
+
+    app.clone()
+        .oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/register")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"username": "user", "email": "l@e.com", "password": "password"}).to_string())).unwrap()
+        ).await.unwrap();
+
+    let login_res = app.clone().oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/login")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"email": "l@e.com", "password": "password"}).to_string())).unwrap()
+        ).await.unwrap();
+
+    let body = login_res.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    let auth_header = format!("Bearer {}", body["token"].as_str().unwrap());
+    let refresh_token = body["refresh_token"].as_str().unwrap().to_string();
+
+    let logout_res = app.clone().oneshot(
+            Request::builder()
+            .method("POST")
+            .uri("/users/logout")
+            .header("content-type", "application/json")
+            .header("Authorization", &auth_header)
+            .body(Body::from(json!({"refresh_token": refresh_token}).to_string())).unwrap()
+        ).await.unwrap();
+
+    assert_eq!(logout_res.status(), StatusCode::NO_CONTENT);
+
+    // The access token no longer works
+    let contacts_res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri("/contacts")
+            .header("Authorization", &auth_header)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+
+    assert_eq!(contacts_res.status(), StatusCode::UNAUTHORIZED);
+
+    // Nor can it be refreshed
+    let refresh_res = app.clone().oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/refresh")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"refresh_token": refresh_token}).to_string())).unwrap()
+        ).await.unwrap();
+
+    assert_eq!(refresh_res.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[sqlx::test]
+async fn test_login_right_after_logout_all(pool: PgPool) {
+    let app = create_app(pool).await;
+    let old_auth = register_and_login(&app, "la@e.com").await;
+
+    let logout_res = app.clone().oneshot(
+            Request::builder()
+            .method("POST")
+            .uri("/users/logout-all")
+            .header("Authorization", &old_auth)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    assert_eq!(logout_res.status(), StatusCode::NO_CONTENT);
+
+    // Usually issued within the same second as the cutoff
+    let new_auth = register_and_login(&app, "la@e.com").await;
+
+    let (old_status, _) = get_json(&app, &old_auth, "/contacts").await;
+    assert_eq!(old_status, StatusCode::UNAUTHORIZED);
+    let (new_status, _) = get_json(&app, &new_auth, "/contacts").await;
+    assert_eq!(new_status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_jwks_endpoint(pool: PgPool) {
+    let app = create_app(pool).await;
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("GET")
+                .uri("/.well-known/jwks.json")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert!(body["keys"].is_array());
+}
+
+async fn register_and_login(app: &axum::Router, email: &str) -> String {
+    app.clone()
+        .oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/register")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"username": "user", "email": email, "password": "password"}).to_string())).unwrap()
+        ).await.unwrap();
+
+    let login_res = app.clone().oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/login")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"email": email, "password": "password"}).to_string())).unwrap()
+        ).await.unwrap();
+
+    let body = login_res.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    format!("Bearer {}", body["token"].as_str().unwrap())
+}
+
+#[sqlx::test]
+async fn test_error_status_codes(pool: PgPool) {
+    let app = create_app(pool).await;
+    let owner = register_and_login(&app, "owner@e.com").await;
+    let other = register_and_login(&app, "other@e.com").await;
+
+    // Duplicate registration is a conflict
+    let dup_res = app.clone().oneshot(
+             Request::builder()
+                .method("POST")
+                .uri("/users/register")
+                .header("content-type", "application/json")
+                .body(Body::from(json!({"username": "user", "email": "owner@e.com", "password": "password"}).to_string())).unwrap()
+        ).await.unwrap();
+    assert_eq!(dup_res.status(), StatusCode::CONFLICT);
+
+    let create_res = app.clone().oneshot(
+            Request::builder()
+            .method("POST")
+            .uri("/contacts")
+            .header("content-type", "application/json")
+            .header("Authorization", &owner)
+            .body(Body::from(json!({"first_name": "Contact"}).to_string())).unwrap()
+        ).await.unwrap();
+    let c_body = create_res.into_body().collect().await.unwrap().to_bytes();
+    let c_json: Value = serde_json::from_slice(&c_body).unwrap();
+    let contact_id = c_json["id"].as_str().unwrap();
+
+    // Someone else's contact does not exist for other users
+    let foreign_res = app.clone().oneshot(
+            Request::builder()
+            .method("PUT")
+            .uri(format!("/contacts/{}", contact_id))
+            .header("content-type", "application/json")
+            .header("Authorization", &other)
+            .body(Body::from(json!({"first_name": "Stolen"}).to_string())).unwrap()
+        ).await.unwrap();
+    assert_eq!(foreign_res.status(), StatusCode::NOT_FOUND);
+
+    // A contact that does not exist is not found
+    let missing_res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri(format!("/contacts/{}", uuid::Uuid::new_v4()))
+            .header("Authorization", &owner)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    assert_eq!(missing_res.status(), StatusCode::NOT_FOUND);
+
+    // Invalid input is a validation error
+    let invalid_res = app.clone().oneshot(
+            Request::builder()
+            .method("POST")
+            .uri("/contacts")
+            .header("content-type", "application/json")
+            .header("Authorization", &owner)
+            .header("x-request-id", "req-123")
+            .body(Body::from(json!({"first_name": "", "email": "nope"}).to_string())).unwrap()
+        ).await.unwrap();
+    assert_eq!(invalid_res.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(invalid_res.headers()["content-type"], "application/problem+json");
+    assert_eq!(invalid_res.headers()["x-request-id"], "req-123");
+
+    let body = invalid_res.into_body().collect().await.unwrap().to_bytes();
+    let body: Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(body["type"], "/problems/validation-error");
+    assert_eq!(body["status"], 400);
+    assert_eq!(body["request_id"], "req-123");
+    assert_eq!(body["errors"]["first_name"][0], "First name is required");
+    assert_eq!(body["errors"]["email"][0], "Invalid email format");
+}
+
+#[sqlx::test]
+async fn test_malformed_json_body_is_problem_json(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth = register_and_login(&app, "mj@e.com").await;
+
+    let post = |body: &'static str, content_type: Option<&'static str>| {
+        let app = app.clone();
+        let auth = auth.clone();
+        async move {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri("/contacts")
+                .header("Authorization", &auth);
+            if let Some(content_type) = content_type {
+                builder = builder.header("content-type", content_type);
+            }
+            let res = app.oneshot(builder.body(Body::from(body)).unwrap()).await.unwrap();
+            let status = res.status();
+            assert_eq!(res.headers()["content-type"], "application/problem+json");
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            (status, serde_json::from_slice::<Value>(&body).unwrap())
+        }
+    };
+
+    let (status, body) = post(r#"{"first_name": 1}"#, Some("application/json")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "/problems/validation-error");
+    assert!(body["errors"]["first_name"][0].as_str().unwrap().starts_with("invalid type: integer `1`"));
+
+    let (status, body) = post(r#"{"first_name": "Jo", "emails": [{"label": "work"}]}"#, Some("application/json")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"]["emails[0].email"][0], "Field is required");
+
+    let (status, body) = post(r#"{"first_name": "#, Some("application/json")).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["type"], "/problems/validation-error");
+
+    let (status, _) = post(r#"{"first_name": "Jo"}"#, None).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+}
+
+#[sqlx::test]
+async fn test_protected_routes_require_token(pool: PgPool) {
+    let app = create_app(pool).await;
+
+    let missing_res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri("/contacts")
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    assert_eq!(missing_res.status(), StatusCode::UNAUTHORIZED);
+    assert_eq!(missing_res.headers()["content-type"], "application/problem+json");
+
+    let garbage_res = app.clone().oneshot(
+            Request::builder()
+            .method("POST")
+            .uri("/users/logout-all")
+            .header("Authorization", "Bearer not-a-jwt")
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    assert_eq!(garbage_res.status(), StatusCode::UNAUTHORIZED);
+
+    let auth_header = register_and_login(&app, "p@e.com").await;
+    let ok_res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri("/contacts")
+            .header("Authorization", &auth_header)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    assert_eq!(ok_res.status(), StatusCode::OK);
+}
+
+async fn create_contact(app: &axum::Router, auth_header: &str, payload: Value) -> Value {
+    let res = app.clone().oneshot(
+            Request::builder()
+            .method("POST")
+            .uri("/contacts")
+            .header("content-type", "application/json")
+            .header("Authorization", auth_header)
+            .body(Body::from(payload.to_string())).unwrap()
+        ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::CREATED);
+
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    serde_json::from_slice(&body).unwrap()
+}
+
+async fn get_json(app: &axum::Router, auth_header: &str, uri: &str) -> (StatusCode, Value) {
+    let res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri(uri)
+            .header("Authorization", auth_header)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    let status = res.status();
+
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[sqlx::test]
+async fn test_list_contacts_pagination_and_filters(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "list@e.com").await;
+
+    for (first, email) in [("Dave", "dave@work.com"), ("Alice", "alice@home.com"), ("Carol", "carol@work.com"), ("Bob", "bob@home.com"), ("Eve", "eve@work.com")] {
+        create_contact(&app, &auth_header, json!({"first_name": first, "email": email})).await;
+    }
+
+    // Offset pagination
+    let (status, page) = get_json(&app, &auth_header, "/contacts?sort=first_name&order=asc&limit=2&offset=2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 5);
+    assert_eq!(page["offset"], 2);
+    let names: Vec<&str> = page["data"].as_array().unwrap().iter().map(|c| c["first_name"].as_str().unwrap()).collect();
+    assert_eq!(names.len(), 2);
+
+    // Keyset pagination walks every contact exactly once
+    let mut seen = Vec::new();
+    let mut uri = "/contacts?sort=first_name&order=desc&limit=2".to_string();
+    loop {
+        let (status, page) = get_json(&app, &auth_header, &uri).await;
+        assert_eq!(status, StatusCode::OK);
+        for c in page["data"].as_array().unwrap() {
+            seen.push(c["first_name"].as_str().unwrap().to_string());
+        }
+        match page["next_cursor"].as_str() {
+            Some(cursor) => uri = format!("/contacts?sort=first_name&order=desc&limit=2&cursor={}", cursor),
+            None => break,
+        }
+    }
+    assert_eq!(seen.len(), 5);
+    let mut sorted = seen.clone();
+    sorted.sort();
+    sorted.reverse();
+    assert_eq!(seen, sorted);
+
+    // Filters narrow both the rows and the total
+    let (_, page) = get_json(&app, &auth_header, "/contacts?email=WORK&name=a").await;
+    assert_eq!(page["total"], 2);
+
+    // Bad parameters are validation errors
+    let (status, _) = get_json(&app, &auth_header, "/contacts?limit=1000").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get_json(&app, &auth_header, "/contacts?sort=shoe_size").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, _) = get_json(&app, &auth_header, "/contacts?cursor=garbage").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_search_contacts_ranked_and_typo_tolerant(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "search@e.com").await;
+
+    let jakarta = create_contact(&app, &auth_header, json!({"first_name": "Budi", "email": "budi@mail.com"})).await;
+    let contact_id = jakarta["id"].as_str().unwrap();
+    send_json(&app, "POST", &format!("/contacts/{}/addresses", contact_id), &auth_header, Some(json!({"city": "Jakarta", "country": "ID"}))).await;
+    create_contact(&app, &auth_header, json!({"first_name": "Sarah", "last_name": "Connor", "email": "sarah@budi.co"})).await;
+    create_contact(&app, &auth_header, json!({"first_name": "Unrelated"})).await;
+
+    // A name hit outranks a hit on a lower-weighted field
+    let (status, page) = get_json(&app, &auth_header, "/contacts?q=budi").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["data"][0]["first_name"], "Budi");
+    assert!(page["data"][0]["search"]["snippet"].as_str().unwrap().contains("<mark>Budi</mark>"));
+    assert!(page["data"][0]["search"]["rank"].as_f64().unwrap() > page["data"][1]["search"]["rank"].as_f64().unwrap());
+
+    // Typos still match, including on address fields
+    let (_, page) = get_json(&app, &auth_header, "/contacts?q=Jakrta").await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["data"][0]["id"], contact_id);
+    assert_eq!(page["data"][0]["addresses"][0]["city"], "Jakarta");
+
+    let (_, page) = get_json(&app, &auth_header, "/contacts?q=Conor").await;
+    assert_eq!(page["data"][0]["last_name"], "Connor");
+
+    // Relevance ordering cannot be combined with an explicit sort
+    let (status, _) = get_json(&app, &auth_header, "/contacts?q=budi&sort=first_name").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_tags_and_groups(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "tags@e.com").await;
+
+    let alice = create_contact(&app, &auth_header, json!({"first_name": "Alice"})).await;
+    let bob = create_contact(&app, &auth_header, json!({"first_name": "Bob"})).await;
+    let alice_id = alice["id"].as_str().unwrap();
+    let bob_id = bob["id"].as_str().unwrap();
+
+    let (status, tag) = send_json(&app, "POST", "/tags", &auth_header, Some(json!({"name": "VIP"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let tag_id = tag["id"].as_str().unwrap();
+    let (status, _) = send_json(&app, "POST", "/tags", &auth_header, Some(json!({"name": "vip"}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    let (status, _) = send_json(&app, "PUT", &format!("/contacts/{}/tags/{}", alice_id, tag_id), &auth_header, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, contact) = get_json(&app, &auth_header, &format!("/contacts/{}", alice_id)).await;
+    assert_eq!(contact["tags"], json!(["VIP"]));
+
+    let (status, group) = send_json(&app, "POST", "/groups", &auth_header, Some(json!({"name": "Family"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let group_id = group["id"].as_str().unwrap();
+    let (status, _) = send_json(&app, "PUT", &format!("/groups/{}/contacts/{}", group_id, bob_id), &auth_header, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    // Filters
+    let (_, page) = get_json(&app, &auth_header, "/contacts?tag=vip").await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["data"][0]["id"], alice_id);
+    let (_, page) = get_json(&app, &auth_header, &format!("/contacts?group={}", group_id)).await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["data"][0]["id"], bob_id);
+
+    // Another user can neither see nor use these
+    let other_auth = register_and_login(&app, "tags-other@e.com").await;
+    let (status, _) = get_json(&app, &other_auth, &format!("/tags/{}", tag_id)).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (_, tags) = get_json(&app, &other_auth, "/tags").await;
+    assert_eq!(tags, json!([]));
+
+    // Deleting a tag detaches it from contacts
+    let (status, _) = send_json(&app, "DELETE", &format!("/tags/{}", tag_id), &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, contact) = get_json(&app, &auth_header, &format!("/contacts/{}", alice_id)).await;
+    assert_eq!(contact["tags"], json!([]));
+}
+
+#[sqlx::test]
+async fn test_trash_restore_and_purge(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "trash@e.com").await;
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Oops"})).await;
+    let contact_id = contact["id"].as_str().unwrap();
+    send_json(&app, "POST", &format!("/contacts/{}/addresses", contact_id), &auth_header, Some(json!({"city": "Bandung", "country": "ID"}))).await;
+
+    let (status, _) = send_json(&app, "DELETE", &format!("/contacts/{}", contact_id), &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Hidden from normal reads, visible in the trash with its addresses
+    let (status, _) = get_json(&app, &auth_header, &format!("/contacts/{}", contact_id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, page) = get_json(&app, &auth_header, "/contacts").await;
+    assert_eq!(page["total"], 0);
+    let (status, trash) = get_json(&app, &auth_header, "/contacts/trash").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(trash[0]["id"], contact_id);
+    assert!(trash[0]["deleted_at"].is_string());
+    assert_eq!(trash[0]["addresses"][0]["city"], "Bandung");
+
+    let (status, restored) = send_json(&app, "POST", &format!("/contacts/{}/restore", contact_id), &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(restored.get("deleted_at").is_none());
+    assert_eq!(restored["addresses"][0]["city"], "Bandung");
+
+    // Only trashed contacts can be purged
+    let purge_uri = format!("/contacts/trash/{}", contact_id);
+    let (status, _) = send_json(&app, "DELETE", &purge_uri, &auth_header, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    send_json(&app, "DELETE", &format!("/contacts/{}", contact_id), &auth_header, None).await;
+    let (status, _) = send_json(&app, "DELETE", &purge_uri, &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, trash) = get_json(&app, &auth_header, "/contacts/trash").await;
+    assert_eq!(trash, json!([]));
+}
+
+#[sqlx::test]
+async fn test_contact_history_and_revert(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "history@e.com").await;
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Jane", "email": "jane@old.com"})).await;
+    let contact_id = contact["id"].as_str().unwrap();
+    let uri = format!("/contacts/{}", contact_id);
+    let (_, address) = send_json(&app, "POST", &format!("{}/addresses", uri), &auth_header, Some(json!({"city": "Bandung", "country": "ID"}))).await;
+    send_json(&app, "PUT", &uri, &auth_header, Some(json!({"email": "jane@new.com"}))).await;
+    send_json(&app, "DELETE", &format!("{}/addresses/{}", uri, address["id"].as_str().unwrap()), &auth_header, None).await;
+
+    let (status, history) = get_json(&app, &auth_header, &format!("{}/history", uri)).await;
+    assert_eq!(status, StatusCode::OK);
+    let actions: Vec<&str> = history.as_array().unwrap().iter().map(|v| v["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, vec!["created", "address_created", "updated", "address_deleted"]);
+    assert_eq!(history[2]["version"], 3);
+    let email_id = contact["emails"][0]["id"].as_str().unwrap();
+    assert_eq!(history[2]["changes"], json!([{"field": format!("emails[{}].email", email_id), "old": "jane@old.com", "new": "jane@new.com"}]));
+    assert!(history[0]["actor_id"].is_string());
+
+    // Version 2 had the old email and the address
+    let (status, reverted) = send_json(&app, "POST", &format!("{}/revert/2", uri), &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(reverted["email"], "jane@old.com");
+    assert_eq!(reverted["addresses"][0]["id"], address["id"]);
+
+    let (_, history) = get_json(&app, &auth_header, &format!("{}/history", uri)).await;
+    assert_eq!(history[4]["action"], "reverted");
+
+    let (status, _) = send_json(&app, "POST", &format!("{}/revert/99", uri), &auth_header, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_contact_etag_and_if_match(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "etag@e.com").await;
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Jane"})).await;
+    let uri = format!("/contacts/{}", contact["id"].as_str().unwrap());
+
+    let res = app.clone().oneshot(
+        Request::builder().uri(&uri).header("Authorization", &auth_header).body(Body::empty()).unwrap()
+    ).await.unwrap();
+    let etag = res.headers()["etag"].to_str().unwrap().to_string();
+    assert_eq!(etag, "\"1\"");
+
+    let conditional_put = |if_match: &str, name: &str| {
+        Request::builder()
+            .method("PUT")
+            .uri(&uri)
+            .header("Authorization", &auth_header)
+            .header("Content-Type", "application/json")
+            .header("If-Match", if_match)
+            .body(Body::from(json!({"first_name": name}).to_string()))
+            .unwrap()
+    };
+
+    // First writer wins and gets the new tag
+    let res = app.clone().oneshot(conditional_put(&etag, "Janet")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()["etag"], "\"2\"");
+
+    // Second writer still holds the old tag
+    let res = app.clone().oneshot(conditional_put(&etag, "Jenny")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+    let res = app.clone().oneshot(conditional_put("W/\"2\"", "Jenny")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+
+    // Stale delete is refused, `*` matches any version
+    let delete = |if_match: &str| {
+        Request::builder()
+            .method("DELETE")
+            .uri(&uri)
+            .header("Authorization", &auth_header)
+            .header("If-Match", if_match)
+            .body(Body::empty())
+            .unwrap()
+    };
+    let res = app.clone().oneshot(delete(&etag)).await.unwrap();
+    assert_eq!(res.status(), StatusCode::PRECONDITION_FAILED);
+    let res = app.clone().oneshot(delete("*")).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_history_versions_follow_etag(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "history-etag@e.com").await;
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Jane"})).await;
+    let uri = format!("/contacts/{}", contact["id"].as_str().unwrap());
+    send_json(&app, "POST", &format!("{}/emails", uri), &auth_header, Some(json!({"email": "jane@e.com"}))).await;
+    send_json(&app, "PUT", &uri, &auth_header, Some(json!({"first_name": "Janet"}))).await;
+    send_json(&app, "DELETE", &uri, &auth_header, None).await;
+    send_json(&app, "POST", &format!("{}/restore", uri), &auth_header, None).await;
+
+    let res = app.clone().oneshot(
+        Request::builder().uri(&uri).header("Authorization", &auth_header).body(Body::empty()).unwrap()
+    ).await.unwrap();
+    assert_eq!(res.headers()["etag"], "\"5\"");
+
+    // Every entry carries the ETag the contact had right after that change
+    let (_, history) = get_json(&app, &auth_header, &format!("{}/history", uri)).await;
+    let versions: Vec<i64> = history.as_array().unwrap().iter().map(|v| v["version"].as_i64().unwrap()).collect();
+    assert_eq!(versions, vec![1, 2, 3, 4, 5]);
+    assert_eq!(history[3]["action"], "deleted");
+
+    // Reverting to the version an ETag names restores that state
+    let (status, reverted) = send_json(&app, "POST", &format!("{}/revert/2", uri), &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(reverted["first_name"], "Jane");
+    assert_eq!(reverted["version"], 6);
+}
+
+#[sqlx::test]
+async fn test_concurrent_updates_apply_whole(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "concurrent@e.com").await;
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Jane", "email": "jane@e.com"})).await;
+    let uri = format!("/contacts/{}", contact["id"].as_str().unwrap());
+
+    // Each write changes the name and the primary email together
+    let (first, second) = tokio::join!(
+        send_json(&app, "PUT", &uri, &auth_header, Some(json!({"first_name": "Ann", "email": "ann@e.com"}))),
+        send_json(&app, "PUT", &uri, &auth_header, Some(json!({"first_name": "Bea", "email": "bea@e.com"}))),
+    );
+    let saved = [first.0, second.0].iter().filter(|s| **s == StatusCode::OK).count() as i64;
+    assert!(saved >= 1);
+    for status in [first.0, second.0] {
+        assert!(status == StatusCode::OK || status == StatusCode::PRECONDITION_FAILED);
+    }
+
+    // Whichever won, its name and email were saved together, once
+    let (_, after) = get_json(&app, &auth_header, &uri).await;
+    let expected_email = format!("{}@e.com", after["first_name"].as_str().unwrap().to_lowercase());
+    assert_eq!(after["email"], expected_email.as_str());
+    assert_eq!(after["emails"].as_array().unwrap().len(), 1);
+    assert_eq!(after["version"], 1 + saved);
+    // and recorded in history with the write, under the version it produced
+    let (_, history) = get_json(&app, &auth_header, &format!("{}/history", uri)).await;
+    let versions: Vec<i64> = history.as_array().unwrap().iter().map(|v| v["version"].as_i64().unwrap()).collect();
+    assert_eq!(versions, (1..=1 + saved).collect::<Vec<_>>());
+    assert_eq!(history.as_array().unwrap().last().unwrap()["changes"].as_array().unwrap().len(), 2);
+}
+
+async fn patch_contact(app: &axum::Router, uri: &str, auth_header: &str, content_type: &str, patch: Value) -> (StatusCode, Value) {
+    let req = Request::builder()
+        .method("PATCH")
+        .uri(uri)
+        .header("Authorization", auth_header)
+        .header("Content-Type", content_type)
+        .body(Body::from(patch.to_string()))
+        .unwrap();
+
+    let res = app.clone().oneshot(req).await.unwrap();
+    let status = res.status();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[sqlx::test]
+async fn test_patch_contact(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "patch@e.com").await;
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Jane", "last_name": "Doe", "email": "jane@e.com", "phone": "+62 812 3456 7890"})).await;
+    let uri = format!("/contacts/{}", contact["id"].as_str().unwrap());
+
+    // Merge patch: null clears, absent keeps
+    let (status, body) = patch_contact(&app, &uri, &auth_header, "application/merge-patch+json", json!({"email": null, "phone": "+62 813 1111 2222"})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["email"], Value::Null);
+    assert_eq!(body["phone"], "+6281311112222");
+    assert_eq!(body["last_name"], "Doe");
+
+    // JSON Patch
+    let ops = json!([
+        {"op": "test", "path": "/last_name", "value": "Doe"},
+        {"op": "remove", "path": "/last_name"},
+        {"op": "replace", "path": "/first_name", "value": "Janet"}
+    ]);
+    let (status, body) = patch_contact(&app, &uri, &auth_header, "application/json-patch+json", ops).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["first_name"], "Janet");
+    assert_eq!(body["last_name"], Value::Null);
+
+    // A failing test op leaves the contact untouched
+    let ops = json!([{"op": "test", "path": "/first_name", "value": "Jane"}, {"op": "replace", "path": "/first_name", "value": "X"}]);
+    let (status, _) = patch_contact(&app, &uri, &auth_header, "application/json-patch+json", ops).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Same rules as create
+    let (status, body) = patch_contact(&app, &uri, &auth_header, "application/merge-patch+json", json!({"first_name": null, "email": "nope"})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"]["first_name"][0], "First name is required");
+    let (status, body) = patch_contact(&app, &uri, &auth_header, "application/merge-patch+json", json!({"email": "nope"})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(body["errors"]["email"][0], "Invalid email format");
+    let (status, _) = patch_contact(&app, &uri, &auth_header, "application/merge-patch+json", json!({"nickname": "JJ"})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, _) = patch_contact(&app, &uri, &auth_header, "application/json", json!({"phone": null})).await;
+    assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let (_, current) = get_json(&app, &auth_header, &uri).await;
+    assert_eq!(current["first_name"], "Janet");
+    assert_eq!(current["phone"], "+6281311112222");
+}
+
+#[sqlx::test]
+async fn test_vcard_export_and_import(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "vcard@e.com").await;
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Budi", "last_name": "Santoso", "email": "budi@example.com"})).await;
+    let contact_id = contact["id"].as_str().unwrap();
+    send_json(&app, "POST", &format!("/contacts/{}/addresses", contact_id), &auth_header, Some(json!({"street": "Jl. Sudirman 1", "city": "Jakarta", "country": "ID"}))).await;
+
+    let res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri(format!("/contacts/{}.vcf", contact_id))
+            .header("Authorization", &auth_header)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers()["content-type"].to_str().unwrap().starts_with("text/vcard"));
+    let card = String::from_utf8(res.into_body().collect().await.unwrap().to_bytes().to_vec()).unwrap();
+    assert!(card.contains("N:Santoso;Budi;;;\r\n"));
+    assert!(card.contains("ADR;TYPE=PREF:;;Jl. Sudirman 1;Jakarta;;;ID\r\n"));
+
+    // One good card, one without a name, one with an invalid email, and one
+    // whose name the database rejects.
+    let upload = format!(
+        "{}BEGIN:VCARD\r\nVERSION:3.0\r\nTEL:555\r\nEND:VCARD\r\nBEGIN:VCARD\r\nVERSION:3.0\r\nFN:Bad Email\r\nEMAIL:not-an-email\r\nEND:VCARD\r\nBEGIN:VCARD\r\nVERSION:3.0\r\nFN:{}\r\nEND:VCARD\r\n",
+        card.replace("budi@example.com", "budi2@example.com"),
+        "B".repeat(101)
+    );
+    let res = app.clone().oneshot(
+            Request::builder()
+            .method("POST")
+            .uri("/contacts/import")
+            .header("content-type", "text/vcard")
+            .header("Authorization", &auth_header)
+            .body(Body::from(upload)).unwrap()
+        ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    let report: Value = serde_json::from_slice(&res.into_body().collect().await.unwrap().to_bytes()).unwrap();
+    assert_eq!(report["created"], 1);
+    assert_eq!(report["failed"], 3);
+    assert_eq!(report["results"][1]["error"], "Card has no name (N or FN)");
+    assert!(report["results"][2]["errors"]["emails[0].email"].is_array());
+    assert_eq!(report["results"][3]["error"], "Internal error");
+
+    let imported_id = report["results"][0]["contact_id"].as_str().unwrap();
+    let (_, imported) = get_json(&app, &auth_header, &format!("/contacts/{}", imported_id)).await;
+    assert_eq!(imported["email"], "budi2@example.com");
+    assert_eq!(imported["addresses"][0]["city"], "Jakarta");
+
+    let (status, _) = get_json(&app, &auth_header, "/contacts/not-a-uuid.vcf").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri("/contacts/export.vcf")
+            .header("Authorization", &auth_header)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    let all = String::from_utf8(res.into_body().collect().await.unwrap().to_bytes().to_vec()).unwrap();
+    assert_eq!(all.matches("BEGIN:VCARD").count(), 2);
+    assert_eq!(all.matches("VERSION:3.0\r\n").count(), 2);
+
+    let res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri(format!("/contacts/{}.vcf?version=4.0", contact_id))
+            .header("Authorization", &auth_header)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    let card = String::from_utf8(res.into_body().collect().await.unwrap().to_bytes().to_vec()).unwrap();
+    assert!(card.contains("VERSION:4.0\r\n"));
+    assert!(card.contains("EMAIL;PREF=1:budi@example.com\r\n"));
+    assert!(card.contains("ADR;PREF=1:;;Jl. Sudirman 1;Jakarta;;;ID\r\n"));
+
+    let (status, _) = get_json(&app, &auth_header, "/contacts/export.vcf?version=2.1").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn test_csv_import_and_export(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "csv@e.com").await;
+
+    let upload = "Given Name,Family Name,E-mail,City,Country\n\
+                  Budi,Santoso,budi@example.com,Jakarta,ID\n\
+                  Siti,,not-an-email,Bandung,\n\
+                  Andi,Wijaya,,,\n";
+    let import = |query: &'static str| {
+        let app = app.clone();
+        let auth_header = auth_header.clone();
+        async move {
+            let res = app.oneshot(
+                    Request::builder()
+                    .method("POST")
+                    .uri(format!("/contacts/import/csv?first_name=Given%20Name&last_name=Family%20Name&email=E-mail{}", query))
+                    .header("content-type", "text/csv")
+                    .header("Authorization", &auth_header)
+                    .body(Body::from(upload)).unwrap()
+                ).await.unwrap();
+            let status = res.status();
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            (status, serde_json::from_slice::<Value>(&body).unwrap())
+        }
+    };
+
+    // Dry run reports per-row validation errors and writes nothing.
+    let (status, report) = import("&dry_run=true").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["dry_run"], true);
+    assert_eq!(report["created"], 2);
+    assert_eq!(report["failed"], 1);
+    assert!(report["results"][0].get("contact_id").is_none());
+    assert!(report["results"][1]["errors"]["email"].is_array());
+    assert!(report["results"][1]["errors"]["addresses[0].country"].is_array());
+    let (_, page) = get_json(&app, &auth_header, "/contacts").await;
+    assert_eq!(page["total"], 0);
+
+    let (status, report) = import("").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(report["created"], 2);
+    let (_, page) = get_json(&app, &auth_header, "/contacts?sort=first_name").await;
+    assert_eq!(page["total"], 2);
+    assert_eq!(page["data"][1]["addresses"][0]["city"], "Jakarta");
+
+    // A mapping that names a missing column rejects the upload.
+    let (status, problem) = import("&phone=Mobile").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["phone"].is_array());
+
+    let res = app.clone().oneshot(
+            Request::builder()
+            .method("GET")
+            .uri("/contacts/export.csv")
+            .header("Authorization", &auth_header)
+            .body(Body::empty()).unwrap()
+        ).await.unwrap();
+    assert_eq!(res.status(), StatusCode::OK);
+    assert!(res.headers()["content-type"].to_str().unwrap().starts_with("text/csv"));
+    let csv = String::from_utf8(res.into_body().collect().await.unwrap().to_bytes().to_vec()).unwrap();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "first_name,last_name,email,phone,street,city,province,postal_code,country");
+    assert_eq!(lines[1], "Budi,Santoso,budi@example.com,,,Jakarta,,,ID");
+    assert_eq!(lines[2], "Andi,Wijaya,,,,,,,");
+}
+
+#[sqlx::test]
+async fn test_csv_dry_run_checks_phone_like_import(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "csvphone@e.com").await;
+
+    let import = |query: &'static str| {
+        let app = app.clone();
+        let auth_header = auth_header.clone();
+        async move {
+            let res = app.oneshot(
+                    Request::builder()
+                    .method("POST")
+                    .uri(format!("/contacts/import/csv?first_name=Name&phone=Phone{}", query))
+                    .header("content-type", "text/csv")
+                    .header("Authorization", &auth_header)
+                    .body(Body::from("Name,Phone\nBudi,0812-3456-7890\n")).unwrap()
+                ).await.unwrap();
+            let body = res.into_body().collect().await.unwrap().to_bytes();
+            serde_json::from_slice::<Value>(&body).unwrap()
+        }
+    };
+
+    // A national number needs the user's region, in a dry run too.
+    let dry_run = import("&dry_run=true").await;
+    let real = import("").await;
+    assert_eq!(dry_run["failed"], 1);
+    assert_eq!(dry_run["results"][0]["errors"], real["results"][0]["errors"]);
+    assert!(dry_run["results"][0]["errors"]["phone"].is_array());
+
+    let (status, _) = send_json(&app, "PATCH", "/users/me", &auth_header, Some(json!({"phone_region": "ID"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    let dry_run = import("&dry_run=true").await;
+    assert_eq!(dry_run["created"], 1);
+}
+
+#[sqlx::test]
+async fn test_find_and_merge_duplicates(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "dupes@e.com").await;
+
+    let target = create_contact(&app, &auth_header, json!({"first_name": "Budi", "last_name": "Santoso", "email": "budi@example.com"})).await;
+    let source = create_contact(&app, &auth_header, json!({"first_name": "B.", "email": "Budi@Example.com", "phone": "+62 812-3456-7890"})).await;
+    create_contact(&app, &auth_header, json!({"first_name": "Andi", "email": "andi@example.com"})).await;
+    let target_id = target["id"].as_str().unwrap();
+    let source_id = source["id"].as_str().unwrap();
+
+    send_json(&app, "POST", &format!("/contacts/{}/addresses", source_id), &auth_header, Some(json!({"city": "Jakarta", "country": "ID"}))).await;
+    let (_, tag) = send_json(&app, "POST", "/tags", &auth_header, Some(json!({"name": "vip"}))).await;
+    let (status, _) = send_json(&app, "PUT", &format!("/contacts/{}/tags/{}", source_id, tag["id"].as_str().unwrap()), &auth_header, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+
+    let (status, groups) = get_json(&app, &auth_header, "/contacts/duplicates").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(groups.as_array().unwrap().len(), 1);
+    assert_eq!(groups[0]["reasons"], json!(["email"]));
+    assert_eq!(groups[0]["confidence"], 0.95);
+    assert_eq!(groups[0]["contacts"].as_array().unwrap().len(), 2);
+
+    let (status, _) = send_json(&app, "POST", "/contacts/merge", &auth_header, Some(json!({"target_id": target_id, "source_ids": [target_id]}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (status, merged) = send_json(&app, "POST", "/contacts/merge", &auth_header, Some(json!({"target_id": target_id, "source_ids": [source_id]}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(merged["email"], "budi@example.com");
+    assert_eq!(merged["phone"], "+6281234567890");
+    assert_eq!(merged["addresses"][0]["city"], "Jakarta");
+    assert_eq!(merged["tags"], json!(["vip"]));
+
+    let (status, _) = get_json(&app, &auth_header, &format!("/contacts/{}", source_id)).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (_, history) = get_json(&app, &auth_header, &format!("/contacts/{}/history", target_id)).await;
+    assert!(history.as_array().unwrap().iter().any(|v| v["action"] == "merged"));
+    let (_, groups) = get_json(&app, &auth_header, "/contacts/duplicates").await;
+    assert!(groups.as_array().unwrap().is_empty());
+
+    // The source went to the trash, keeping its history
+    let (_, trash) = get_json(&app, &auth_header, "/contacts/trash").await;
+    assert_eq!(trash[0]["id"], source_id);
+    let (_, history) = get_json(&app, &auth_header, &format!("/contacts/{}/history", source_id)).await;
+    let actions: Vec<&str> = history.as_array().unwrap().iter().map(|v| v["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, vec!["created", "address_created", "merged_into"]);
+}
+
+#[sqlx::test]
+async fn test_merge_checks_custom_fields(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "merge-fields@e.com").await;
+
+    let (_, tier) = send_json(&app, "POST", "/custom-fields", &auth_header, Some(json!({"name": "tier", "type": "enum", "enum_values": ["bronze", "gold"]}))).await;
+    let target = create_contact(&app, &auth_header, json!({"first_name": "Budi"})).await;
+    let source = create_contact(&app, &auth_header, json!({"first_name": "Budi", "custom_fields": {"tier": "bronze"}})).await;
+    let (status, _) = send_json(&app, "PATCH", &format!("/custom-fields/{}", tier["id"].as_str().unwrap()), &auth_header, Some(json!({"enum_values": ["gold"]}))).await;
+    assert_eq!(status, StatusCode::OK);
+
+    // The source's value is no longer allowed, so the target cannot take it
+    let merge = json!({"target_id": target["id"], "source_ids": [source["id"]]});
+    let (status, problem) = send_json(&app, "POST", "/contacts/merge", &auth_header, Some(merge)).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["custom_fields.tier"].is_array());
+    let (status, _) = get_json(&app, &auth_header, &format!("/contacts/{}", source["id"].as_str().unwrap())).await;
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[sqlx::test]
+async fn test_phone_numbers_are_normalized(pool: PgPool) {
+    let app = create_app(pool.clone()).await;
+    let auth_header = register_and_login(&app, "phone@e.com").await;
+
+    // Without a region only international numbers are accepted.
+    let (status, problem) = send_json(&app, "POST", "/contacts", &auth_header, Some(json!({"first_name": "Budi", "phone": "0812-3456-7890"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["phone"].is_array());
+
+    let (status, _) = send_json(&app, "PATCH", "/users/me", &auth_header, Some(json!({"phone_region": "XX"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, me) = send_json(&app, "PATCH", "/users/me", &auth_header, Some(json!({"phone_region": "id"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(me["phone_region"], "ID");
+    let (_, me) = send_json(&app, "PATCH", "/users/me", &auth_header, Some(json!({}))).await;
+    assert_eq!(me["phone_region"], "ID");
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Budi", "phone": "0812-3456-7890"})).await;
+    assert_eq!(contact["phone"], "+6281234567890");
+    assert_eq!(contact["phone_raw"], "0812-3456-7890");
+    create_contact(&app, &auth_header, json!({"first_name": "Andi", "phone": "+1 (415) 555-2671"})).await;
+
+    let (status, _) = send_json(&app, "POST", "/contacts", &auth_header, Some(json!({"first_name": "Bad", "phone": "abc"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    for filter in ["0812%203456", "%2B62-812-3456-7890", "81234"] {
+        let (_, page) = get_json(&app, &auth_header, &format!("/contacts?phone={}", filter)).await;
+        assert_eq!(page["total"], 1, "filter {}", filter);
+        assert_eq!(page["data"][0]["first_name"], "Budi");
+    }
+
+    // Numbers stored before normalization keep the spelling they were typed in.
+    let legacy = create_contact(&app, &auth_header, json!({"first_name": "Siti", "phone": "+62 813-1111-2222"})).await;
+    sqlx::query("UPDATE contact_phones SET phone = '0813 1111 2222' WHERE contact_id = $1")
+        .bind(Uuid::parse_str(legacy["id"].as_str().unwrap()).unwrap())
+        .execute(&pool)
+        .await
+        .unwrap();
+    for filter in ["0813-1111-2222", "%2B62%20813%201111%202222", "1111"] {
+        let (_, page) = get_json(&app, &auth_header, &format!("/contacts?phone={}", filter)).await;
+        assert_eq!(page["total"], 1, "filter {}", filter);
+        assert_eq!(page["data"][0]["first_name"], "Siti");
+    }
+
+    let (_, me) = send_json(&app, "PATCH", "/users/me", &auth_header, Some(json!({"phone_region": null}))).await;
+    assert!(me["phone_region"].is_null());
+}
+
+#[sqlx::test]
+async fn test_contact_emails_and_phones(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "methods@e.com").await;
+
+    let (status, problem) = send_json(&app, "POST", "/contacts", &auth_header, Some(json!({"first_name": "Budi", "email": "a@e.com", "emails": [{"email": "b@e.com"}]}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["email"].is_array());
+    let (status, problem) = send_json(&app, "POST", "/contacts", &auth_header, Some(json!({"first_name": "Budi", "phones": [{"phone": "+62 812 3456 7890"}, {"phone": "12"}]}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["phones[1].phone"].is_array());
+
+    let contact = create_contact(&app, &auth_header, json!({
+        "first_name": "Budi",
+        "emails": [
+            {"email": "budi@work.com", "label": "work"},
+            {"email": "budi@home.com", "label": "home", "primary": true}
+        ],
+        "phones": [{"phone": "+62 812 3456 7890", "label": "mobile"}]
+    })).await;
+    let contact_id = contact["id"].as_str().unwrap();
+    let uri = format!("/contacts/{}", contact_id);
+    assert_eq!(contact["email"], "budi@home.com");
+    assert_eq!(contact["emails"][1]["label"], "work");
+    assert_eq!(contact["emails"][1]["primary"], false);
+    assert_eq!(contact["phone"], "+6281234567890");
+    assert_eq!(contact["phones"][0]["label"], "mobile");
+    assert_eq!(contact["phones"][0]["primary"], true);
+
+    // Filters match any entry, not only the primary one
+    let (_, page) = get_json(&app, &auth_header, "/contacts?email=work.com").await;
+    assert_eq!(page["total"], 1);
+
+    // A new primary email demotes the old one
+    let (status, added) = send_json(&app, "POST", &format!("{}/emails", uri), &auth_header, Some(json!({"email": "budi@new.com", "primary": true}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(added["label"], "other");
+    let (_, emails) = get_json(&app, &auth_header, &format!("{}/emails", uri)).await;
+    let listed: Vec<&str> = emails.as_array().unwrap().iter().map(|e| e["email"].as_str().unwrap()).collect();
+    assert_eq!(listed, vec!["budi@new.com", "budi@work.com", "budi@home.com"]);
+    let email_uri = format!("{}/emails/{}", uri, added["id"].as_str().unwrap());
+    let (status, _) = send_json(&app, "PATCH", &email_uri, &auth_header, Some(json!({"primary": false}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    // Longer than the column holds, though a valid address
+    let long_email = format!("{}@{}.com", "b".repeat(60), "e".repeat(40));
+    let (status, problem) = send_json(&app, "POST", &format!("{}/emails", uri), &auth_header, Some(json!({"email": long_email}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(problem["errors"]["email"][0], "Email must be at most 100 characters");
+    let (status, problem) = send_json(&app, "PATCH", &email_uri, &auth_header, Some(json!({"email": long_email}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(problem["errors"]["email"][0], "Email must be at most 100 characters");
+
+    // Deleting the primary promotes the oldest remaining email
+    let (status, _) = send_json(&app, "DELETE", &email_uri, &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, current) = get_json(&app, &auth_header, &uri).await;
+    assert_eq!(current["email"], "budi@work.com");
+    assert_eq!(current["emails"][0]["primary"], true);
+
+    // The contact's own email field edits the primary entry
+    let (status, updated) = send_json(&app, "PUT", &uri, &auth_header, Some(json!({"email": "budi@office.com"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["emails"][0]["email"], "budi@office.com");
+    assert_eq!(updated["emails"][0]["label"], "work");
+
+    let (status, phone) = send_json(&app, "POST", &format!("{}/phones", uri), &auth_header, Some(json!({"phone": "+62 21 555 0100", "label": "work"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(phone["phone"], "+62215550100");
+    assert_eq!(phone["primary"], false);
+    let (status, problem) = send_json(&app, "POST", &format!("{}/phones", uri), &auth_header, Some(json!({"phone": "abc"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["phone"].is_array());
+
+    let (_, history) = get_json(&app, &auth_header, &format!("{}/history", uri)).await;
+    let actions: Vec<&str> = history.as_array().unwrap().iter().map(|v| v["action"].as_str().unwrap()).collect();
+    assert_eq!(actions, vec!["created", "email_created", "email_deleted", "updated", "phone_created"]);
+
+    // Reverting restores the entries of that version
+    let (status, reverted) = send_json(&app, "POST", &format!("{}/revert/1", uri), &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(reverted["email"], "budi@home.com");
+    assert_eq!(reverted["emails"].as_array().unwrap().len(), 2);
+    assert_eq!(reverted["emails"][1]["email"], "budi@work.com");
+    assert_eq!(reverted["phones"].as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn test_custom_fields(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "fields@e.com").await;
+
+    let (status, problem) = send_json(&app, "POST", "/custom-fields", &auth_header, Some(json!({"name": "Job Title", "type": "text"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["name"].is_array());
+    let (status, problem) = send_json(&app, "POST", "/custom-fields", &auth_header, Some(json!({"name": "tier", "type": "enum"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["enum_values"].is_array());
+
+    let (status, company) = send_json(&app, "POST", "/custom-fields", &auth_header, Some(json!({"name": "company", "type": "text", "required": true}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send_json(&app, "POST", "/custom-fields", &auth_header, Some(json!({"name": "tier", "type": "enum", "enum_values": ["gold", "silver"]}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send_json(&app, "POST", "/custom-fields", &auth_header, Some(json!({"name": "birthday", "type": "date"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send_json(&app, "POST", "/custom-fields", &auth_header, Some(json!({"name": "company", "type": "text"}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Values are checked against the definitions
+    let (status, problem) = send_json(&app, "POST", "/contacts", &auth_header, Some(json!({"first_name": "Budi", "custom_fields": {"tier": "bronze", "birthday": "1990-02-30"}}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(problem["errors"]["custom_fields.company"][0], "Field is required");
+    assert!(problem["errors"]["custom_fields.tier"].is_array());
+    assert!(problem["errors"]["custom_fields.birthday"].is_array());
+
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Budi", "custom_fields": {"company": "Acme", "tier": "gold"}})).await;
+    assert_eq!(contact["custom_fields"], json!({"company": "Acme", "tier": "gold"}));
+    create_contact(&app, &auth_header, json!({"first_name": "Sari", "custom_fields": {"company": "Initech", "tier": "silver"}})).await;
+    let uri = format!("/contacts/{}", contact["id"].as_str().unwrap());
+
+    // Updates merge into the current values; null removes one
+    let (status, updated) = send_json(&app, "PUT", &uri, &auth_header, Some(json!({"custom_fields": {"tier": null, "birthday": "1990-02-28"}}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["custom_fields"], json!({"company": "Acme", "birthday": "1990-02-28"}));
+    let (status, patched) = patch_contact(&app, &uri, &auth_header, "application/merge-patch+json", json!({"custom_fields": {"tier": "gold"}})).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(patched["custom_fields"]["tier"], "gold");
+    let (status, _) = patch_contact(&app, &uri, &auth_header, "application/merge-patch+json", json!({"custom_fields": {"company": null}})).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // Listing filters on exact values
+    let (_, page) = get_json(&app, &auth_header, "/contacts?custom_fields=%7B%22tier%22%3A%22gold%22%7D").await;
+    assert_eq!(page["total"], 1);
+    assert_eq!(page["data"][0]["first_name"], "Budi");
+    let (status, problem) = get_json(&app, &auth_header, "/contacts?custom_fields=%7B%22team%22%3A1%7D").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["custom_fields.team"].is_array());
+    let (status, _) = get_json(&app, &auth_header, "/contacts?custom_fields=gold").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    let (_, history) = get_json(&app, &auth_header, &format!("{}/history", uri)).await;
+    let fields: Vec<&str> = history[1]["changes"].as_array().unwrap().iter().map(|c| c["field"].as_str().unwrap()).collect();
+    assert_eq!(fields, vec!["custom_fields.birthday", "custom_fields.tier"]);
+
+    // Renaming a field renames the stored values; deleting one drops them
+    let field_uri = format!("/custom-fields/{}", company["id"].as_str().unwrap());
+    let (status, renamed) = send_json(&app, "PATCH", &field_uri, &auth_header, Some(json!({"name": "employer"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(renamed["type"], "text");
+    let (_, current) = get_json(&app, &auth_header, &uri).await;
+    assert_eq!(current["custom_fields"]["employer"], "Acme");
+    assert!(current["custom_fields"].get("company").is_none());
+    let (status, _) = send_json(&app, "DELETE", &field_uri, &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, current) = get_json(&app, &auth_header, &uri).await;
+    assert_eq!(current["custom_fields"], json!({"birthday": "1990-02-28", "tier": "gold"}));
+
+    let other_header = register_and_login(&app, "fields-other@e.com").await;
+    let (_, fields) = get_json(&app, &other_header, "/custom-fields").await;
+    assert_eq!(fields, json!([]));
+}
+
+#[sqlx::test]
+async fn test_address_kind_primary_and_validation(pool: PgPool) {
+    let app = create_app(pool).await;
+    let auth_header = register_and_login(&app, "addresses@e.com").await;
+    let contact = create_contact(&app, &auth_header, json!({"first_name": "Budi"})).await;
+    let base = format!("/contacts/{}/addresses", contact["id"].as_str().unwrap());
+
+    let (status, problem) = send_json(&app, "POST", &base, &auth_header, Some(json!({"country": "Indonesia"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["country"].is_array());
+    let (status, problem) = send_json(&app, "POST", &base, &auth_header, Some(json!({"country": "id", "postal_code": "4011"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["postal_code"].is_array());
+
+    // The first address is primary; codes are stored normalized
+    let (status, home) = send_json(&app, "POST", &base, &auth_header, Some(json!({"country": "id", "postal_code": " 40115 ", "kind": "home"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(home["country"], "ID");
+    assert_eq!(home["postal_code"], "40115");
+    assert_eq!(home["kind"], "home");
+    assert_eq!(home["primary"], true);
+    let home_uri = format!("{}/{}", base, home["id"].as_str().unwrap());
+
+    let (status, work) = send_json(&app, "POST", &base, &auth_header, Some(json!({"country": "GB", "postal_code": "sw1a1aa", "kind": "work", "primary": true}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(work["postal_code"], "SW1A 1AA");
+    let work_uri = format!("{}/{}", base, work["id"].as_str().unwrap());
+    let (_, list) = get_json(&app, &auth_header, &base).await;
+    let primaries: Vec<bool> = list.as_array().unwrap().iter().map(|a| a["primary"].as_bool().unwrap()).collect();
+    assert_eq!(primaries, vec![true, false]);
+    assert_eq!(list[0]["kind"], "work");
+
+    // The postal code is checked against the resulting country
+    let (status, problem) = send_json(&app, "PATCH", &work_uri, &auth_header, Some(json!({"country": "US"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["postal_code"].is_array());
+    let (status, _) = send_json(&app, "PATCH", &work_uri, &auth_header, Some(json!({"primary": false}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    let (status, replaced) = send_json(&app, "PUT", &work_uri, &auth_header, Some(json!({"country": "US", "postal_code": "94105"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replaced["primary"], true);
+    assert_eq!(replaced["kind"], "other");
+
+    // Deleting the primary promotes the remaining address
+    let (status, _) = send_json(&app, "DELETE", &work_uri, &auth_header, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (_, current) = get_json(&app, &auth_header, &home_uri).await;
+    assert_eq!(current["primary"], true);
+
+    // Merged addresses never take over the target's primary
+    let source = create_contact(&app, &auth_header, json!({"first_name": "Budi S"})).await;
+    let source_id = source["id"].as_str().unwrap();
+    send_json(&app, "POST", &format!("/contacts/{}/addresses", source_id), &auth_header, Some(json!({"country": "MY"}))).await;
+    let (status, merged) = send_json(&app, "POST", "/contacts/merge", &auth_header, Some(json!({"target_id": contact["id"], "source_ids": [source_id]}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(merged["addresses"][0]["country"], "ID");
+    assert_eq!(merged["addresses"][0]["primary"], true);
+    assert_eq!(merged["addresses"][1]["primary"], false);
+}
+
+#[sqlx::test]
+async fn test_contact_sharing(pool: PgPool) {
+    let app = create_app(pool).await;
+    let owner = register_and_login(&app, "owner@e.com").await;
+    let colleague = register_and_login(&app, "colleague@e.com").await;
+
+    let budi = create_contact(&app, &owner, json!({"first_name": "Budi"})).await;
+    let sari = create_contact(&app, &owner, json!({"first_name": "Sari"})).await;
+    let budi_uri = format!("/contacts/{}", budi["id"].as_str().unwrap());
+    let sari_uri = format!("/contacts/{}", sari["id"].as_str().unwrap());
+
+    let (status, _) = get_json(&app, &colleague, &budi_uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let shares_uri = format!("{}/shares", budi_uri);
+    let (status, problem) = send_json(&app, "POST", &shares_uri, &owner, Some(json!({"email": "nobody@e.com", "permission": "read"}))).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(problem["errors"]["email"].is_array());
+    let (status, share) = send_json(&app, "POST", &shares_uri, &owner, Some(json!({"email": "colleague@e.com", "permission": "read"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(share["grantee_email"], "colleague@e.com");
+    let (status, _) = send_json(&app, "POST", &shares_uri, &owner, Some(json!({"email": "colleague@e.com", "permission": "edit"}))).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    let (status, _) = get_json(&app, &colleague, &shares_uri).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Read shares allow reading only
+    let (status, shared) = get_json(&app, &colleague, &budi_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(shared["first_name"], "Budi");
+    let (status, _) = send_json(&app, "PUT", &budi_uri, &colleague, Some(json!({"last_name": "Santoso"}))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    let share_uri = format!("/shares/{}", share["id"].as_str().unwrap());
+    let (status, _) = send_json(&app, "PATCH", &share_uri, &colleague, Some(json!({"permission": "edit"}))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send_json(&app, "PATCH", &share_uri, &owner, Some(json!({"permission": "edit"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, updated) = send_json(&app, "PUT", &budi_uri, &colleague, Some(json!({"last_name": "Santoso"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["last_name"], "Santoso");
+
+    // Deleting stays with the owner
+    let (status, _) = send_json(&app, "DELETE", &budi_uri, &colleague, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // A group share reaches its members
+    let (_, group) = send_json(&app, "POST", "/groups", &owner, Some(json!({"name": "Team"}))).await;
+    let group_uri = format!("/groups/{}", group["id"].as_str().unwrap());
+    send_json(&app, "PUT", &format!("{}/contacts/{}", group_uri, sari["id"].as_str().unwrap()), &owner, None).await;
+    let (status, _) = get_json(&app, &colleague, &sari_uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = send_json(&app, "POST", &format!("{}/shares", group_uri), &owner, Some(json!({"email": "colleague@e.com", "permission": "read"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = get_json(&app, &colleague, &sari_uri).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, shared) = get_json(&app, &colleague, "/contacts/shared").await;
+    assert_eq!(status, StatusCode::OK);
+    let shared = shared.as_array().unwrap();
+    assert_eq!(shared.len(), 2);
+    assert_eq!(shared[0]["first_name"], "Budi");
+    assert_eq!(shared[0]["permission"], "edit");
+    assert_eq!(shared[1]["first_name"], "Sari");
+    assert_eq!(shared[1]["permission"], "read");
+    // Shared contacts stay out of the grantee's own list
+    let (_, own) = get_json(&app, &colleague, "/contacts").await;
+    assert_eq!(own["total"], 0);
+
+    // The grantee can give a share up
+    let (status, _) = send_json(&app, "DELETE", &share_uri, &colleague, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = get_json(&app, &colleague, &budi_uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn test_organization_contacts(pool: PgPool) {
+    let app = create_app(pool).await;
+    let owner = register_and_login(&app, "org-owner@e.com").await;
+    let member = register_and_login(&app, "org-member@e.com").await;
+    let outsider = register_and_login(&app, "org-outsider@e.com").await;
+
+    let (status, organization) = send_json(&app, "POST", "/organizations", &owner, Some(json!({"name": "Acme"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(organization["role"], "owner");
+    let organization_id = organization["id"].as_str().unwrap();
+    let members_uri = format!("/organizations/{}/members", organization_id);
+    let (status, added) = send_json(&app, "POST", &members_uri, &owner, Some(json!({"email": "org-member@e.com"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(added["role"], "member");
+
+    // Members cannot manage the organization
+    let (status, _) = send_json(&app, "POST", &members_uri, &member, Some(json!({"email": "org-outsider@e.com"}))).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send_json(&app, "DELETE", &format!("/organizations/{}", organization_id), &member, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Contacts created in the organization belong to it, not to the creator
+    let (status, budi) = send_in_organization(&app, "POST", "/contacts", &owner, organization_id, Some(json!({"first_name": "Budi"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    assert_eq!(budi["organization_id"], organization_id);
+    create_contact(&app, &owner, json!({"first_name": "Personal"})).await;
+    let budi_uri = format!("/contacts/{}", budi["id"].as_str().unwrap());
+
+    let (_, listed) = send_in_organization(&app, "GET", "/contacts", &member, organization_id, None).await;
+    assert_eq!(listed["total"], 1);
+    assert_eq!(listed["data"][0]["first_name"], "Budi");
+    let (status, updated) = send_in_organization(&app, "PUT", &budi_uri, &member, organization_id, Some(json!({"last_name": "Santoso"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(updated["last_name"], "Santoso");
+
+    // Each address book only sees its own contacts
+    let (_, personal) = get_json(&app, &owner, "/contacts").await;
+    assert_eq!(personal["total"], 1);
+    assert_eq!(personal["data"][0]["first_name"], "Personal");
+    let (status, _) = get_json(&app, &owner, &budi_uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+    let (status, _) = get_json(&app, &outsider, &budi_uri).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    // Selecting an organization requires membership
+    let (status, _) = send_in_organization(&app, "GET", "/contacts", &outsider, organization_id, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    let (status, _) = send_in_organization(&app, "GET", "/contacts", &outsider, "not-a-uuid", None).await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+
+    // The last owner has to stay
+    let (_, me) = get_json(&app, &owner, "/users/me").await;
+    let (status, _) = send_json(&app, "DELETE", &format!("{}/{}", members_uri, me["id"].as_str().unwrap()), &owner, None).await;
+    assert_eq!(status, StatusCode::CONFLICT);
+
+    // Leaving ends access
+    let member_uri = format!("{}/{}", members_uri, added["user_id"].as_str().unwrap());
+    let (status, _) = send_json(&app, "DELETE", &member_uri, &member, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (status, _) = send_in_organization(&app, "GET", &budi_uri, &member, organization_id, None).await;
+    assert_eq!(status, StatusCode::FORBIDDEN);
+
+    // Deleting the organization deletes its contacts
+    let (status, _) = send_json(&app, "DELETE", &format!("/organizations/{}", organization_id), &owner, None).await;
+    assert_eq!(status, StatusCode::NO_CONTENT);
+    let (_, organizations) = get_json(&app, &owner, "/organizations").await;
+    assert_eq!(organizations, json!([]));
+}
+
+#[sqlx::test]
+async fn test_row_level_security(pool: PgPool) {
+    let app = create_app(pool.clone()).await;
+    let owner = register_and_login(&app, "rls-owner@e.com").await;
+    let reader = register_and_login(&app, "rls-reader@e.com").await;
+    let intruder = register_and_login(&app, "rls-intruder@e.com").await;
+    let editor = register_and_login(&app, "rls-editor@e.com").await;
+    let user_id = |me: Value| me["id"].as_str().unwrap().parse::<Uuid>().unwrap();
+    let owner_id = user_id(get_json(&app, &owner, "/users/me").await.1);
+    let reader_id = user_id(get_json(&app, &reader, "/users/me").await.1);
+    let intruder_id = user_id(get_json(&app, &intruder, "/users/me").await.1);
+    let editor_id = user_id(get_json(&app, &editor, "/users/me").await.1);
+
+    let contact = create_contact(&app, &owner, json!({"first_name": "Budi", "email": "budi@e.com", "phone": "+62 812 3456 7890"})).await;
+    let contact_uri = format!("/contacts/{}", contact["id"].as_str().unwrap());
+    let contact_id: Uuid = contact["id"].as_str().unwrap().parse().unwrap();
+    let (_, address) = send_json(&app, "POST", &format!("{}/addresses", contact_uri), &owner, Some(json!({"city": "Bandung", "country": "ID"}))).await;
+    let address_id: Uuid = address["id"].as_str().unwrap().parse().unwrap();
+    let (status, _) = send_json(&app, "POST", &format!("{}/shares", contact_uri), &owner, Some(json!({"email": "rls-reader@e.com", "permission": "read"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let (status, _) = send_json(&app, "POST", &format!("{}/shares", contact_uri), &owner, Some(json!({"email": "rls-editor@e.com", "permission": "edit"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+
+    // Talk to the repository directly, as a usecase that forgot its ownership
+    // check would: only the database stands in the way.
+    let repo = PostgresContactRepository::new(pool.clone());
+    let stored: Contact = repo.find_contact_by_id(&Tenant::personal(owner_id), &contact_id).await.unwrap().unwrap();
+    let stored_address: Address = repo.find_address_by_id(&Tenant::personal(owner_id), &address_id).await.unwrap().unwrap();
+    let intruder = Tenant::personal(intruder_id);
+
+    assert!(repo.find_address_by_id(&intruder, &address_id).await.unwrap().is_none());
+    assert!(repo.find_addresses_by_contact_id(&intruder, &contact_id).await.unwrap().is_empty());
+    let email_id = contact["emails"][0]["id"].as_str().unwrap().parse().unwrap();
+    let phone_id = contact["phones"][0]["id"].as_str().unwrap().parse().unwrap();
+    assert!(repo.find_email_by_id(&intruder, &email_id).await.unwrap().is_none());
+    assert!(repo.find_emails_by_contact_ids(&intruder, &[contact_id]).await.unwrap().is_empty());
+    assert!(repo.find_phone_by_id(&intruder, &phone_id).await.unwrap().is_none());
+    assert!(repo.find_phones_by_contact_ids(&intruder, &[contact_id]).await.unwrap().is_empty());
+    let renamed = Contact { first_name: "Hacked".to_string(), ..stored.clone() };
+    assert!(repo.update_contact(&intruder, &renamed, &ContactChanges::default(), ContactChange::Updated).await.is_err());
+    assert!(repo.trash_contact(&intruder, &contact_id, stored.version, chrono::Utc::now()).await.is_err());
+    let planted = ContactChanges {
+        addresses: vec![Address { id: Uuid::new_v4(), ..stored_address.clone() }],
+        ..Default::default()
+    };
+    assert!(repo.update_contact(&intruder, &stored, &planted, ContactChange::AddressCreated).await.is_err());
+    // Deletes of invisible rows match nothing.
+    repo.delete_contact(&intruder, &contact_id).await.unwrap();
+    // Nor can contacts be created in someone else's name.
+    let forged = Contact { id: Uuid::new_v4(), ..stored.clone() };
+    assert!(matches!(repo.create_contact(&intruder, &forged, &ContactChanges::default()).await, Err(DomainError::Forbidden(_))));
+
+    // A read-only share lets the grantee see the contact, not change it.
+    let reader = Tenant::personal(reader_id);
+    assert_eq!(repo.find_addresses_by_contact_id(&reader, &contact_id).await.unwrap().len(), 1);
+    assert!(repo.find_email_by_id(&reader, &email_id).await.unwrap().is_some());
+    assert_eq!(repo.find_phones_by_contact_ids(&reader, &[contact_id]).await.unwrap().len(), 1);
+    let removed = ContactChanges {
+        deleted_addresses: vec![address_id],
+        deleted_emails: vec![email_id],
+        ..Default::default()
+    };
+    assert!(repo.update_contact(&reader, &stored, &removed, ContactChange::AddressDeleted).await.is_err());
+
+    // An edit share lets the grantee change the contact, not take it over.
+    let editor = Tenant::personal(editor_id);
+    let taken = Contact { user_id: editor_id, ..stored.clone() };
+    assert!(repo.update_contact(&editor, &taken, &ContactChanges::default(), ContactChange::Updated).await.is_err());
+    let mut tx = pool.begin().await.unwrap();
+    sqlx::query("SELECT set_config('role', 'contacts_app', true), set_config('app.current_user_id', $1, true)")
+        .bind(editor_id.to_string())
+        .execute(&mut *tx)
+        .await
+        .unwrap();
+    let moved = sqlx::query("UPDATE contacts SET user_id = $1 WHERE id = $2")
+        .bind(editor_id)
+        .bind(contact_id)
+        .execute(&mut *tx)
+        .await;
+    assert!(matches!(DomainError::from(moved.unwrap_err()), DomainError::Forbidden(_)));
+    drop(tx);
+    assert_eq!(repo.find_contact_by_id(&editor, &contact_id).await.unwrap().unwrap().user_id, owner_id);
+
+    let (status, after) = get_json(&app, &owner, &contact_uri).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(after["first_name"], "Budi");
+    assert_eq!(after["version"], stored.version);
+    assert_eq!(after["addresses"].as_array().unwrap().len(), 1);
+    assert_eq!(after["addresses"][0]["city"], "Bandung");
+    assert_eq!(after["emails"].as_array().unwrap().len(), 1);
+    let (_, owned) = get_json(&app, &owner, "/contacts").await;
+    assert_eq!(owned["total"], 1);
+}
+
+async fn send_json(app: &axum::Router, method: &str, uri: &str, auth_header: &str, payload: Option<Value>) -> (StatusCode, Value) {
+    let builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Authorization", auth_header);
+    let request = match payload {
+        Some(payload) => builder
+            .header("content-type", "application/json")
+            .body(Body::from(payload.to_string()))
+            .unwrap(),
+        None => builder.body(Body::empty()).unwrap(),
+    };
+
+    let res = app.clone().oneshot(request).await.unwrap();
+    let status = res.status();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+async fn send_in_organization(
+    app: &axum::Router,
+    method: &str,
+    uri: &str,
+    auth_header: &str,
+    organization_id: &str,
+    payload: Option<Value>,
+) -> (StatusCode, Value) {
+    let builder = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("Authorization", auth_header)
+        .header("X-Organization-Id", organization_id);
+    let request = match payload {
+        Some(payload) => builder
+            .header("content-type", "application/json")
+            .body(Body::from(payload.to_string()))
+            .unwrap(),
+        None => builder.body(Body::empty()).unwrap(),
+    };
+
+    let res = app.clone().oneshot(request).await.unwrap();
+    let status = res.status();
+    let body = res.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+}
+
+#[sqlx::test]
+async fn test_address_crud(pool: PgPool) {
+    let app = create_app(pool).await;
+    let owner = register_and_login(&app, "addr@e.com").await;
+    let other = register_and_login(&app, "addr-other@e.com").await;
+
+    let contact = create_contact(&app, &owner, json!({"first_name": "Contact"})).await;
+    let contact_id = contact["id"].as_str().unwrap();
+    let base = format!("/contacts/{}/addresses", contact_id);
+
+    let (status, address) = send_json(&app, "POST", &base, &owner, Some(json!({"country": "ID", "city": "Jakarta", "street": "Jl. Sudirman"}))).await;
+    assert_eq!(status, StatusCode::CREATED);
+    let address_uri = format!("{}/{}", base, address["id"].as_str().unwrap());
+
+    let (status, list) = send_json(&app, "GET", &base, &owner, None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(list.as_array().unwrap().len(), 1);
+
+    // PATCH only touches the given fields
+    let (status, patched) = send_json(&app, "PATCH", &address_uri, &owner, Some(json!({"city": "Bandung"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(patched["city"], "Bandung");
+    assert_eq!(patched["street"], "Jl. Sudirman");
+
+    // PUT replaces the whole address
+    let (status, replaced) = send_json(&app, "PUT", &address_uri, &owner, Some(json!({"country": "MY"}))).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(replaced["country"], "MY");
+    assert!(replaced["street"].is_null());
+
+    let (status, _) = send_json(&app, "GET", &address_uri, &other, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+
+    let (status, _) = send_json(&app, "DELETE", &address_uri, &owner, None).await;
+    assert_eq!(status, StatusCode::OK);
+    let (status, _) = send_json(&app, "GET", &address_uri, &owner, None).await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}